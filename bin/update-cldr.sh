@@ -0,0 +1,58 @@
+#!/usr/bin/env bash
+
+# Replaces the CLDR files under chrono-tz/cldr with the complete ones of a CLDR
+# release, and records its version in chrono-tz/cldr/VERSION.
+
+set -euxo pipefail
+
+CLDR_VERSION="${1:-46}"
+
+cd "$(dirname "$0")/../chrono-tz/cldr"
+
+tmp="$(mktemp -d)"
+trap 'rm -rf "$tmp"' EXIT
+
+curl -fsSL "https://unicode.org/Public/cldr/${CLDR_VERSION}/core.zip" -o "$tmp/core.zip"
+unzip -q "$tmp/core.zip" \
+    'common/supplemental/metaZones.xml' \
+    'common/supplemental/windowsZones.xml' \
+    'common/main/*.xml' \
+    -d "$tmp"
+
+cp "$tmp/common/supplemental/metaZones.xml" "$tmp/common/supplemental/windowsZones.xml" .
+
+# Only the long names of zones and metazones are read when building, so the
+# rest of each locale is left out to keep the package small. Locales without
+# any are skipped. Which of the others are compiled in is left to
+# CHRONO_TZ_LOCALES.
+trim_locale='
+NR == 1, /<ldml>/ { print; next }
+/<identity>/, /<\/identity>/ { print; next }
+/<timeZoneNames>/ { names = 1; next }
+/<\/timeZoneNames>/ { names = 0; next }
+!names { next }
+/<(zone|metazone) / { element = $0 "\n"; long = ""; next }
+/<\/(zone|metazone)>/ {
+    if (long != "") body = body element long $0 "\n"
+    next
+}
+/<long>/ { in_long = 1 }
+in_long {
+    long = long $0 "\n"
+    if (/<\/long>/) in_long = 0
+}
+END {
+    if (body == "") exit 1
+    printf "\t<dates>\n\t\t<timeZoneNames>\n%s\t\t</timeZoneNames>\n\t</dates>\n</ldml>\n", body
+}
+'
+
+rm -rf main
+mkdir main
+set +x
+for file in "$tmp"/common/main/*.xml; do
+    name="$(basename "$file")"
+    awk "$trim_locale" "$file" > "main/$name" || rm "main/$name"
+done
+set -x
+echo "$CLDR_VERSION" > VERSION
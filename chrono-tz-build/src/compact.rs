@@ -0,0 +1,306 @@
+//! Writing the compact transition tables of the zones, which `chrono-tz`
+//! decodes in its `compact` module.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{self, Write};
+
+use parse_zoneinfo::posix::{PosixDate, PosixTransition, PosixTz, TablePosix};
+use parse_zoneinfo::table::Table;
+use parse_zoneinfo::transitions::{FixedTimespan, TableTransitions};
+
+use crate::convert_bad_chars;
+
+/// The log2 of the width in seconds of the buckets of the lookup index, about a
+/// year. This must match `BUCKET_SHIFT` in `chrono-tz`.
+#[cfg(feature = "lookup-index")]
+const BUCKET_SHIFT: u32 = 25;
+
+/// The log2 of the number of transitions between the checkpoints of a table,
+/// which are closer together with the lookup index. This must match
+/// `CHECKPOINT_SHIFT` in `chrono-tz`.
+#[cfg(feature = "lookup-index")]
+const CHECKPOINT_SHIFT: u32 = 3;
+#[cfg(not(feature = "lookup-index"))]
+const CHECKPOINT_SHIFT: u32 = 5;
+
+/// Writes the shared tables of the zones and the abbreviations they use, and
+/// implements `TimeSpans` for `Tz` with them.
+pub(crate) fn write_timespans(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+) -> io::Result<()> {
+    let timespans = zones
+        .iter()
+        .map(|zone| (*zone, table.timespans(zone).unwrap()))
+        .collect::<Vec<_>>();
+    let pool = AbbreviationPool::new(timespans.iter().flat_map(|(_, timespans)| {
+        std::iter::once(&timespans.first).chain(timespans.rest.iter().map(|(_, t)| t))
+    }));
+
+    // Zones with the same history, which includes links and their targets, share a table.
+    let mut tables = BTreeMap::<String, Vec<&str>>::new();
+    let mut zone_tables = Vec::new();
+    for (zone, timespans) in &timespans {
+        let rule = format_rule(table.posix_tz(zone), &timespans.rest);
+        let entry = format_table(&pool, &timespans.first, &timespans.rest, &rule);
+        tables.entry(entry.clone()).or_default().push(zone);
+        zone_tables.push((zone, entry));
+    }
+    let indices = tables
+        .keys()
+        .enumerate()
+        .map(|(index, entry)| (entry, index))
+        .collect::<BTreeMap<_, _>>();
+
+    writeln!(
+        timezone_file,
+        "/// The abbreviations of all timespans, which `CompactTimespan`s are slices of.
+pub(crate) static ABBREVIATION_POOL: &str = {:?};\n",
+        pool.pool
+    )?;
+    writeln!(
+        timezone_file,
+        "static TABLES: [CompactTable; {}] = [",
+        tables.len()
+    )?;
+    for (entry, zones) in &tables {
+        writeln!(timezone_file, "    // {}", zones.join(", "))?;
+        writeln!(timezone_file, "{}", entry)?;
+    }
+    writeln!(timezone_file, "];\n")?;
+    writeln!(
+        timezone_file,
+        "impl TimeSpans for Tz {{
+    fn timespans(&self) -> FixedTimespanSet<'static> {{
+        let index = match *self {{"
+    )?;
+    for (zone, entry) in &zone_tables {
+        writeln!(
+            timezone_file,
+            "            Tz::{} => {},",
+            convert_bad_chars(zone),
+            indices[entry]
+        )?;
+    }
+    writeln!(
+        timezone_file,
+        "        }};
+        TABLES[index].timespans()
+    }}
+}}\n"
+    )
+}
+
+/// The names of all timespans, concatenated so that each is a slice of the pool.
+struct AbbreviationPool {
+    pool: String,
+    /// The start of each name in the pool.
+    starts: BTreeMap<String, usize>,
+}
+
+impl AbbreviationPool {
+    fn new<'a, I: Iterator<Item = &'a FixedTimespan>>(timespans: I) -> AbbreviationPool {
+        let names = timespans
+            .map(|timespan| &timespan.name)
+            .filter(|name| *name != "%z")
+            .collect::<BTreeSet<_>>();
+        // Names that are part of longer ones, such as "EST" of "AEST", are not repeated.
+        let mut names = names.into_iter().collect::<Vec<_>>();
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let mut pool = String::new();
+        let mut starts = BTreeMap::new();
+        for name in names {
+            let start = match pool.find(name.as_str()) {
+                Some(start) => start,
+                None => {
+                    pool.push_str(name);
+                    pool.len() - name.len()
+                }
+            };
+            starts.insert(name.clone(), start);
+        }
+        assert!(
+            pool.len() <= usize::from(u16::MAX),
+            "abbreviation pool too large"
+        );
+        AbbreviationPool { pool, starts }
+    }
+
+    /// Formats a timespan with its name in the pool.
+    fn format(&self, timespan: &FixedTimespan) -> String {
+        let (start, len) = match timespan.name.as_str() {
+            "%z" => (0, 0),
+            name => (self.starts[name], name.len()),
+        };
+        format!(
+            "CompactTimespan {{ utc_offset: {}, dst_offset: {}, name: {}, name_len: {} }}",
+            timespan.utc_offset,
+            timespan.dst_offset,
+            start,
+            u8::try_from(len).expect("abbreviation too long"),
+        )
+    }
+}
+
+/// Formats the table of a zone, with its transition times as the seconds from
+/// each to the next one, and its timespans as indices into the distinct ones.
+fn format_table(
+    pool: &AbbreviationPool,
+    first: &FixedTimespan,
+    rest: &[(i64, FixedTimespan)],
+    rule: &str,
+) -> String {
+    let mut timespans = Vec::new();
+    let mut kind = |timespan: &FixedTimespan| {
+        let formatted = pool.format(timespan);
+        let index = match timespans.iter().position(|t| *t == formatted) {
+            Some(index) => index,
+            None => {
+                timespans.push(formatted);
+                timespans.len() - 1
+            }
+        };
+        u8::try_from(index).expect("too many distinct timespans in a zone")
+    };
+    let first = kind(first);
+    let kinds = rest
+        .iter()
+        .map(|(_, timespan)| kind(timespan).to_string())
+        .collect::<Vec<_>>();
+    let deltas = rest
+        .windows(2)
+        .map(|pair| {
+            let delta = u32::try_from(pair[1].0 - pair[0].0).expect("transitions too far apart");
+            delta.to_string()
+        })
+        .collect::<Vec<_>>();
+    let start = rest.first().map_or(0, |&(time, _)| time);
+    let end = rest.last().map_or(0, |&(time, _)| time);
+    format!(
+        "    CompactTable {{
+        timespans: &[
+{timespans}        ],
+        first: {first},
+        start: {start},
+        end: {end},
+        deltas: &[{deltas}],
+        kinds: &[{kinds}],
+        rule: {rule},{index}
+        checkpoints: &[{checkpoints}],
+    }},",
+        timespans = timespans
+            .iter()
+            .map(|timespan| format!("            {},\n", timespan))
+            .collect::<String>(),
+        first = first,
+        start = start,
+        end = end,
+        deltas = deltas.join(", "),
+        kinds = kinds.join(", "),
+        rule = rule,
+        index = format_index(rest),
+        checkpoints = rest
+            .iter()
+            .step_by(1 << CHECKPOINT_SHIFT)
+            .map(|(time, _)| time.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Formats the lookup index of a table: the number of transitions before each
+/// bucket.
+#[cfg(feature = "lookup-index")]
+fn format_index(rest: &[(i64, FixedTimespan)]) -> String {
+    let (start, end) = match (rest.first(), rest.last()) {
+        (Some(&(start, _)), Some(&(end, _))) => (start, end),
+        _ => (0, 0),
+    };
+    let buckets = (0..=(end - start) >> BUCKET_SHIFT)
+        .map(|bucket| {
+            let time = start + (bucket << BUCKET_SHIFT);
+            let count = rest.partition_point(|&(transition, _)| transition < time);
+            u16::try_from(count)
+                .expect("too many transitions in a zone")
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "
+        buckets: &[{}],",
+        buckets.join(", "),
+    )
+}
+
+#[cfg(not(feature = "lookup-index"))]
+fn format_index(_rest: &[(i64, FixedTimespan)]) -> String {
+    String::new()
+}
+
+// Format the rule that continues after the last transition, like the TZ string footer of a TZif
+// file. Zones that don't observe DST anymore have no rule, as their last timespan lasts forever.
+fn format_rule(posix_tz: Option<PosixTz>, rest: &[(i64, FixedTimespan)]) -> String {
+    let (posix_tz, dst) = match posix_tz {
+        Some(PosixTz {
+            dst: Some(ref dst), ..
+        }) => (posix_tz.as_ref().unwrap(), dst),
+        _ => return "None".to_string(),
+    };
+    // The abbreviations are named like the last timespans with the same offsets, so that the
+    // ones formatted from "%z" stay unnamed.
+    let name = |utc_offset: i64, dst_offset: i64, abbreviation: &str| {
+        let timespan = rest
+            .iter()
+            .rev()
+            .map(|(_, timespan)| timespan)
+            .find(|t| t.utc_offset == utc_offset && t.dst_offset == dst_offset);
+        match timespan {
+            Some(timespan) if timespan.name == "%z" => None,
+            Some(timespan) => Some(timespan.name.clone()),
+            None => Some(abbreviation.to_string()),
+        }
+    };
+    let transition = |transition: &PosixTransition| {
+        let date = match transition.date {
+            PosixDate::Julian(day) => format!("RuleDate::Julian({})", day),
+            PosixDate::Ordinal(day) => format!("RuleDate::Ordinal({})", day),
+            PosixDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => format!(
+                "RuleDate::MonthWeekday {{ month: {}, week: {}, weekday: {} }}",
+                month, week, weekday
+            ),
+        };
+        format!(
+            "RuleTransition {{ date: {}, time: {} }}",
+            date, transition.time
+        )
+    };
+    format!(
+        "Some(TrailingRule {{
+                        std: FixedTimespan {{ utc_offset: {std}, dst_offset: 0, name: {std_name:?} }},
+                        dst: FixedTimespan {{ utc_offset: {std}, dst_offset: {save}, name: {dst_name:?} }},
+                        rule: DstRule {{
+                            std_offset: {std},
+                            dst_offset: {dst},
+                            start: {start},
+                            end: {end},
+                        }},
+                    }})",
+        std = posix_tz.std_offset,
+        std_name = name(posix_tz.std_offset, 0, &posix_tz.std_abbreviation),
+        save = dst.utc_offset - posix_tz.std_offset,
+        dst_name = name(
+            posix_tz.std_offset,
+            dst.utc_offset - posix_tz.std_offset,
+            &dst.abbreviation
+        ),
+        dst = dst.utc_offset,
+        start = transition(&dst.start),
+        end = transition(&dst.end),
+    )
+}
@@ -2,18 +2,30 @@ extern crate parse_zoneinfo;
 #[cfg(feature = "filter-by-regex")]
 extern crate regex;
 
-use std::collections::BTreeSet;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use parse_zoneinfo::line::{Line, LineParser};
+use parse_zoneinfo::posix::TablePosix;
 use parse_zoneinfo::structure::{Child, Structure};
 use parse_zoneinfo::table::{Table, TableBuilder};
-use parse_zoneinfo::transitions::FixedTimespan;
 use parse_zoneinfo::transitions::TableTransitions;
 
+mod compact;
+mod names;
+mod tab;
+mod windows;
+mod xml;
+
+use compact::write_timespans;
+use names::ZoneNames;
+use tab::ZoneTables;
+use windows::WindowsZones;
+
 /// The name of the environment variable which possibly holds the filter regex.
 const FILTER_ENV_VAR_NAME: &str = "CHRONO_TZ_TIMEZONE_FILTER";
 
@@ -28,37 +40,6 @@ fn strip_comments(mut line: String) -> String {
     line
 }
 
-// Generate a list of the time zone periods beyond the first that apply
-// to this zone, as a string representation of a static slice.
-fn format_rest(rest: Vec<(i64, FixedTimespan)>) -> String {
-    let mut ret = "&[\n".to_string();
-    for (
-        start,
-        FixedTimespan {
-            utc_offset,
-            dst_offset,
-            name,
-        },
-    ) in rest
-    {
-        let timespan_name = match name.as_ref() {
-            "%z" => None,
-            name => Some(name),
-        };
-        ret.push_str(&format!(
-            "                    ({start}, FixedTimespan {{ \
-             utc_offset: {utc}, dst_offset: {dst}, name: {name:?} \
-             }}),\n",
-            start = start,
-            utc = utc_offset,
-            dst = dst_offset,
-            name = timespan_name,
-        ));
-    }
-    ret.push_str("                ]");
-    ret
-}
-
 // Convert all '/' to '__', all '+' to 'Plus' and '-' to 'Minus', unless
 // it's a hyphen, in which case remove it. This is so the names can be used
 // as rust identifiers.
@@ -83,7 +64,13 @@ fn convert_bad_chars(name: &str) -> String {
 // The timezone file contains impls of `Timespans` for all timezones in the
 // database. The `Wrap` wrapper in the `timezone_impl` module then implements
 // TimeZone for any contained struct that implements `Timespans`.
-fn write_timezone_file(timezone_file: &mut File, table: &Table) -> io::Result<()> {
+fn write_timezone_file(
+    timezone_file: &mut File,
+    table: &Table,
+    zone_tables: &ZoneTables,
+    windows_zones: &WindowsZones,
+    zone_names: &ZoneNames,
+) -> io::Result<()> {
     let zones = table
         .zonesets
         .keys()
@@ -96,7 +83,16 @@ fn write_timezone_file(timezone_file: &mut File, table: &Table) -> io::Result<()
     writeln!(timezone_file, "use core::str::FromStr;\n",)?;
     writeln!(
         timezone_file,
-        "use crate::timezone_impl::{{TimeSpans, FixedTimespanSet, FixedTimespan}};\n",
+        "use crate::location::Location;
+use crate::names::{{LocaleNames, ZoneNames}};
+use crate::windows::WindowsZone;
+use crate::compact::{{CompactTable, CompactTimespan}};
+use crate::timezone_impl::{{TimeSpans, FixedTimespanSet}};
+// Only the rules of zones that still observe DST use these, and filtering may leave none.
+#[allow(unused_imports)]
+use crate::posix::{{DstRule, RuleDate, RuleTransition}};
+#[allow(unused_imports)]
+use crate::timezone_impl::{{FixedTimespan, TrailingRule}};\n",
     )?;
     writeln!(
         timezone_file,
@@ -167,7 +163,7 @@ impl std::error::Error for ParseError {{}}
 impl FromStr for Tz {{
     type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {{
-        return TIMEZONES.get(s).cloned().ok_or(ParseError(()));
+        TIMEZONES.get(s).cloned().ok_or(ParseError(()))
     }}
 }}
 "#
@@ -202,7 +198,7 @@ impl FromStr for Tz {{
     #[cfg(feature = "case-insensitive")]
     /// Parses a timezone string in a case-insensitive way
     pub fn from_str_insensitive(s: &str) -> Result<Self, ParseError> {{
-        return TIMEZONES_UNCASED.get(s.into()).cloned().ok_or(ParseError(()));
+        TIMEZONES_UNCASED.get(s.into()).cloned().ok_or(ParseError(()))
     }}"#
         )?;
     }
@@ -225,42 +221,36 @@ impl FromStr for Tz {{
     }}
 }}\n"
     )?;
+    write_timespans(timezone_file, table, &zones)?;
+    write_link_methods(timezone_file, table, &zones)?;
+    write_abbreviations(timezone_file, table, &zones)?;
+    zone_tables.write(timezone_file, table, &zones)?;
+    windows_zones.write(timezone_file, table, &zones)?;
+    zone_names.write(timezone_file, table, &zones)?;
     writeln!(
         timezone_file,
-        "impl TimeSpans for Tz {{
-    fn timespans(&self) -> FixedTimespanSet {{
-        match *self {{"
+        "#[cfg(feature = \"tzif-writer\")]
+impl Tz {{
+    /// The TZ string describing this zone after its last transition, or an
+    /// empty string if it can't be described by one.
+    pub(crate) fn posix_tz(self) -> &'static str {{
+        match self {{"
     )?;
     for zone in &zones {
-        let timespans = table.timespans(zone).unwrap();
-        let zone_name = convert_bad_chars(zone);
-        let timespan_name = match timespans.first.name.as_ref() {
-            "%z" => None,
-            name => Some(name),
-        };
+        let posix_tz = table
+            .posix_tz(zone)
+            .map(|tz| tz.to_string())
+            .unwrap_or_default();
         writeln!(
             timezone_file,
-            "            Tz::{zone} => {{
-                const REST: &[(i64, FixedTimespan)] = {rest};
-                FixedTimespanSet {{
-                    first: FixedTimespan {{
-                        utc_offset: {utc},
-                        dst_offset: {dst},
-                        name: {name:?},
-                    }},
-                    rest: REST
-                }}
-            }},\n",
-            zone = zone_name,
-            rest = format_rest(timespans.rest),
-            utc = timespans.first.utc_offset,
-            dst = timespans.first.dst_offset,
-            name = timespan_name,
+            "            Tz::{zone} => {posix_tz:?},",
+            zone = convert_bad_chars(zone),
+            posix_tz = posix_tz,
         )?;
     }
-    write!(
+    writeln!(
         timezone_file,
-        "         }}
+        "        }}
     }}
 }}\n"
     )?;
@@ -289,6 +279,185 @@ pub static TZ_VARIANTS: [Tz; {num}] = [
     Ok(())
 }
 
+// Returns the zone that a link ultimately points to, or the name itself if it
+// is not a link. Links whose target was filtered out are treated as zones.
+fn canonical_name<'a>(table: &'a Table, zones: &BTreeSet<&String>, mut name: &'a str) -> &'a str {
+    while let Some(target) = table.links.get(name) {
+        if !zones.contains(target) {
+            break;
+        }
+        name = target;
+    }
+    name
+}
+
+// Returns the CLDR entry of a zone. CLDR keeps the identifiers zones had when it first listed
+// them, some of which have since become links, so zones that are not listed themselves take the
+// entry of the zone they point to, or of another link to it.
+fn cldr_entry<'a, K: Borrow<str> + Ord, T>(
+    table: &Table,
+    zones: &BTreeSet<&String>,
+    entries: &'a BTreeMap<K, T>,
+    zone: &str,
+) -> Option<&'a T> {
+    let canonical = canonical_name(table, zones, zone);
+    entries
+        .get(zone)
+        .or_else(|| entries.get(canonical))
+        .or_else(|| {
+            entries
+                .iter()
+                .find(|(name, _)| canonical_name(table, zones, (*name).borrow()) == canonical)
+                .map(|(_, entry)| entry)
+        })
+}
+
+fn write_link_methods(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+) -> io::Result<()> {
+    let mut groups = BTreeMap::<&str, Vec<&str>>::new();
+    for zone in zones {
+        groups
+            .entry(canonical_name(table, zones, zone))
+            .or_default()
+            .push(zone);
+    }
+    writeln!(
+        timezone_file,
+        "impl Tz {{
+    /// Returns the zone this one is a link to, or the zone itself if it is not a
+    /// link.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert_eq!(Tz::US__Pacific.canonical(), Tz::America__Los_Angeles);
+    /// assert_eq!(Tz::America__Los_Angeles.canonical(), Tz::America__Los_Angeles);
+    /// ```
+    pub fn canonical(self) -> Tz {{
+        match self {{"
+    )?;
+    for zone in zones {
+        let canonical = canonical_name(table, zones, zone);
+        if canonical != zone.as_str() {
+            writeln!(
+                timezone_file,
+                "            Tz::{zone} => Tz::{canonical},",
+                zone = convert_bad_chars(zone),
+                canonical = convert_bad_chars(canonical),
+            )?;
+        }
+    }
+    writeln!(
+        timezone_file,
+        "            _ => self,
+        }}
+    }}
+
+    /// Returns whether this zone is a link to another one, which it is
+    /// identical to.
+    pub fn is_link(self) -> bool {{
+        self.canonical() != self
+    }}
+
+    /// Returns the other names of this zone: the zone it is a link to, if any,
+    /// and all other links to that zone.
+    ///
+    /// The canonical zone comes first, followed by the links in alphabetical
+    /// order.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert!(Tz::America__Los_Angeles.aliases().contains(&Tz::US__Pacific));
+    /// assert_eq!(Tz::US__Pacific.aliases()[0], Tz::America__Los_Angeles);
+    /// ```
+    pub fn aliases(self) -> &'static [Tz] {{
+        match self {{"
+    )?;
+    for (canonical, names) in &groups {
+        if names.len() < 2 {
+            continue;
+        }
+        let ordered = std::iter::once(*canonical)
+            .chain(names.iter().copied().filter(|name| name != canonical));
+        let ordered = ordered.collect::<Vec<_>>();
+        for zone in &ordered {
+            let others = ordered
+                .iter()
+                .filter(|name| *name != zone)
+                .map(|name| format!("Tz::{}", convert_bad_chars(name)))
+                .collect::<Vec<_>>();
+            writeln!(
+                timezone_file,
+                "            Tz::{zone} => &[{others}],",
+                zone = convert_bad_chars(zone),
+                others = others.join(", "),
+            )?;
+        }
+    }
+    writeln!(
+        timezone_file,
+        "            _ => &[],
+        }}
+    }}
+}}\n"
+    )
+}
+
+// Write an index of the zones that have ever used each abbreviation, for looking zones up by the
+// abbreviation in effect at some instant.
+fn write_abbreviations(
+    timezone_file: &mut File,
+    table: &Table,
+    zones: &BTreeSet<&String>,
+) -> io::Result<()> {
+    let mut abbreviations = BTreeMap::<String, BTreeSet<&str>>::new();
+    for zone in zones {
+        let timespans = table.timespans(zone).unwrap();
+        let mut names = std::iter::once(&timespans.first)
+            .chain(timespans.rest.iter().map(|(_, timespan)| timespan))
+            .map(|timespan| timespan.name.clone())
+            .collect::<Vec<_>>();
+        // The rule after the last transition may bring abbreviations of its own. Numeric ones
+        // stand for unnamed offsets.
+        if let Some(posix_tz) = table.posix_tz(zone) {
+            names.push(posix_tz.std_abbreviation);
+            names.extend(posix_tz.dst.map(|dst| dst.abbreviation));
+        }
+        for name in names {
+            if name != "%z" && !name.starts_with(['+', '-']) {
+                abbreviations.entry(name).or_default().insert(zone);
+            }
+        }
+    }
+    writeln!(
+        timezone_file,
+        "/// The zones that have used each abbreviation, in the order of the abbreviations.
+///
+/// The zones of each abbreviation are in alphabetical order, followed by links.
+pub(crate) static ABBREVIATIONS: &[(&str, &[Tz])] = &["
+    )?;
+    for (abbreviation, names) in &abbreviations {
+        // Zones come before the links to them.
+        let mut names = names.iter().collect::<Vec<_>>();
+        names.sort_by_key(|name| canonical_name(table, zones, name) != **name);
+        let zones = names
+            .iter()
+            .map(|zone| format!("Tz::{}", convert_bad_chars(zone)))
+            .collect::<Vec<_>>();
+        writeln!(
+            timezone_file,
+            "    ({:?}, &[{}]),",
+            abbreviation,
+            zones.join(", ")
+        )?;
+    }
+    writeln!(timezone_file, "];\n")
+}
+
 // Create a file containing nice-looking re-exports such as Europe::London
 // instead of having to use chrono_tz::timezones::Europe__London
 fn write_directory_file(directory_file: &mut File, table: &Table, version: &str) -> io::Result<()> {
@@ -514,7 +683,7 @@ fn detect_iana_db_version() -> String {
 pub fn main() {
     println!("cargo:rerun-if-env-changed={}", FILTER_ENV_VAR_NAME);
 
-    let parser = LineParser::new();
+    let parser = LineParser::default();
     let mut table = TableBuilder::new();
 
     let tzfiles = [
@@ -556,9 +725,22 @@ pub fn main() {
     let mut table = table.build();
     filter::maybe_filter_timezone_table(&mut table);
 
+    let manifest_dir =
+        PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| String::new()));
+    let zone_tables = ZoneTables::read(&manifest_dir.join("tz"));
+    let windows_zones = WindowsZones::read(&manifest_dir.join("cldr/windowsZones.xml"));
+    let zone_names = ZoneNames::read(&manifest_dir.join("cldr"));
+
     let timezone_path = Path::new(&env::var("OUT_DIR").unwrap()).join("timezones.rs");
     let mut timezone_file = File::create(timezone_path).unwrap();
-    write_timezone_file(&mut timezone_file, &table).unwrap();
+    write_timezone_file(
+        &mut timezone_file,
+        &table,
+        &zone_tables,
+        &windows_zones,
+        &zone_names,
+    )
+    .unwrap();
 
     let directory_path = Path::new(&env::var("OUT_DIR").unwrap()).join("directory.rs");
     let mut directory_file = File::create(directory_path).unwrap();
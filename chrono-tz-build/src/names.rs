@@ -0,0 +1,268 @@
+//! Reading the localized names of zones from CLDR: the metazone history of each
+//! zone in `metaZones.xml`, and the names of metazones and zones in the
+//! `timeZoneNames` of each locale under `main`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use parse_zoneinfo::line::days_from_civil;
+use parse_zoneinfo::table::Table;
+
+use crate::convert_bad_chars;
+use crate::xml;
+
+/// The name of the environment variable which possibly holds the locales to
+/// include, separated by commas.
+const LOCALES_ENV_VAR_NAME: &str = "CHRONO_TZ_LOCALES";
+
+/// A `usesMetazone` element, with its bounds as Unix timestamps.
+struct UsesMetazone {
+    from: Option<i64>,
+    to: Option<i64>,
+    metazone: String,
+}
+
+/// The long names of a metazone or zone in a locale.
+#[derive(Default)]
+struct Names {
+    generic: Option<String>,
+    standard: Option<String>,
+    daylight: Option<String>,
+}
+
+/// The `timeZoneNames` of a locale.
+#[derive(Default)]
+struct Locale {
+    zones: BTreeMap<String, Names>,
+    metazones: BTreeMap<String, Names>,
+}
+
+/// The metazone history of zones and the names of the selected locales.
+#[derive(Default)]
+pub(crate) struct ZoneNames {
+    metazones: BTreeMap<String, Vec<UsesMetazone>>,
+    locales: BTreeMap<String, Locale>,
+}
+
+impl ZoneNames {
+    /// Reads the metazones and the locales selected by `CHRONO_TZ_LOCALES`, or
+    /// all of them if it is not set, from the CLDR directory.
+    pub(crate) fn read(dir: &Path) -> ZoneNames {
+        println!("cargo:rerun-if-env-changed={}", LOCALES_ENV_VAR_NAME);
+        let path = dir.join("metaZones.xml");
+        let contents = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot open {}: {}", path.display(), e));
+        let mut names = ZoneNames::default();
+        let mut zone = None;
+        for line in contents.lines().map(str::trim) {
+            if line.starts_with("<timezone ") {
+                zone = Some(attribute(line, "type"));
+            } else if line.starts_with("<usesMetazone ") {
+                let zone = zone
+                    .clone()
+                    .unwrap_or_else(|| panic!("usesMetazone outside of timezone: {:?}", line));
+                let timestamp = |name| {
+                    xml::attribute(line, name).map(|time| {
+                        parse_time(&time).unwrap_or_else(|| panic!("invalid time: {:?}", time))
+                    })
+                };
+                names.metazones.entry(zone).or_default().push(UsesMetazone {
+                    from: timestamp("from"),
+                    to: timestamp("to"),
+                    metazone: attribute(line, "mzone"),
+                });
+            }
+        }
+
+        let requested = env::var(LOCALES_ENV_VAR_NAME).ok().map(|locales| {
+            locales
+                .split(',')
+                .map(|locale| locale.trim().replace('-', "_"))
+                .filter(|locale| !locale.is_empty())
+                .collect::<Vec<_>>()
+        });
+        // Names missing in a locale are looked up in its parents, which are
+        // included as well.
+        let selected = requested
+            .iter()
+            .flatten()
+            .flat_map(|locale| parents(locale))
+            .collect::<BTreeSet<_>>();
+        let main = dir.join("main");
+        let entries =
+            fs::read_dir(&main).unwrap_or_else(|e| panic!("cannot open {}: {}", main.display(), e));
+        for entry in entries {
+            let path = entry.unwrap().path();
+            let locale = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(locale) if path.extension().map_or(false, |ext| ext == "xml") => locale,
+                _ => continue,
+            };
+            if requested.is_none() || selected.contains(locale) {
+                let contents = fs::read_to_string(&path)
+                    .unwrap_or_else(|e| panic!("cannot open {}: {}", path.display(), e));
+                names
+                    .locales
+                    .insert(locale.to_owned(), parse_locale(&contents));
+            }
+        }
+        for locale in requested.iter().flatten() {
+            if !parents(locale).any(|parent| names.locales.contains_key(parent)) {
+                println!("cargo:warning=no zone names for locale {}", locale);
+            }
+        }
+        names
+    }
+
+    /// Writes the metazone history of the zones in `zones` and the names of each
+    /// locale.
+    pub(crate) fn write(
+        &self,
+        file: &mut fs::File,
+        table: &Table,
+        zones: &BTreeSet<&String>,
+    ) -> io::Result<()> {
+        writeln!(
+            file,
+            "impl Tz {{
+    pub(crate) fn metazone_periods(self) -> &'static [(i64, Option<&'static str>)] {{
+        match self {{"
+        )?;
+        for zone in zones {
+            let uses = match crate::cldr_entry(table, zones, &self.metazones, zone) {
+                Some(uses) => uses,
+                None => continue,
+            };
+            // Periods start where the previous one ends, if it has an end.
+            let mut periods = Vec::<(Option<i64>, Option<&str>)>::new();
+            for use_ in uses {
+                if periods.last().map(|&(start, _)| start) == Some(use_.from) {
+                    periods.pop();
+                }
+                periods.push((use_.from, Some(&use_.metazone)));
+                if let Some(to) = use_.to {
+                    periods.push((Some(to), None));
+                }
+            }
+            let periods = periods
+                .iter()
+                .map(|&(start, metazone)| match start {
+                    Some(start) => format!("({}, {:?})", start, metazone),
+                    None => format!("(i64::MIN, {:?})", metazone),
+                })
+                .collect::<Vec<_>>();
+            writeln!(
+                file,
+                "            Tz::{} => &[{}],",
+                convert_bad_chars(zone),
+                periods.join(", ")
+            )?;
+        }
+        writeln!(
+            file,
+            "            _ => &[],
+        }}
+    }}
+}}
+
+/// The names of each locale, in the order of their identifiers.
+pub(crate) static LOCALE_NAMES: &[LocaleNames] = &["
+        )?;
+        for (locale, names) in &self.locales {
+            let mut zone_names = Vec::new();
+            for zone in zones {
+                if let Some(names) = crate::cldr_entry(table, zones, &names.zones, zone) {
+                    zone_names.push(format!("(Tz::{}, {})", convert_bad_chars(zone), names));
+                }
+            }
+            let metazone_names = names
+                .metazones
+                .iter()
+                .map(|(metazone, names)| format!("({:?}, {})", metazone, names))
+                .collect::<Vec<_>>();
+            writeln!(
+                file,
+                "    LocaleNames {{
+        locale: {:?},
+        zones: &[{}],
+        metazones: &[{}],
+    }},",
+                locale,
+                zone_names.join(", "),
+                metazone_names.join(", ")
+            )?;
+        }
+        writeln!(file, "];\n")
+    }
+}
+
+impl std::fmt::Display for Names {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "ZoneNames {{ generic: {:?}, standard: {:?}, daylight: {:?} }}",
+            self.generic, self.standard, self.daylight
+        )
+    }
+}
+
+/// Returns a locale followed by its parents, such as `fr_CA` and `fr`.
+fn parents(locale: &str) -> impl Iterator<Item = &str> {
+    let mut next = Some(locale);
+    std::iter::from_fn(move || {
+        let locale = next?;
+        next = locale.rfind('_').map(|index| &locale[..index]);
+        Some(locale)
+    })
+}
+
+/// Parses the long names of the `timeZoneNames` of a locale.
+fn parse_locale(contents: &str) -> Locale {
+    let mut locale = Locale::default();
+    let mut current = None;
+    let mut long = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with("<zone ") {
+            current = Some(locale.zones.entry(attribute(line, "type")).or_default());
+        } else if line.starts_with("<metazone ") {
+            current = Some(locale.metazones.entry(attribute(line, "type")).or_default());
+        } else if line == "</zone>" || line == "</metazone>" {
+            current = None;
+        } else if line == "<long>" {
+            long = true;
+        } else if line == "</long>" {
+            long = false;
+        } else if let (Some(names), true) = (current.as_mut(), long) {
+            for (tag, field) in [
+                ("generic", &mut names.generic),
+                ("standard", &mut names.standard),
+                ("daylight", &mut names.daylight),
+            ] {
+                // `↑↑↑` defers to the parent locale and `∅∅∅` means there is no
+                // name, which both come down to leaving the name out.
+                match xml::element_text(line, tag) {
+                    Some(text) if text != "↑↑↑" && text != "∅∅∅" => *field = Some(text),
+                    _ => {}
+                }
+            }
+        }
+    }
+    locale
+}
+
+fn attribute(element: &str, name: &str) -> String {
+    xml::attribute(element, name).unwrap_or_else(|| panic!("no {} attribute: {:?}", name, element))
+}
+
+/// Parses a time in UTC such as `1970-04-26 07:00` into a Unix timestamp.
+fn parse_time(time: &str) -> Option<i64> {
+    let field = |range: std::ops::Range<usize>| time.get(range)?.parse::<i64>().ok();
+    if time.len() != 16 || &time[4..5] != "-" || &time[7..8] != "-" || &time[10..11] != " " {
+        return None;
+    }
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute) = (field(11..13)?, field(14..16)?);
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60)
+}
@@ -0,0 +1,195 @@
+//! Reading the tables of countries and their zones: `zone1970.tab`, `zone.tab`
+//! and `iso3166.tab`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use parse_zoneinfo::table::Table;
+
+use crate::convert_bad_chars;
+
+/// A row of `zone1970.tab` or `zone.tab`.
+struct Row {
+    countries: Vec<String>,
+    latitude: f64,
+    longitude: f64,
+    comment: Option<String>,
+}
+
+/// The contents of the three tables.
+#[derive(Default)]
+pub(crate) struct ZoneTables {
+    /// Rows by zone name, from `zone1970.tab` where possible.
+    rows: BTreeMap<String, Row>,
+    /// Zone names by country code, in the order of `zone.tab`.
+    countries: BTreeMap<String, Vec<String>>,
+    /// Country names by country code.
+    names: BTreeMap<String, String>,
+}
+
+impl ZoneTables {
+    /// Reads the tables from the tz database directory.
+    pub(crate) fn read(dir: &Path) -> ZoneTables {
+        let read = |name: &str| {
+            let path = dir.join(name);
+            fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("cannot open {}: {}", path.display(), e))
+        };
+        let (zone1970, zone, iso3166) =
+            (read("zone1970.tab"), read("zone.tab"), read("iso3166.tab"));
+
+        let mut tables = ZoneTables::default();
+        for (name, row) in lines(&zone).map(parse_row) {
+            for country in &row.countries {
+                tables
+                    .countries
+                    .entry(country.clone())
+                    .or_default()
+                    .push(name.to_owned());
+            }
+            tables.rows.insert(name.to_owned(), row);
+        }
+        // Zones in both tables take the countries they cover from `zone1970.tab`.
+        for (name, row) in lines(&zone1970).map(parse_row) {
+            tables.rows.insert(name.to_owned(), row);
+        }
+        for line in lines(&iso3166) {
+            let (code, name) = line
+                .split_once('\t')
+                .unwrap_or_else(|| panic!("invalid line in iso3166.tab: {:?}", line));
+            tables.names.insert(code.to_owned(), name.to_owned());
+        }
+        tables
+    }
+
+    /// Writes the functions looking up the metadata of each zone in `zones`.
+    ///
+    /// Links that are not listed take the metadata of the zone they point to.
+    pub(crate) fn write(
+        &self,
+        file: &mut fs::File,
+        table: &Table,
+        zones: &BTreeSet<&String>,
+    ) -> io::Result<()> {
+        writeln!(
+            file,
+            "impl Tz {{
+    pub(crate) fn location_data(self) -> Option<Location> {{
+        match self {{"
+        )?;
+        for zone in zones {
+            let row = match self.rows.get(zone.as_str()) {
+                Some(row) => row,
+                None => match self.rows.get(crate::canonical_name(table, zones, zone)) {
+                    Some(row) => row,
+                    None => continue,
+                },
+            };
+            writeln!(
+                file,
+                "            Tz::{zone} => Some(Location {{ countries: &{countries:?}, \
+                 latitude: {latitude:?}, longitude: {longitude:?}, comment: {comment:?} }}),",
+                zone = convert_bad_chars(zone),
+                countries = row.countries,
+                latitude = row.latitude,
+                longitude = row.longitude,
+                comment = row.comment,
+            )?;
+        }
+        writeln!(
+            file,
+            "            _ => None,
+        }}
+    }}
+}}
+
+pub(crate) fn country_zones(code: &str) -> &'static [Tz] {{
+    match code {{"
+        )?;
+        for (code, names) in &self.countries {
+            let names = names
+                .iter()
+                .filter(|name| zones.contains(name))
+                .map(|name| format!("Tz::{}", convert_bad_chars(name)))
+                .collect::<Vec<_>>();
+            if !names.is_empty() {
+                writeln!(file, "        {:?} => &[{}],", code, names.join(", "))?;
+            }
+        }
+        writeln!(
+            file,
+            "        _ => &[],
+    }}
+}}
+
+pub(crate) fn iso3166_name(code: &str) -> Option<&'static str> {{
+    match code {{"
+        )?;
+        for (code, name) in &self.names {
+            writeln!(file, "        {:?} => Some({:?}),", code, name)?;
+        }
+        writeln!(
+            file,
+            "        _ => None,
+    }}
+}}\n"
+        )
+    }
+}
+
+/// Returns the lines of a table that are not empty or comments.
+fn lines(contents: &str) -> impl Iterator<Item = &str> {
+    contents
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Parses a row of `zone1970.tab` or `zone.tab`, which only differ in that the
+/// former may list several countries.
+fn parse_row(line: &str) -> (&str, Row) {
+    let mut fields = line.split('\t');
+    let (countries, coordinates, name) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(countries), Some(coordinates), Some(name)) => (countries, coordinates, name),
+        _ => panic!("invalid zone table line: {:?}", line),
+    };
+    let (latitude, longitude) = parse_coordinates(coordinates)
+        .unwrap_or_else(|| panic!("invalid coordinates: {:?}", coordinates));
+    let row = Row {
+        countries: countries.split(',').map(str::to_owned).collect(),
+        latitude,
+        longitude,
+        comment: fields.next().map(str::to_owned),
+    };
+    (name, row)
+}
+
+/// Parses coordinates in the ISO 6709 sign-degrees-minutes-seconds format, such
+/// as `+4230+00131` or `-0045-07945` with optional seconds, into degrees.
+fn parse_coordinates(coordinates: &str) -> Option<(f64, f64)> {
+    let split = coordinates.get(1..)?.find(['+', '-'])? + 1;
+    let (latitude, longitude) = coordinates.split_at(split);
+    Some((parse_angle(latitude, 2)?, parse_angle(longitude, 3)?))
+}
+
+fn parse_angle(angle: &str, degree_digits: usize) -> Option<f64> {
+    let sign = match angle.as_bytes().first()? {
+        b'+' => 1.0,
+        b'-' => -1.0,
+        _ => return None,
+    };
+    let digits = &angle[1..];
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let field = |start: usize, len: usize| digits.get(start..start + len)?.parse::<u32>().ok();
+    let degrees = field(0, degree_digits)?;
+    let minutes = field(degree_digits, 2)?;
+    let seconds = match digits.len().checked_sub(degree_digits)? {
+        2 => 0,
+        4 => field(degree_digits + 2, 2)?,
+        _ => return None,
+    };
+    Some(sign * (degrees as f64 + minutes as f64 / 60.0 + seconds as f64 / 3600.0))
+}
@@ -0,0 +1,161 @@
+//! Reading the mapping between Windows time zone IDs and tz database zones from
+//! the `windowsZones.xml` table of CLDR.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use parse_zoneinfo::table::Table;
+
+use crate::convert_bad_chars;
+use crate::xml;
+
+/// A `mapZone` element, mapping a Windows ID in a territory to tz database zones.
+struct MapZone {
+    id: String,
+    territory: String,
+    zones: Vec<String>,
+}
+
+/// The contents of `windowsZones.xml`.
+pub(crate) struct WindowsZones {
+    /// The elements in the order of the file.
+    map_zones: Vec<MapZone>,
+}
+
+impl WindowsZones {
+    /// Reads the table from a file.
+    pub(crate) fn read(path: &Path) -> WindowsZones {
+        let contents = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("cannot open {}: {}", path.display(), e));
+        let map_zones = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("<mapZone "))
+            .map(|line| {
+                let attribute = |name: &str| {
+                    xml::attribute(line, name)
+                        .unwrap_or_else(|| panic!("no {} in windowsZones.xml: {:?}", name, line))
+                };
+                MapZone {
+                    id: attribute("other"),
+                    territory: attribute("territory"),
+                    zones: attribute("type")
+                        .split_whitespace()
+                        .map(str::to_owned)
+                        .collect(),
+                }
+            })
+            .collect();
+        WindowsZones { map_zones }
+    }
+
+    /// Writes the lookups between Windows IDs and the zones in `zones`.
+    ///
+    /// Zones that were filtered out are replaced by a compiled link to the
+    /// same zone if there is one, or left out. Windows IDs whose default zone
+    /// ends up left out are left out too.
+    pub(crate) fn write(
+        &self,
+        file: &mut fs::File,
+        table: &Table,
+        zones: &BTreeSet<&String>,
+    ) -> io::Result<()> {
+        // Zones listed under several IDs take the one they are the default of.
+        let mut ids = BTreeMap::<&str, &str>::new();
+        let defaults = self.map_zones.iter().filter(|m| m.territory == "001");
+        let others = self.map_zones.iter().filter(|m| m.territory != "001");
+        for map_zone in defaults.chain(others) {
+            for zone in &map_zone.zones {
+                ids.entry(zone).or_insert(&map_zone.id);
+            }
+        }
+
+        writeln!(
+            file,
+            "impl Tz {{
+    pub(crate) fn windows_id_data(self) -> Option<&'static str> {{
+        match self {{"
+        )?;
+        let mut unmapped = false;
+        for zone in zones {
+            match crate::cldr_entry(table, zones, &ids, zone) {
+                Some(id) => writeln!(
+                    file,
+                    "            Tz::{} => Some({:?}),",
+                    convert_bad_chars(zone),
+                    id
+                )?,
+                None => unmapped = true,
+            }
+        }
+        // With a filter, every zone left may have an ID, and the pattern would be unreachable.
+        if unmapped {
+            writeln!(file, "            _ => None,")?;
+        }
+        writeln!(
+            file,
+            "        }}
+    }}
+}}
+
+/// Windows time zones, in the order of their IDs ignoring case.
+pub(crate) static WINDOWS_ZONES: &[WindowsZone] = &["
+        )?;
+
+        let mut ids = BTreeMap::<String, Vec<(&str, Vec<String>)>>::new();
+        for map_zone in &self.map_zones {
+            let territories = ids.entry(map_zone.id.clone()).or_default();
+            let mut names = Vec::new();
+            for zone in &map_zone.zones {
+                if let Some(zone) = compiled_zone(table, zones, zone) {
+                    let name = format!("Tz::{}", convert_bad_chars(zone));
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+            if names.is_empty() {
+                continue;
+            }
+            // The default comes first, wherever the file lists it.
+            match map_zone.territory.as_str() {
+                "001" => territories.insert(0, (&map_zone.territory, names)),
+                _ => territories.push((&map_zone.territory, names)),
+            }
+        }
+        // Windows IDs whose default zone is not compiled in are left out.
+        let mut ids = ids
+            .into_iter()
+            .filter(|(_, territories)| territories.first().map(|t| t.0) == Some("001"))
+            .collect::<Vec<_>>();
+        ids.sort_by_key(|(id, _)| id.to_ascii_lowercase());
+        for (id, territories) in ids {
+            let territories = territories
+                .iter()
+                .map(|(territory, names)| format!("({:?}, &[{}])", territory, names.join(", ")))
+                .collect::<Vec<_>>();
+            writeln!(
+                file,
+                "    WindowsZone {{ id: {:?}, territories: &[{}] }},",
+                id,
+                territories.join(", ")
+            )?;
+        }
+        writeln!(file, "];\n")
+    }
+}
+
+// Returns the compiled zone to use for a zone of the table: the zone itself, or, if it was
+// filtered out, one with the same canonical name, such as `Asia/Kolkata` for `Asia/Calcutta`.
+fn compiled_zone<'a>(table: &Table, zones: &BTreeSet<&'a String>, zone: &str) -> Option<&'a str> {
+    if let Some(zone) = zones.get(&zone.to_owned()) {
+        return Some(zone);
+    }
+    let canonical = crate::canonical_name(table, zones, zone);
+    zones
+        .iter()
+        .find(|other| crate::canonical_name(table, zones, other) == canonical)
+        .map(|zone| zone.as_str())
+}
@@ -0,0 +1,25 @@
+//! Reading the CLDR XML files, which put each element on a line of its own.
+
+/// Returns the value of an attribute of an element on a single line.
+pub(crate) fn attribute(element: &str, name: &str) -> Option<String> {
+    let start = element.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let len = element[start..].find('"')?;
+    Some(unescape(&element[start..start + len]))
+}
+
+/// Returns the text of an element on a single line, such as
+/// `<standard>Pacific Standard Time</standard>`.
+pub(crate) fn element_text(line: &str, tag: &str) -> Option<String> {
+    let text = line
+        .strip_prefix(&format!("<{}>", tag))?
+        .strip_suffix(&format!("</{}>", tag))?;
+    Some(unescape(text))
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
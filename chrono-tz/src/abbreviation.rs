@@ -0,0 +1,114 @@
+//! Finding the zones that use an abbreviation, such as `"CST"`, at an instant.
+//!
+//! Abbreviations are ambiguous: `"CST"` stands for Central Standard Time in
+//! North America, China Standard Time and Cuba Standard Time, among others.
+//! Every zone using one is found, so callers can pick among them.
+
+use core::iter::FusedIterator;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone};
+
+use crate::location::{as_str, uppercase};
+use crate::timezone_impl::TzOffset;
+use crate::timezones::{Tz, ABBREVIATIONS};
+
+/// An iterator over the zones using an abbreviation at an instant, along with
+/// their offsets then.
+///
+/// This `struct` is created by [`zones_with_abbreviation`].
+#[derive(Clone, Debug)]
+pub struct AbbreviationZones {
+    abbreviation: &'static str,
+    /// The zones that have used the abbreviation at some point, followed by links.
+    candidates: &'static [Tz],
+    utc: NaiveDateTime,
+    region: Option<[u8; 2]>,
+    /// Whether the zones of the region have all been yielded, so that the
+    /// others are being walked through.
+    others: bool,
+    index: usize,
+}
+
+impl AbbreviationZones {
+    fn in_region(&self, tz: Tz) -> bool {
+        let region = match self.region {
+            Some(ref region) => as_str(region),
+            None => return false,
+        };
+        tz.location()
+            .map_or(false, |location| location.countries().contains(&region))
+    }
+}
+
+impl Iterator for AbbreviationZones {
+    type Item = (Tz, TzOffset);
+
+    fn next(&mut self) -> Option<(Tz, TzOffset)> {
+        loop {
+            let tz = match self.candidates.get(self.index) {
+                Some(&tz) => tz,
+                None if self.region.is_some() && !self.others => {
+                    self.others = true;
+                    self.index = 0;
+                    continue;
+                }
+                None => return None,
+            };
+            self.index += 1;
+            if self.region.is_some() && self.in_region(tz) == self.others {
+                continue;
+            }
+            let offset = tz.offset_from_utc_datetime(&self.utc);
+            if offset.offset.name == Some(self.abbreviation) {
+                return Some((tz, offset));
+            }
+        }
+    }
+}
+
+impl FusedIterator for AbbreviationZones {}
+
+/// Returns the zones whose abbreviation at an instant is `abbreviation`, such
+/// as `"CST"`, along with their offsets then.
+///
+/// Abbreviations are matched exactly, including their case. Links are found
+/// along with the zones they point to, as they are zones of their own.
+///
+/// Zones are yielded in alphabetical order, followed by links. If `region`, an
+/// ISO 3166 alpha-2 country code, is given, those of its country come first. A
+/// zone belongs to a country if its [`Location`](crate::Location) lists it.
+///
+/// ```
+/// use chrono::TimeZone;
+/// use chrono_tz::{zones_with_abbreviation, Tz};
+///
+/// let instant = Tz::UTC.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+/// let (tz, offset) = zones_with_abbreviation("CST", &instant, Some("CN")).next().unwrap();
+/// assert_eq!(tz, Tz::Asia__Shanghai);
+/// assert_eq!(offset.to_string(), "CST");
+///
+/// let zones: Vec<_> = zones_with_abbreviation("CST", &instant, None).map(|(tz, _)| tz).collect();
+/// assert!(zones.contains(&Tz::America__Chicago));
+/// assert!(zones.contains(&Tz::America__Havana));
+/// assert!(!zones.contains(&Tz::America__New_York));
+/// ```
+pub fn zones_with_abbreviation<T: TimeZone>(
+    abbreviation: &str,
+    instant: &DateTime<T>,
+    region: Option<&str>,
+) -> AbbreviationZones {
+    let (abbreviation, candidates) = match ABBREVIATIONS
+        .binary_search_by_key(&abbreviation, |&(abbreviation, _)| abbreviation)
+    {
+        Ok(index) => ABBREVIATIONS[index],
+        Err(_) => ("", &[][..]),
+    };
+    AbbreviationZones {
+        abbreviation,
+        candidates,
+        utc: instant.naive_utc(),
+        region: region.and_then(uppercase),
+        others: false,
+        index: 0,
+    }
+}
@@ -0,0 +1,226 @@
+//! Kernels converting Apache Arrow timestamp columns between UTC and a zone.
+//!
+//! Arrow timestamps with a time zone are instants, counted from the Unix epoch
+//! in UTC, while those without one are local dates and times. The kernels
+//! convert whole columns at once with the batch lookups of [`Tz`], and keep
+//! the nulls of their input.
+
+use core::fmt::{self, Display, Formatter};
+
+use arrow_array::types::ArrowTimestampType;
+use arrow_array::{Array, Date32Array, PrimitiveArray};
+use arrow_schema::{ArrowError, TimeUnit};
+use chrono::{LocalResult, TimeZone, Utc};
+
+use crate::disambiguation::{Disambiguation, LocalTimeError};
+use crate::timezones::Tz;
+
+/// An error encountered while converting a timestamp column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimestampColumnError {
+    /// The local time in a row is skipped or repeated, and the disambiguation
+    /// policy rejects it.
+    LocalTime {
+        row: usize,
+        error: Box<LocalTimeError>,
+    },
+    /// The converted value of a row is out of the range of its type.
+    OutOfRange { row: usize },
+}
+
+impl Display for TimestampColumnError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TimestampColumnError::LocalTime { row, error } => write!(f, "row {}: {}", row, error),
+            TimestampColumnError::OutOfRange { row } => {
+                write!(f, "row {}: converted timestamp is out of range", row)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimestampColumnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TimestampColumnError::LocalTime { error, .. } => Some(&**error),
+            TimestampColumnError::OutOfRange { .. } => None,
+        }
+    }
+}
+
+/// Makes a compute error, as returned by Arrow's own kernels.
+impl From<TimestampColumnError> for ArrowError {
+    fn from(error: TimestampColumnError) -> Self {
+        ArrowError::ComputeError(error.to_string())
+    }
+}
+
+/// Converts a column of instants to the local dates and times in a zone.
+///
+/// The values of `array` are taken as UTC whatever time zone it is tagged
+/// with, and the result has none, as Arrow does for local dates and times.
+///
+/// ```
+/// use arrow_array::{Array, TimestampSecondArray};
+/// use chrono_tz::{timestamps_to_local, Tz};
+///
+/// // 2024-01-01 00:00:00 and 2024-07-01 00:00:00 UTC
+/// let utc = TimestampSecondArray::from(vec![Some(1_704_067_200), None, Some(1_719_792_000)])
+///     .with_timezone("UTC");
+/// let local = timestamps_to_local(&utc, Tz::Europe__Paris).unwrap();
+/// assert_eq!(local.value(0), 1_704_067_200 + 3600);
+/// assert!(local.is_null(1));
+/// assert_eq!(local.value(2), 1_719_792_000 + 7200);
+/// assert_eq!(local.timezone(), None);
+/// ```
+pub fn timestamps_to_local<T: ArrowTimestampType>(
+    array: &PrimitiveArray<T>,
+    tz: Tz,
+) -> Result<PrimitiveArray<T>, TimestampColumnError> {
+    let units = units_per_second::<T>();
+    let seconds = seconds(array, units);
+    let mut offsets = vec![0; seconds.len()];
+    tz.offsets_for_utc_timestamps(&seconds, &mut offsets);
+    let values = rows(array)
+        .map(|(row, value)| {
+            i64::from(offsets[row])
+                .checked_mul(units)
+                .and_then(|offset| value.checked_add(offset))
+                .ok_or(TimestampColumnError::OutOfRange { row })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(PrimitiveArray::new(values.into(), array.nulls().cloned()))
+}
+
+/// Converts a column of local dates and times in a zone to instants.
+///
+/// Local times that are skipped or repeated by a transition are resolved with
+/// `disambiguation`, the first one it rejects being returned as an error. The
+/// result is tagged with the name of the zone.
+///
+/// ```
+/// use arrow_array::{Array, TimestampSecondArray};
+/// use chrono_tz::{timestamps_from_local, Disambiguation, Tz};
+///
+/// // 2024-03-10 02:30:00 is skipped in New York, and 2024-11-03 01:30:00 is repeated.
+/// let local = TimestampSecondArray::from(vec![1_710_037_800, 1_730_597_400]);
+/// let tz = Tz::America__New_York;
+/// let utc = timestamps_from_local(&local, tz, Disambiguation::Compatible).unwrap();
+/// assert_eq!(utc.value(0), 1_710_037_800 + 5 * 3600);
+/// assert_eq!(utc.value(1), 1_730_597_400 + 4 * 3600);
+/// assert_eq!(utc.timezone(), Some("America/New_York"));
+///
+/// assert!(timestamps_from_local(&local, tz, Disambiguation::Reject).is_err());
+/// ```
+pub fn timestamps_from_local<T: ArrowTimestampType>(
+    array: &PrimitiveArray<T>,
+    tz: Tz,
+    disambiguation: Disambiguation,
+) -> Result<PrimitiveArray<T>, TimestampColumnError> {
+    let units = units_per_second::<T>();
+    let seconds = seconds(array, units);
+    let mut offsets = vec![LocalResult::None; seconds.len()];
+    tz.offsets_for_local_timestamps(&seconds, &mut offsets);
+    let values = rows(array)
+        .map(|(row, value)| {
+            let out_of_range = TimestampColumnError::OutOfRange { row };
+            let offset = match offsets[row] {
+                LocalResult::Single(offset) => i64::from(offset),
+                // Skipped and repeated times are rare, so they are resolved one at a time.
+                _ => {
+                    let subsec = value.rem_euclid(units);
+                    let nanos = subsec * (1_000_000_000 / units);
+                    let local = Utc
+                        .timestamp_opt(seconds[row], nanos as u32)
+                        .single()
+                        .ok_or_else(|| out_of_range.clone())?
+                        .naive_utc();
+                    let dt = tz
+                        .resolve_local(&local)
+                        .resolve(disambiguation)
+                        .map_err(|error| TimestampColumnError::LocalTime {
+                            row,
+                            error: Box::new(error),
+                        })?;
+                    seconds[row] - dt.timestamp()
+                }
+            };
+            offset
+                .checked_mul(units)
+                .and_then(|offset| value.checked_sub(offset))
+                .ok_or(out_of_range)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(PrimitiveArray::new(values.into(), array.nulls().cloned()).with_timezone(tz.name()))
+}
+
+/// Extracts the local dates of a column of instants in a zone, as the days
+/// since the Unix epoch of an Arrow `Date32` column.
+///
+/// ```
+/// use arrow_array::{Array, TimestampMillisecondArray};
+/// use chrono::NaiveDate;
+/// use chrono_tz::{timestamps_to_local_dates, Tz};
+///
+/// // 2024-01-01 03:00:00 UTC is still the last day of 2023 in New York.
+/// let utc = TimestampMillisecondArray::from(vec![1_704_078_000_000]);
+/// let dates = timestamps_to_local_dates(&utc, Tz::America__New_York).unwrap();
+/// assert_eq!(dates.value_as_date(0), NaiveDate::from_ymd_opt(2023, 12, 31));
+/// ```
+pub fn timestamps_to_local_dates<T: ArrowTimestampType>(
+    array: &PrimitiveArray<T>,
+    tz: Tz,
+) -> Result<Date32Array, TimestampColumnError> {
+    let seconds = seconds(array, units_per_second::<T>());
+    let mut offsets = vec![0; seconds.len()];
+    tz.offsets_for_utc_timestamps(&seconds, &mut offsets);
+    let values = rows(array)
+        .map(|(row, _)| {
+            seconds[row]
+                .checked_add(i64::from(offsets[row]))
+                .and_then(|local| i32::try_from(local.div_euclid(86_400)).ok())
+                .ok_or(TimestampColumnError::OutOfRange { row })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Date32Array::new(values.into(), array.nulls().cloned()))
+}
+
+fn units_per_second<T: ArrowTimestampType>() -> i64 {
+    match T::UNIT {
+        TimeUnit::Second => 1,
+        TimeUnit::Millisecond => 1_000,
+        TimeUnit::Microsecond => 1_000_000,
+        TimeUnit::Nanosecond => 1_000_000_000,
+    }
+}
+
+/// Returns the whole seconds of each row.
+///
+/// Null rows take those of the row before them, so that the batch lookups can
+/// walk through the zone's transitions without jumping around.
+fn seconds<T: ArrowTimestampType>(array: &PrimitiveArray<T>, units: i64) -> Vec<i64> {
+    let mut previous = 0;
+    (0..array.len())
+        .map(|row| {
+            if array.is_valid(row) {
+                previous = array.value(row).div_euclid(units);
+            }
+            previous
+        })
+        .collect()
+}
+
+/// Iterates over the rows with their values, with the values of null rows
+/// replaced by 0 so that they do not fail to convert.
+fn rows<T: ArrowTimestampType>(
+    array: &PrimitiveArray<T>,
+) -> impl Iterator<Item = (usize, i64)> + '_ {
+    (0..array.len()).map(move |row| {
+        let value = if array.is_valid(row) {
+            array.value(row)
+        } else {
+            0
+        };
+        (row, value)
+    })
+}
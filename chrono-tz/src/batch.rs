@@ -0,0 +1,92 @@
+//! Converting many timestamps at once, such as the columns of a table.
+
+use chrono::LocalResult;
+
+use crate::timezone_impl::{SpanCursor, TimeSpans};
+use crate::timezones::Tz;
+
+impl Tz {
+    /// Writes the offset from UTC in seconds of this zone at each of `timestamps`,
+    /// which are seconds since the Unix epoch, into `offsets`.
+    ///
+    /// The offsets are the same as those of [`TimeZone::offset_from_utc_datetime`]
+    /// one timestamp at a time, but they are found much faster when the timestamps
+    /// are sorted or nearly so, as the zone's transitions are then walked once
+    /// rather than searched for each timestamp.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestamps` and `offsets` have different lengths.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// // 2024-01-01 and 2024-07-01 00:00:00 UTC
+    /// let timestamps = [1_704_067_200, 1_719_792_000];
+    /// let mut offsets = [0; 2];
+    /// Tz::Europe__Paris.offsets_for_utc_timestamps(&timestamps, &mut offsets);
+    /// assert_eq!(offsets, [3600, 7200]);
+    /// ```
+    ///
+    /// [`TimeZone::offset_from_utc_datetime`]: chrono::TimeZone::offset_from_utc_datetime
+    pub fn offsets_for_utc_timestamps(self, timestamps: &[i64], offsets: &mut [i32]) {
+        assert_eq!(
+            timestamps.len(),
+            offsets.len(),
+            "timestamps and offsets have different lengths"
+        );
+        let timespans = self.timespans();
+        let mut cursor = SpanCursor::new(timespans);
+        for (&timestamp, offset) in timestamps.iter().zip(offsets) {
+            let timespan = timespans.get(cursor.utc_index(timestamp));
+            *offset = timespan.utc_offset + timespan.dst_offset;
+        }
+    }
+
+    /// Writes the offsets from UTC in seconds of this zone for each of the local
+    /// `timestamps`, which are the seconds from the Unix epoch to local dates and
+    /// times taken as UTC, into `offsets`.
+    ///
+    /// A local time has no offset if it was skipped, and two if it was repeated,
+    /// exactly as with [`TimeZone::offset_from_local_datetime`]. Subtracting an
+    /// offset from a local timestamp gives the timestamp in UTC. As with
+    /// [`Tz::offsets_for_utc_timestamps`], sorted timestamps are the fastest to
+    /// convert.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestamps` and `offsets` have different lengths.
+    ///
+    /// ```
+    /// use chrono::LocalResult;
+    /// use chrono_tz::Tz;
+    ///
+    /// // 2024-03-10 and 2024-11-03 01:30:00, local time
+    /// let timestamps = [1_710_034_200, 1_730_597_400];
+    /// let mut offsets = [LocalResult::None; 2];
+    /// Tz::America__New_York.offsets_for_local_timestamps(&timestamps, &mut offsets);
+    /// assert_eq!(offsets[0], LocalResult::Single(-5 * 3600));
+    /// assert_eq!(offsets[1], LocalResult::Ambiguous(-4 * 3600, -5 * 3600));
+    /// ```
+    ///
+    /// [`TimeZone::offset_from_local_datetime`]: chrono::TimeZone::offset_from_local_datetime
+    pub fn offsets_for_local_timestamps(
+        self,
+        timestamps: &[i64],
+        offsets: &mut [LocalResult<i32>],
+    ) {
+        assert_eq!(
+            timestamps.len(),
+            offsets.len(),
+            "timestamps and offsets have different lengths"
+        );
+        let timespans = self.timespans();
+        let mut cursor = SpanCursor::new(timespans);
+        for (&timestamp, offset) in timestamps.iter().zip(offsets) {
+            *offset = cursor.local_index(timestamp).map(|index| {
+                let timespan = timespans.get(index);
+                timespan.utc_offset + timespan.dst_offset
+            });
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! The compact encoding of the transition tables compiled into the crate.
+//!
+//! Zones with the same history, which includes every link and its target, share
+//! a table. Within a table, transition times are stored as the seconds between
+//! consecutive transitions, and timespans as indices into a short list of the
+//! distinct ones, whose names are in turn slices of a single pool of
+//! abbreviations. The time of every few transitions is also stored, so that
+//! lookups binary-search these checkpoints and only decode the deltas after one.
+//!
+//! With the `lookup-index` feature, each table also has an index that takes
+//! lookups straight to the right part of the table, and checkpoints are closer
+//! together, so that lookups take constant time rather than time logarithmic in
+//! the number of transitions.
+
+use crate::timezone_impl::{FixedTimespan, FixedTimespanSet, TrailingRule, Transitions};
+use crate::timezones::ABBREVIATION_POOL;
+
+/// The log2 of the width in seconds of the buckets of [`CompactTable::buckets`], about a year.
+#[cfg(feature = "lookup-index")]
+const BUCKET_SHIFT: u32 = 25;
+
+/// The log2 of the number of transitions between [`CompactTable::checkpoints`].
+#[cfg(feature = "lookup-index")]
+const CHECKPOINT_SHIFT: u32 = 3;
+#[cfg(not(feature = "lookup-index"))]
+const CHECKPOINT_SHIFT: u32 = 5;
+
+/// The timespans of one or more compiled zones.
+pub(crate) struct CompactTable {
+    /// The distinct timespans of the zone.
+    pub(crate) timespans: &'static [CompactTimespan],
+    /// The timespan in effect before the first transition.
+    pub(crate) first: u8,
+    /// The time of the first transition, if there are any.
+    pub(crate) start: i64,
+    /// The time of the last transition, if there are any.
+    pub(crate) end: i64,
+    /// The seconds from each transition to the next one.
+    pub(crate) deltas: &'static [u32],
+    /// The timespan starting at each transition.
+    pub(crate) kinds: &'static [u8],
+    /// The rule that keeps applying after the last transition, if any.
+    pub(crate) rule: Option<TrailingRule>,
+    /// The number of transitions before each bucket of `1 << BUCKET_SHIFT` seconds, counting
+    /// from `start`.
+    #[cfg(feature = "lookup-index")]
+    pub(crate) buckets: &'static [u16],
+    /// The time of every `1 << CHECKPOINT_SHIFT`th transition, starting with the first one.
+    pub(crate) checkpoints: &'static [i64],
+}
+
+fn sum(deltas: &[u32]) -> i64 {
+    deltas.iter().map(|&delta| i64::from(delta)).sum()
+}
+
+/// A timespan whose name is interned in [`ABBREVIATION_POOL`].
+pub(crate) struct CompactTimespan {
+    pub(crate) utc_offset: i32,
+    pub(crate) dst_offset: i32,
+    /// Where the name starts in the pool.
+    pub(crate) name: u16,
+    /// The length of the name, or 0 for offsets without abbreviations.
+    pub(crate) name_len: u8,
+}
+
+impl CompactTimespan {
+    fn decode(&self) -> FixedTimespan {
+        let start = self.name as usize;
+        FixedTimespan {
+            utc_offset: self.utc_offset,
+            dst_offset: self.dst_offset,
+            name: match self.name_len {
+                0 => None,
+                len => Some(&ABBREVIATION_POOL[start..start + len as usize]),
+            },
+        }
+    }
+}
+
+impl CompactTable {
+    /// The timespans of the zones using this table.
+    pub(crate) fn timespans(&'static self) -> FixedTimespanSet<'static> {
+        FixedTimespanSet {
+            first: self.first(),
+            rest: Transitions::Compact(self),
+            rule: self.rule,
+        }
+    }
+
+    /// The timespan in effect before the first transition.
+    pub(crate) fn first(&self) -> FixedTimespan {
+        self.timespans[self.first as usize].decode()
+    }
+
+    /// The number of transitions.
+    pub(crate) fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    /// The time of the transition at `index`, summing the deltas from the checkpoint before it.
+    pub(crate) fn time(&self, index: usize) -> i64 {
+        let checkpoint = index >> CHECKPOINT_SHIFT;
+        self.checkpoints[checkpoint] + sum(&self.deltas[checkpoint << CHECKPOINT_SHIFT..index])
+    }
+
+    /// The time of the transition after the one at `index`, which is at `time`.
+    pub(crate) fn time_after(&self, index: usize, time: i64) -> i64 {
+        time + i64::from(self.deltas[index])
+    }
+
+    /// The timespan starting at the transition at `index`.
+    pub(crate) fn timespan(&self, index: usize) -> FixedTimespan {
+        self.timespans[self.kinds[index] as usize].decode()
+    }
+
+    /// Returns the number of transitions at or before `timestamp`.
+    ///
+    /// The transitions are walked forward from the one given by `before`, and there are only a
+    /// few of them to walk.
+    pub(crate) fn count_until(&self, timestamp: i64) -> usize {
+        if self.len() == 0 || timestamp < self.start {
+            return 0;
+        }
+        if timestamp >= self.end {
+            return self.len();
+        }
+        let (mut index, mut time) = self.before(timestamp);
+        // `before` may already be past `timestamp`, in which case nothing is walked. The last
+        // transition is after `timestamp`, so this stops before it.
+        while time <= timestamp {
+            time += i64::from(self.deltas[index]);
+            index += 1;
+        }
+        index
+    }
+
+    /// Returns a transition to walk forward from to `timestamp`, which is within the table, and
+    /// its time.
+    ///
+    /// This is the first transition at or after the start of the bucket of `timestamp`, so it may
+    /// come after `timestamp`. Only the transitions before it are known to be at or before
+    /// `timestamp`.
+    #[cfg(feature = "lookup-index")]
+    fn before(&self, timestamp: i64) -> (usize, i64) {
+        let bucket = ((timestamp - self.start) >> BUCKET_SHIFT) as usize;
+        let index = self.buckets[bucket] as usize;
+        (index, self.time(index))
+    }
+
+    /// Returns a transition to walk forward from to `timestamp`, which is within the table, and
+    /// its time.
+    ///
+    /// This is the last checkpoint at or before `timestamp`, found by binary search. The first
+    /// checkpoint is at `start`, so there is always one.
+    #[cfg(not(feature = "lookup-index"))]
+    fn before(&self, timestamp: i64) -> (usize, i64) {
+        let checkpoint = self.checkpoints.partition_point(|&time| time <= timestamp) - 1;
+        (checkpoint << CHECKPOINT_SHIFT, self.checkpoints[checkpoint])
+    }
+
+    /// Iterates over the transitions, with the timespan starting at each.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn iter(&self) -> CompactIter<'_> {
+        CompactIter {
+            table: self,
+            index: 0,
+            time: self.start,
+        }
+    }
+}
+
+/// An iterator over the transitions of a [`CompactTable`].
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) struct CompactIter<'a> {
+    table: &'a CompactTable,
+    index: usize,
+    /// The time of the transition at `index`.
+    time: i64,
+}
+
+impl Iterator for CompactIter<'_> {
+    type Item = (i64, FixedTimespan);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.table.len() {
+            return None;
+        }
+        let item = (self.time, self.table.timespan(self.index));
+        if let Some(&delta) = self.table.deltas.get(self.index) {
+            self.time += i64::from(delta);
+        }
+        self.index += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.table.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CompactIter<'_> {}
@@ -0,0 +1,115 @@
+//! A global time zone database that can be replaced while a program runs, so
+//! that long-running services can pick up new releases of the tz database.
+
+use core::fmt::{self, Debug, Formatter};
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::dynamic::DynamicTz;
+use crate::provider::{CompiledTz, TzProvider};
+use crate::IANA_TZDB_VERSION;
+
+/// A release of the tz database: a source of zones along with its version.
+pub struct TzDatabase {
+    version: String,
+    provider: Box<dyn TzProvider + Send + Sync>,
+}
+
+impl TzDatabase {
+    /// Makes a database of the zones of a provider, which come from the tz
+    /// database release `version`, such as `"2024b"`.
+    pub fn new<P: TzProvider + Send + Sync + 'static>(version: &str, provider: P) -> TzDatabase {
+        TzDatabase {
+            version: version.to_owned(),
+            provider: Box::new(provider),
+        }
+    }
+
+    /// The database compiled into the crate, which is in use until another one
+    /// is set with [`set_database`].
+    pub fn compiled() -> TzDatabase {
+        TzDatabase::new(IANA_TZDB_VERSION, CompiledTz)
+    }
+
+    /// The tz database release the zones come from.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+impl TzProvider for TzDatabase {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        self.provider.resolve(name)
+    }
+}
+
+impl Debug for TzDatabase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TzDatabase")
+            .field("version", &self.version)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The global database, created on first use.
+fn global() -> &'static RwLock<Arc<TzDatabase>> {
+    // `OnceLock` and a `const` `RwLock::new` are newer than the oldest supported
+    // Rust, so the database is leaked and its address kept in an atomic.
+    static GLOBAL: AtomicPtr<RwLock<Arc<TzDatabase>>> = AtomicPtr::new(ptr::null_mut());
+    let mut global = GLOBAL.load(Ordering::Acquire);
+    if global.is_null() {
+        let new = Box::into_raw(Box::new(RwLock::new(Arc::new(TzDatabase::compiled()))));
+        global = match GLOBAL.compare_exchange(
+            ptr::null_mut(),
+            new,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => new,
+            Err(existing) => {
+                // Another thread got there first.
+                // SAFETY: `new` was never shared.
+                drop(unsafe { Box::from_raw(new) });
+                existing
+            }
+        };
+    }
+    // SAFETY: the pointer comes from a leaked `Box` and is never written again.
+    unsafe { &*global }
+}
+
+/// Returns the database currently in use.
+///
+/// The database is a snapshot: it stays the same even if another one is set in
+/// the meantime, so a series of lookups made with it is consistent. Zones that
+/// were looked up are not affected by later changes either, as each
+/// [`DynamicTz`] owns its data.
+///
+/// ```
+/// use chrono_tz::{current_database, TzProvider, IANA_TZDB_VERSION};
+///
+/// let database = current_database();
+/// assert_eq!(database.version(), IANA_TZDB_VERSION);
+/// let tz = database.resolve("Europe/London").unwrap();
+/// ```
+pub fn current_database() -> Arc<TzDatabase> {
+    let global = global().read().unwrap_or_else(|e| e.into_inner());
+    Arc::clone(&global)
+}
+
+/// Replaces the database in use for all threads, and returns the one it
+/// replaces.
+///
+/// ```no_run
+/// use chrono_tz::{set_database, CompiledTz, TzDatabase, TzProvider, ZoneinfoDir};
+///
+/// // A new release was installed into `/opt/tzdata/2025a`.
+/// let provider = ZoneinfoDir::new("/opt/tzdata/2025a").or(CompiledTz);
+/// let previous = set_database(TzDatabase::new("2025a", provider));
+/// println!("replaced tz database {}", previous.version());
+/// ```
+pub fn set_database(database: TzDatabase) -> Arc<TzDatabase> {
+    let mut global = global().write().unwrap_or_else(|e| e.into_inner());
+    core::mem::replace(&mut *global, Arc::new(database))
+}
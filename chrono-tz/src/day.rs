@@ -0,0 +1,107 @@
+//! The span of time covered by a local calendar day.
+
+use core::ops::Range;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+
+use crate::disambiguation::LocalResolution;
+use crate::timezones::Tz;
+use crate::transitions::Transitions;
+
+/// A calendar day in a time zone, and the instants it covers.
+///
+/// Most days last 24 hours, but those with a transition are longer or shorter,
+/// and may not start at midnight. This `struct` is created by [`Tz::local_day`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LocalDay {
+    date: NaiveDate,
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
+}
+
+impl LocalDay {
+    /// The local date.
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// The first instant of the day.
+    pub fn start(&self) -> DateTime<Tz> {
+        self.start
+    }
+
+    /// The first instant of the following day, which is not part of this one.
+    pub fn end(&self) -> DateTime<Tz> {
+        self.end
+    }
+
+    /// The instants the day covers, from its start up to, but not including,
+    /// its end.
+    pub fn utc_range(&self) -> Range<DateTime<Utc>> {
+        self.start.with_timezone(&Utc)..self.end.with_timezone(&Utc)
+    }
+
+    /// How long the day lasts.
+    pub fn length(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// The local time at which the day starts, which is midnight unless midnight
+    /// was skipped by a transition.
+    pub fn first_time(&self) -> NaiveTime {
+        self.start.naive_local().time()
+    }
+
+    /// The transitions that happen during the day, including one at its start.
+    pub fn transitions(&self) -> Transitions {
+        self.start.timezone().transitions(self.start..self.end)
+    }
+}
+
+impl Tz {
+    /// Returns the span of time covered by a local date, or `None` if the whole
+    /// day was skipped.
+    ///
+    /// `None` is also returned when the start or end of the day cannot be
+    /// represented. This is always the case for [`NaiveDate::MAX`], which has no
+    /// following day, and for [`NaiveDate::MIN`] in zones ahead of UTC, where its
+    /// midnight comes before the first instant chrono can represent.
+    ///
+    /// A day ends when the next one starts. Should clocks be put back across
+    /// midnight, the local times of this day that are repeated after that are
+    /// counted as part of the next day.
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use chrono_tz::Tz;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+    /// let day = Tz::Europe__London.local_day(date).unwrap();
+    /// assert_eq!(day.length(), Duration::hours(23));
+    /// assert_eq!(day.transitions().count(), 1);
+    ///
+    /// // Samoa moved across the date line by skipping a day.
+    /// let date = NaiveDate::from_ymd_opt(2011, 12, 30).unwrap();
+    /// assert_eq!(Tz::Pacific__Apia.local_day(date), None);
+    /// ```
+    pub fn local_day(self, date: NaiveDate) -> Option<LocalDay> {
+        let start = self.start_of_day(date)?;
+        let end = self.start_of_day(date.succ_opt()?)?;
+        if start >= end {
+            return None;
+        }
+        Some(LocalDay { date, start, end })
+    }
+
+    /// Returns the first instant at or after the local midnight that starts a
+    /// date, if it can be represented.
+    fn start_of_day(self, date: NaiveDate) -> Option<DateTime<Tz>> {
+        let midnight = date.and_time(NaiveTime::MIN);
+        match self.resolve_local(&midnight) {
+            LocalResolution::Single(dt) => Some(dt),
+            LocalResolution::Fold(fold) => Some(fold.earlier()),
+            LocalResolution::Gap(gap) => Some(gap.transition().utc().with_timezone(&self)),
+            LocalResolution::OutOfRange(_) => None,
+        }
+    }
+}
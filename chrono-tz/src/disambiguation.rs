@@ -0,0 +1,286 @@
+//! Resolving local times that are skipped or repeated by a transition.
+
+use core::fmt::{self, Display, Formatter};
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone};
+
+use crate::timezone_impl::TimeSpans;
+use crate::timezones::Tz;
+use crate::transitions::Transition;
+
+/// How to pick an instant for a local time that does not occur exactly once.
+///
+/// These follow the `disambiguation` option of the JavaScript Temporal API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Disambiguation {
+    /// The earlier instant of a repeated time, and the later instant of a
+    /// skipped time, as if the clock had not been changed yet.
+    ///
+    /// This is what most other date and time libraries do, and is the default.
+    Compatible,
+    /// The earlier of the two instants a local time can be read as.
+    Earlier,
+    /// The later of the two instants a local time can be read as.
+    Later,
+    /// Return an error for any time that is skipped or repeated.
+    Reject,
+}
+
+/// Returns [`Disambiguation::Compatible`].
+impl Default for Disambiguation {
+    fn default() -> Self {
+        Disambiguation::Compatible
+    }
+}
+
+/// The result of looking up a local time in a time zone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LocalResolution {
+    /// The local time occurs exactly once.
+    Single(DateTime<Tz>),
+    /// The local time is skipped, usually because clocks were put forward.
+    Gap(Gap),
+    /// The local time occurs twice, usually because clocks were put back.
+    Fold(Fold),
+    /// The local time is so close to [`NaiveDateTime::MIN`] or
+    /// [`NaiveDateTime::MAX`] that the instant it refers to cannot be
+    /// represented.
+    OutOfRange(NaiveDateTime),
+}
+
+impl LocalResolution {
+    /// Picks a single instant according to a disambiguation policy.
+    ///
+    /// A local time that is out of range is an error whatever the policy.
+    pub fn resolve(self, disambiguation: Disambiguation) -> Result<DateTime<Tz>, LocalTimeError> {
+        use Disambiguation::*;
+        match (self, disambiguation) {
+            (LocalResolution::Single(dt), _) => Ok(dt),
+            (LocalResolution::Gap(gap), Reject) => Err(LocalTimeError::Gap(gap)),
+            (LocalResolution::Gap(gap), Earlier) => Ok(gap.earlier()),
+            (LocalResolution::Gap(gap), Compatible | Later) => Ok(gap.later()),
+            (LocalResolution::Fold(fold), Reject) => Err(LocalTimeError::Fold(fold)),
+            (LocalResolution::Fold(fold), Compatible | Earlier) => Ok(fold.earlier()),
+            (LocalResolution::Fold(fold), Later) => Ok(fold.later()),
+            (LocalResolution::OutOfRange(local), _) => Err(LocalTimeError::OutOfRange(local)),
+        }
+    }
+}
+
+/// A local time skipped by a transition.
+///
+/// The local times from [`start`](Gap::start) up to, but not including,
+/// [`end`](Gap::end) never occur.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Gap {
+    local: NaiveDateTime,
+    transition: Transition,
+}
+
+impl Gap {
+    /// The local time that was looked up.
+    pub fn local(&self) -> NaiveDateTime {
+        self.local
+    }
+
+    /// The transition that skips the local time.
+    pub fn transition(&self) -> &Transition {
+        &self.transition
+    }
+
+    /// The first local time that is skipped.
+    pub fn start(&self) -> NaiveDateTime {
+        local_at(&self.transition, self.transition.before())
+    }
+
+    /// The first local time after the gap, at which the new offset takes effect.
+    pub fn end(&self) -> NaiveDateTime {
+        local_at(&self.transition, self.transition.after())
+    }
+
+    /// How much local time is skipped.
+    pub fn length(&self) -> Duration {
+        self.end() - self.start()
+    }
+
+    /// The instant the local time refers to under the offset after the gap,
+    /// which comes before the transition.
+    pub fn earlier(&self) -> DateTime<Tz> {
+        instant(self.local, self.transition.after())
+    }
+
+    /// The instant the local time refers to under the offset before the gap,
+    /// which comes after the transition. This is the local time moved forward
+    /// by the length of the gap.
+    pub fn later(&self) -> DateTime<Tz> {
+        instant(self.local, self.transition.before())
+    }
+}
+
+/// A local time repeated because of a transition.
+///
+/// The local times from [`start`](Fold::start) up to, but not including,
+/// [`end`](Fold::end) occur once before the transition and once after it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fold {
+    local: NaiveDateTime,
+    transition: Transition,
+}
+
+impl Fold {
+    /// The local time that was looked up.
+    pub fn local(&self) -> NaiveDateTime {
+        self.local
+    }
+
+    /// The transition that repeats the local time.
+    pub fn transition(&self) -> &Transition {
+        &self.transition
+    }
+
+    /// The first local time that is repeated.
+    pub fn start(&self) -> NaiveDateTime {
+        local_at(&self.transition, self.transition.after())
+    }
+
+    /// The first local time after the fold, which only occurs once.
+    pub fn end(&self) -> NaiveDateTime {
+        local_at(&self.transition, self.transition.before())
+    }
+
+    /// How much local time is repeated.
+    pub fn width(&self) -> Duration {
+        self.end() - self.start()
+    }
+
+    /// The first occurrence of the local time, before the transition.
+    pub fn earlier(&self) -> DateTime<Tz> {
+        instant(self.local, self.transition.before())
+    }
+
+    /// The second occurrence of the local time, after the transition.
+    pub fn later(&self) -> DateTime<Tz> {
+        instant(self.local, self.transition.after())
+    }
+}
+
+/// The local time at which a transition happens, under one of its offsets.
+fn local_at<O: Offset>(transition: &Transition, offset: &O) -> NaiveDateTime {
+    transition.utc().naive_utc() + Duration::seconds(offset.fix().local_minus_utc() as i64)
+}
+
+/// The instant a local time refers to under an offset.
+fn instant(local: NaiveDateTime, offset: &<Tz as TimeZone>::Offset) -> DateTime<Tz> {
+    let utc = local - Duration::seconds(offset.fix().local_minus_utc() as i64);
+    Tz::from_offset(offset).from_utc_datetime(&utc)
+}
+
+/// An error returned by [`Disambiguation::Reject`] for a local time that does
+/// not occur exactly once, or by any policy for a local time out of range.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LocalTimeError {
+    /// The local time is skipped.
+    Gap(Gap),
+    /// The local time is repeated.
+    Fold(Fold),
+    /// The instant the local time refers to cannot be represented.
+    OutOfRange(NaiveDateTime),
+}
+
+impl Display for LocalTimeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LocalTimeError::Gap(gap) => write!(
+                f,
+                "local time {} is skipped by the transition at {}",
+                gap.local,
+                gap.transition.utc()
+            ),
+            LocalTimeError::Fold(fold) => write!(
+                f,
+                "local time {} is repeated by the transition at {}",
+                fold.local,
+                fold.transition.utc()
+            ),
+            LocalTimeError::OutOfRange(local) => {
+                write!(f, "local time {} is out of range", local)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LocalTimeError {}
+
+impl Tz {
+    /// Looks up a local time, with details of the gap or fold it falls in if it
+    /// does not occur exactly once.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use chrono_tz::{LocalResolution, Tz};
+    ///
+    /// let local = NaiveDate::from_ymd_opt(2024, 3, 10)
+    ///     .unwrap()
+    ///     .and_hms_opt(2, 30, 0)
+    ///     .unwrap();
+    /// match Tz::America__New_York.resolve_local(&local) {
+    ///     LocalResolution::Gap(gap) => {
+    ///         assert_eq!(gap.start().to_string(), "2024-03-10 02:00:00");
+    ///         assert_eq!(gap.end().to_string(), "2024-03-10 03:00:00");
+    ///         assert_eq!(gap.later().to_string(), "2024-03-10 03:30:00 EDT");
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn resolve_local(self, local: &NaiveDateTime) -> LocalResolution {
+        let result = self.from_local_datetime(local);
+        if let LocalResult::Single(dt) = result {
+            return LocalResolution::Single(dt);
+        }
+        let timestamp = local.and_utc().timestamp();
+        let transition = match self.timespans().local_transition(timestamp) {
+            Some(transition) => Transition::new(self, transition),
+            // chrono finds no instant either when applying the offset takes the
+            // local time out of range, which is the only way a time that is not
+            // near a transition can fail to occur once.
+            None => return LocalResolution::OutOfRange(*local),
+        };
+        match result {
+            LocalResult::Ambiguous(..) => LocalResolution::Fold(Fold {
+                local: *local,
+                transition,
+            }),
+            _ => LocalResolution::Gap(Gap {
+                local: *local,
+                transition,
+            }),
+        }
+    }
+
+    /// Converts a local time to an instant, using a policy for times that are
+    /// skipped or repeated.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use chrono_tz::{Disambiguation, Tz};
+    ///
+    /// let tz = Tz::Europe__Berlin;
+    /// let local = NaiveDate::from_ymd_opt(2024, 10, 27)
+    ///     .unwrap()
+    ///     .and_hms_opt(2, 30, 0)
+    ///     .unwrap();
+    /// let earlier = tz.from_local_datetime_with(&local, Disambiguation::Earlier);
+    /// assert_eq!(earlier.unwrap().to_string(), "2024-10-27 02:30:00 CEST");
+    /// let later = tz.from_local_datetime_with(&local, Disambiguation::Later);
+    /// assert_eq!(later.unwrap().to_string(), "2024-10-27 02:30:00 CET");
+    /// assert!(tz.from_local_datetime_with(&local, Disambiguation::Reject).is_err());
+    /// ```
+    pub fn from_local_datetime_with(
+        self,
+        local: &NaiveDateTime,
+        disambiguation: Disambiguation,
+    ) -> Result<DateTime<Tz>, LocalTimeError> {
+        self.resolve_local(local).resolve(disambiguation)
+    }
+}
@@ -0,0 +1,240 @@
+use core::fmt::{Debug, Display, Error, Formatter};
+use std::sync::Arc;
+
+use chrono::{
+    Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+};
+
+use crate::posix::DstRule;
+use crate::timezone_impl::{
+    FixedTimespan, FixedTimespanSet, OffsetComponents, OffsetName, TimeSpans, TrailingRule,
+    Transitions,
+};
+use crate::timezones::Tz;
+
+/// A time zone loaded at runtime rather than compiled into the crate.
+///
+/// It behaves exactly like [`Tz`](crate::Tz) when converting dates, but owns its
+/// transition data, so it can be built from sources such as TZif files (see
+/// [`DynamicTz::from_tzif`]). Cloning is cheap, as the data is shared.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DynamicTz {
+    name: Arc<str>,
+    // Shared with the links to this zone, which only differ by name.
+    data: Arc<ZoneData>,
+}
+
+#[derive(PartialEq, Eq)]
+struct ZoneData {
+    first: FixedTimespan,
+    rest: Vec<(i64, FixedTimespan)>,
+    rule: Option<TrailingRule>,
+    // One abbreviation per timespan, in the same order as `first` followed by `rest`, and then
+    // the standard and daylight saving time of `rule`.
+    abbreviations: Vec<Option<String>>,
+}
+
+impl DynamicTz {
+    /// Builds a zone from its timespans.
+    ///
+    /// The `name` field of each timespan is ignored; the abbreviations are given
+    /// separately, one per timespan, starting with the one for `first` and ending
+    /// with the two of `rule`.
+    pub(crate) fn new(
+        name: &str,
+        first: FixedTimespan,
+        rest: Vec<(i64, FixedTimespan)>,
+        rule: Option<TrailingRule>,
+        abbreviations: Vec<Option<String>>,
+    ) -> Self {
+        debug_assert_eq!(
+            abbreviations.len(),
+            rest.len() + 1 + if rule.is_some() { 2 } else { 0 }
+        );
+        DynamicTz {
+            name: name.into(),
+            data: Arc::new(ZoneData {
+                first,
+                rest,
+                rule,
+                abbreviations,
+            }),
+        }
+    }
+
+    /// Returns the same zone under another name, as links are.
+    #[cfg(feature = "runtime-tzdata")]
+    pub(crate) fn with_name(&self, name: &str) -> Self {
+        DynamicTz {
+            name: name.into(),
+            data: self.data.clone(),
+        }
+    }
+
+    /// The identifier this zone was loaded under (ex: America/New_York)
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn timespans(&self) -> FixedTimespanSet<'_> {
+        FixedTimespanSet {
+            first: self.data.first,
+            rest: Transitions::Plain(&self.data.rest),
+            rule: self.data.rule,
+        }
+    }
+
+    fn offset(&self, index: usize) -> DynamicOffset {
+        DynamicOffset {
+            tz: self.clone(),
+            index,
+        }
+    }
+}
+
+/// Copies a compiled zone, which then behaves exactly like it.
+impl From<Tz> for DynamicTz {
+    fn from(tz: Tz) -> Self {
+        let timespans = tz.timespans();
+        let rule_timespans = timespans.rule.iter().flat_map(|rule| [rule.std, rule.dst]);
+        let abbreviations = core::iter::once(timespans.first)
+            .chain(timespans.rest.iter().map(|(_, timespan)| timespan))
+            .chain(rule_timespans)
+            .map(|timespan| timespan.name.map(str::to_owned))
+            .collect();
+        DynamicTz::new(
+            tz.name(),
+            timespans.first,
+            timespans.rest.iter().collect(),
+            timespans.rule,
+            abbreviations,
+        )
+    }
+}
+
+impl Debug for DynamicTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for DynamicTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str(self.name())
+    }
+}
+
+impl TrailingRule {
+    /// Builds the timespans that a daylight saving time rule alternates between.
+    pub(crate) fn new(rule: DstRule) -> Self {
+        TrailingRule {
+            std: FixedTimespan {
+                utc_offset: rule.std_offset,
+                dst_offset: 0,
+                name: None,
+            },
+            dst: FixedTimespan {
+                utc_offset: rule.std_offset,
+                dst_offset: rule.dst_offset - rule.std_offset,
+                name: None,
+            },
+            rule,
+        }
+    }
+}
+
+/// The offset in effect in a [`DynamicTz`] at a given time.
+///
+/// This is the runtime counterpart of [`TzOffset`](crate::TzOffset), and exposes the
+/// same information through [`OffsetComponents`] and [`OffsetName`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct DynamicOffset {
+    tz: DynamicTz,
+    index: usize,
+}
+
+impl DynamicOffset {
+    fn timespan(&self) -> FixedTimespan {
+        self.tz.timespans().get(self.index)
+    }
+}
+
+impl OffsetComponents for DynamicOffset {
+    fn base_utc_offset(&self) -> Duration {
+        Duration::seconds(self.timespan().utc_offset as i64)
+    }
+
+    fn dst_offset(&self) -> Duration {
+        Duration::seconds(self.timespan().dst_offset as i64)
+    }
+}
+
+impl OffsetName for DynamicOffset {
+    fn tz_id(&self) -> &str {
+        self.tz.name()
+    }
+
+    fn abbreviation(&self) -> Option<&str> {
+        self.tz.data.abbreviations[self.index].as_deref()
+    }
+}
+
+impl Offset for DynamicOffset {
+    fn fix(&self) -> FixedOffset {
+        self.timespan().fix()
+    }
+}
+
+impl Display for DynamicOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self.abbreviation() {
+            Some(name) => f.write_str(name),
+            None => Display::fmt(&self.timespan(), f),
+        }
+    }
+}
+
+impl Debug for DynamicOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        Display::fmt(self, f)
+    }
+}
+
+impl TimeZone for DynamicTz {
+    type Offset = DynamicOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz.clone()
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        // See `Tz::offset_from_local_date` for why a single offset is preferred here.
+        let earliest = self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN));
+        let latest = self.offset_from_local_datetime(&local.and_hms_opt(23, 59, 59).unwrap());
+        use chrono::LocalResult::*;
+        match (earliest, latest) {
+            (result @ Single(_), _) => result,
+            (_, result @ Single(_)) => result,
+            (Ambiguous(offset, _), _) => Single(offset),
+            (_, Ambiguous(offset, _)) => Single(offset),
+            (None, None) => None,
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        self.timespans()
+            .local_index(local.and_utc().timestamp())
+            .map(|i| self.offset(i))
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, dt: &NaiveDateTime) -> Self::Offset {
+        let index = self.timespans().utc_index(dt.and_utc().timestamp());
+        self.offset(index)
+    }
+}
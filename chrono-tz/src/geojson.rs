@@ -0,0 +1,486 @@
+//! Finding the zone of a point from a GeoJSON file of zone boundaries, such as
+//! those published by the timezone-boundary-builder project.
+//!
+//! The file is a `FeatureCollection` of `Polygon` and `MultiPolygon` features,
+//! each with a `tzid` property naming its zone. It is not bundled with this
+//! crate: callers load whichever release suits them.
+
+use core::fmt::{self, Display, Formatter};
+use std::io::Read;
+
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use serde_json::Value;
+
+use crate::timezones::Tz;
+
+/// The size of the cells of the grid that polygons are indexed by, in degrees.
+const CELL_DEGREES: f64 = 1.0;
+const COLUMNS: usize = (360.0 / CELL_DEGREES) as usize;
+const ROWS: usize = (180.0 / CELL_DEGREES) as usize;
+
+/// An error encountered while reading zone boundaries.
+#[derive(Debug)]
+pub enum BoundaryError {
+    /// The data is not valid JSON, or could not be read.
+    Json(serde_json::Error),
+    /// The data is not a GeoJSON `FeatureCollection`.
+    NotAFeatureCollection,
+    /// The feature at this index has no `tzid` property, or its geometry is not
+    /// a well-formed `Polygon` or `MultiPolygon`.
+    InvalidFeature(usize),
+}
+
+impl Display for BoundaryError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            BoundaryError::Json(e) => write!(f, "invalid JSON: {}", e),
+            BoundaryError::NotAFeatureCollection => f.write_str("not a GeoJSON feature collection"),
+            BoundaryError::InvalidFeature(index) => write!(f, "invalid feature at index {}", index),
+        }
+    }
+}
+
+impl std::error::Error for BoundaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BoundaryError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A feature that was left out of a [`BoundaryIndex`] because its `tzid` does
+/// not name a zone of the compiled database.
+///
+/// This happens when the boundaries are newer than the database, or when zones
+/// were left out with the `filter-by-regex` feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedFeature {
+    index: usize,
+    tzid: String,
+}
+
+impl SkippedFeature {
+    /// The index of the feature in the collection.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The `tzid` property of the feature.
+    pub fn tzid(&self) -> &str {
+        &self.tzid
+    }
+}
+
+/// A polygon with holes, as rings of longitude and latitude pairs.
+struct Polygon {
+    tz: Tz,
+    rings: Vec<Vec<[f64; 2]>>,
+}
+
+impl Polygon {
+    /// Returns whether a point is inside of the polygon, using the even-odd rule
+    /// so that holes are left out.
+    fn contains(&self, point: [f64; 2]) -> bool {
+        let mut inside = false;
+        for ring in &self.rings {
+            let mut previous = match ring.last() {
+                Some(&previous) => previous,
+                None => continue,
+            };
+            for &current in ring {
+                // Count the edges crossed by a ray from the point towards the east.
+                if (current[1] > point[1]) != (previous[1] > point[1]) {
+                    let crossing = current[0]
+                        + (point[1] - current[1]) * (previous[0] - current[0])
+                            / (previous[1] - current[1]);
+                    if point[0] < crossing {
+                        inside = !inside;
+                    }
+                }
+                previous = current;
+            }
+        }
+        inside
+    }
+}
+
+/// Zone boundaries, indexed to find which zone a point lies in.
+///
+/// ```no_run
+/// use chrono_tz::{BoundaryIndex, Tz};
+///
+/// let file = std::fs::File::open("combined-with-oceans.json").unwrap();
+/// let index = BoundaryIndex::from_reader(std::io::BufReader::new(file)).unwrap();
+/// for skipped in index.skipped() {
+///     eprintln!("unknown zone {} in feature {}", skipped.tzid(), skipped.index());
+/// }
+/// // Windsor, Ontario, which is south of Detroit
+/// assert_eq!(index.lookup(42.30, -83.02), Some(Tz::America__Toronto));
+/// ```
+pub struct BoundaryIndex {
+    polygons: Vec<Polygon>,
+    /// The polygons whose bounding boxes overlap each cell of a grid over the
+    /// globe, row by row from the south-west.
+    cells: Vec<Vec<u32>>,
+    skipped: Vec<SkippedFeature>,
+}
+
+impl BoundaryIndex {
+    /// Reads zone boundaries from GeoJSON data.
+    pub fn from_slice(data: &[u8]) -> Result<BoundaryIndex, BoundaryError> {
+        BoundaryIndex::read(serde_json::Deserializer::from_slice(data))
+    }
+
+    /// Reads zone boundaries from a reader of GeoJSON data, such as a file.
+    ///
+    /// Features are indexed as they are read, so the data is never held in
+    /// memory as a whole.
+    pub fn from_reader<R: Read>(reader: R) -> Result<BoundaryIndex, BoundaryError> {
+        BoundaryIndex::read(serde_json::Deserializer::from_reader(reader))
+    }
+
+    // Features are indexed as they are read, so that only one of them is held
+    // in memory at a time, rather than the whole file.
+    fn read<'de, R: serde_json::de::Read<'de>>(
+        mut deserializer: serde_json::Deserializer<R>,
+    ) -> Result<BoundaryIndex, BoundaryError> {
+        let mut index = BoundaryIndex {
+            polygons: Vec::new(),
+            cells: vec![Vec::new(); COLUMNS * ROWS],
+            skipped: Vec::new(),
+        };
+        let mut invalid = None;
+        let visitor = CollectionVisitor {
+            index: &mut index,
+            invalid: &mut invalid,
+        };
+        let result = deserializer
+            .deserialize_map(visitor)
+            .and_then(|is_collection| deserializer.end().map(|()| is_collection));
+        match (result, invalid) {
+            (_, Some(i)) => Err(BoundaryError::InvalidFeature(i)),
+            (Err(e), None) => Err(BoundaryError::Json(e)),
+            (Ok(false), None) => Err(BoundaryError::NotAFeatureCollection),
+            (Ok(true), None) => Ok(index),
+        }
+    }
+
+    fn add_feature(&mut self, i: usize, feature: Feature) -> Option<()> {
+        let tzid = feature.tzid?;
+        let polygons = feature.geometry?.polygons()?;
+        let tz = match tzid.parse::<Tz>() {
+            Ok(tz) => tz,
+            Err(_) => {
+                self.skipped.push(SkippedFeature { index: i, tzid });
+                return Some(());
+            }
+        };
+        for rings in polygons {
+            self.insert(Polygon { tz, rings });
+        }
+        Some(())
+    }
+
+    fn insert(&mut self, polygon: Polygon) {
+        let outer = match polygon.rings.first() {
+            Some(outer) if !outer.is_empty() => outer,
+            _ => return,
+        };
+        let (mut min, mut max) = (outer[0], outer[0]);
+        for point in outer {
+            min = [min[0].min(point[0]), min[1].min(point[1])];
+            max = [max[0].max(point[0]), max[1].max(point[1])];
+        }
+        let id = self.polygons.len() as u32;
+        let (min_column, min_row) = cell(min);
+        let (max_column, max_row) = cell(max);
+        for row in min_row..=max_row {
+            for column in min_column..=max_column {
+                self.cells[row * COLUMNS + column].push(id);
+            }
+        }
+        self.polygons.push(polygon);
+    }
+
+    /// Returns the zone whose boundaries contain a point, given in degrees north
+    /// and east, or `None` if there is none.
+    pub fn lookup(&self, latitude: f64, longitude: f64) -> Option<Tz> {
+        if !latitude.is_finite() || !longitude.is_finite() {
+            return None;
+        }
+        let point = [longitude, latitude];
+        let (column, row) = cell(point);
+        self.cells[row * COLUMNS + column]
+            .iter()
+            .map(|&id| &self.polygons[id as usize])
+            .find(|polygon| polygon.contains(point))
+            .map(|polygon| polygon.tz)
+    }
+
+    /// Returns the features that were left out because their `tzid` is not a
+    /// known zone.
+    pub fn skipped(&self) -> &[SkippedFeature] {
+        &self.skipped
+    }
+}
+
+/// Returns the column and row of the grid cell containing a longitude and
+/// latitude.
+fn cell(point: [f64; 2]) -> (usize, usize) {
+    let column = ((point[0] + 180.0) / CELL_DEGREES) as usize;
+    let row = ((point[1] + 90.0) / CELL_DEGREES) as usize;
+    (column.min(COLUMNS - 1), row.min(ROWS - 1))
+}
+
+/// Visits a `FeatureCollection`, adding its features to an index, and returns
+/// whether it is one.
+struct CollectionVisitor<'a> {
+    index: &'a mut BoundaryIndex,
+    /// The index of the first invalid feature, which stops the reading.
+    invalid: &'a mut Option<usize>,
+}
+
+impl<'de> Visitor<'de> for CollectionVisitor<'_> {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a GeoJSON feature collection")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<bool, A::Error> {
+        let (mut is_collection, mut has_features) = (false, false);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "type" => is_collection = map.next_value::<String>()? == "FeatureCollection",
+                "features" => {
+                    map.next_value_seed(FeaturesSeed {
+                        index: &mut *self.index,
+                        invalid: &mut *self.invalid,
+                    })?;
+                    has_features = true;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(is_collection && has_features)
+    }
+}
+
+/// Adds the features of a collection to an index one by one.
+struct FeaturesSeed<'a> {
+    index: &'a mut BoundaryIndex,
+    invalid: &'a mut Option<usize>,
+}
+
+impl<'de> DeserializeSeed<'de> for FeaturesSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FeaturesSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("an array of GeoJSON features")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut i = 0;
+        while let Some(feature) = seq.next_element::<Feature>()? {
+            if self.index.add_feature(i, feature).is_none() {
+                *self.invalid = Some(i);
+                return Err(de::Error::custom("invalid feature"));
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+}
+
+/// The parts of a feature that matter, with `None` for those that are missing
+/// or of the wrong type.
+struct Feature {
+    tzid: Option<String>,
+    geometry: Option<Geometry>,
+}
+
+impl<'de> Deserialize<'de> for Feature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Feature, D::Error> {
+        struct FeatureVisitor;
+
+        impl<'de> Visitor<'de> for FeatureVisitor {
+            type Value = Feature;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("a GeoJSON feature")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Feature, A::Error> {
+                let mut feature = Feature {
+                    tzid: None,
+                    geometry: None,
+                };
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "properties" => {
+                            let properties = map.next_value::<Value>()?;
+                            feature.tzid = properties
+                                .get("tzid")
+                                .and_then(Value::as_str)
+                                .map(str::to_owned);
+                        }
+                        "geometry" => feature.geometry = map.next_value()?,
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(feature)
+            }
+        }
+
+        deserializer.deserialize_map(FeatureVisitor)
+    }
+}
+
+/// A geometry, whose coordinates can only be made sense of once its type is
+/// known, which may come after them.
+struct Geometry {
+    kind: Option<String>,
+    coordinates: Option<Coordinates>,
+}
+
+impl<'de> Deserialize<'de> for Geometry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Geometry, D::Error> {
+        struct GeometryVisitor;
+
+        impl<'de> Visitor<'de> for GeometryVisitor {
+            type Value = Geometry;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("a GeoJSON geometry")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Geometry, A::Error> {
+                let mut geometry = Geometry {
+                    kind: None,
+                    coordinates: None,
+                };
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "type" => geometry.kind = Some(map.next_value()?),
+                        "coordinates" => geometry.coordinates = Some(map.next_value()?),
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(geometry)
+            }
+        }
+
+        deserializer.deserialize_map(GeometryVisitor)
+    }
+}
+
+impl Geometry {
+    /// Converts a `Polygon` or `MultiPolygon` geometry into its polygons.
+    fn polygons(self) -> Option<Vec<Vec<Vec<[f64; 2]>>>> {
+        match (self.kind?.as_str(), self.coordinates?) {
+            ("Polygon", coordinates) => Some(vec![coordinates.polygon()?]),
+            ("MultiPolygon", Coordinates::List(polygons)) => {
+                polygons.into_iter().map(Coordinates::polygon).collect()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Nested arrays of coordinates, whose innermost arrays of numbers are
+/// positions.
+enum Coordinates {
+    Number(f64),
+    Position([f64; 2]),
+    List(Vec<Coordinates>),
+}
+
+impl Coordinates {
+    fn polygon(self) -> Option<Vec<Vec<[f64; 2]>>> {
+        match self {
+            Coordinates::List(rings) => rings.into_iter().map(Coordinates::ring).collect(),
+            _ => None,
+        }
+    }
+
+    fn ring(self) -> Option<Vec<[f64; 2]>> {
+        match self {
+            Coordinates::List(positions) => positions
+                .into_iter()
+                .map(|position| match position {
+                    Coordinates::Position(position) => Some(position),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Coordinates {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Coordinates, D::Error> {
+        struct CoordinatesVisitor;
+
+        impl<'de> Visitor<'de> for CoordinatesVisitor {
+            type Value = Coordinates;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("GeoJSON coordinates")
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Coordinates, E> {
+                Ok(Coordinates::Number(value as f64))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Coordinates, E> {
+                Ok(Coordinates::Number(value as f64))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Coordinates, E> {
+                Ok(Coordinates::Number(value))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Coordinates, A::Error> {
+                let first = match seq.next_element()? {
+                    Some(Coordinates::Number(longitude)) => {
+                        let latitude = match seq.next_element::<f64>()? {
+                            Some(latitude) => latitude,
+                            None => {
+                                return Ok(Coordinates::List(vec![Coordinates::Number(longitude)]))
+                            }
+                        };
+                        // Positions may have an altitude, which is of no interest.
+                        while seq.next_element::<IgnoredAny>()?.is_some() {}
+                        return Ok(Coordinates::Position([longitude, latitude]));
+                    }
+                    Some(first) => first,
+                    None => return Ok(Coordinates::List(Vec::new())),
+                };
+                let mut list = vec![first];
+                while let Some(coordinates) = seq.next_element()? {
+                    list.push(coordinates);
+                }
+                Ok(Coordinates::List(list))
+            }
+        }
+
+        deserializer.deserialize_any(CoordinatesVisitor)
+    }
+}
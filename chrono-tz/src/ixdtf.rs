@@ -0,0 +1,443 @@
+//! Dates and times with their zone in the Internet Extended Date/Time Format
+//! of [RFC 9557], such as `2024-03-10T01:30:00-05:00[America/New_York]`.
+//!
+//! This is the format of `Temporal.ZonedDateTime` in JavaScript: an [RFC 3339]
+//! date, time and offset, followed by the zone in brackets and optionally more
+//! bracketed annotations. The offset pins down the instant when the local time
+//! is repeated, and lets a reader notice when the zone's rules have changed
+//! since the string was written.
+//!
+//! [RFC 9557]: https://www.rfc-editor.org/rfc/rfc9557
+//! [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+
+use core::fmt::{self, Display, Formatter};
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike,
+};
+
+use crate::disambiguation::{Disambiguation, LocalTimeError};
+use crate::timezone_impl::OffsetName;
+use crate::timezones::Tz;
+
+/// What to do when the offset of a string is not one the zone has at its
+/// local time, because the zone's rules changed after it was written.
+///
+/// These follow the `offset` option of the JavaScript Temporal API. Whatever
+/// the policy, a zone marked as critical, as in `[!America/New_York]`, must
+/// agree with the offset, as [RFC 9557] requires the reader to act on the
+/// disagreement.
+///
+/// [RFC 9557]: https://www.rfc-editor.org/rfc/rfc9557#section-3.4
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OffsetConflict {
+    /// Keep the instant given by the offset, at whatever local time it now has
+    /// in the zone.
+    Use,
+    /// Keep the local time, with the given offset if the zone has it, and as
+    /// resolved by the disambiguation policy otherwise.
+    Prefer,
+    /// Keep the local time, resolved by the disambiguation policy, without
+    /// looking at the offset.
+    Ignore,
+    /// Return an error if the zone does not have the given offset.
+    ///
+    /// This is the default.
+    Reject,
+}
+
+/// Returns [`OffsetConflict::Reject`].
+impl Default for OffsetConflict {
+    fn default() -> Self {
+        OffsetConflict::Reject
+    }
+}
+
+/// Formats a date and time with its zone, as in
+/// `2024-03-10T01:30:00-05:00[America/New_York]`.
+///
+/// As in Temporal, fractions of a second are written only to their last
+/// non-zero digit, offsets are rounded to the minute, and years before 0 or
+/// after 9999 are written with a sign and six digits. A rounded offset can
+/// match either instant of a time repeated by a change of a few seconds, as
+/// from some local mean times, so the string may then be read back as the
+/// other one.
+///
+/// ```
+/// use chrono::TimeZone;
+/// use chrono_tz::{format_ixdtf, Tz};
+///
+/// let dt = Tz::America__New_York.with_ymd_and_hms(2024, 3, 10, 1, 30, 0).unwrap();
+/// assert_eq!(
+///     format_ixdtf(&dt).to_string(),
+///     "2024-03-10T01:30:00-05:00[America/New_York]"
+/// );
+/// ```
+pub fn format_ixdtf(datetime: &DateTime<Tz>) -> IxdtfFormat<'_> {
+    IxdtfFormat { datetime }
+}
+
+/// A date and time with its zone, formatted by [`format_ixdtf`].
+#[derive(Copy, Clone, Debug)]
+pub struct IxdtfFormat<'a> {
+    datetime: &'a DateTime<Tz>,
+}
+
+impl Display for IxdtfFormat<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let local = self.datetime.naive_local();
+        if (0..=9999).contains(&local.year()) {
+            write!(f, "{:04}", local.year())?;
+        } else {
+            write!(f, "{:+07}", local.year())?;
+        }
+        // Leap seconds are kept by chrono as more than a second of nanoseconds.
+        let (second, mut nanos) = match local.nanosecond() {
+            nanos if nanos >= 1_000_000_000 => (60, nanos - 1_000_000_000),
+            nanos => (local.second(), nanos),
+        };
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            local.month(),
+            local.day(),
+            local.hour(),
+            local.minute(),
+            second
+        )?;
+        if nanos != 0 {
+            let mut digits = 9;
+            while nanos % 10 == 0 {
+                nanos /= 10;
+                digits -= 1;
+            }
+            write!(f, ".{:0width$}", nanos, width = digits)?;
+        }
+        let offset = self.datetime.offset().fix().local_minus_utc();
+        let sign = if offset < 0 { '-' } else { '+' };
+        let minutes = round_to_minutes(offset).unsigned_abs();
+        write!(f, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60)?;
+        write!(f, "[{}]", self.datetime.offset().tz_id())
+    }
+}
+
+/// Parses a date and time with its zone, as in
+/// `2024-03-10T01:30:00-05:00[America/New_York]`.
+///
+/// The offset must be one the zone has at the local time, which also picks
+/// between the two instants of a repeated time. An offset of `Z` means that
+/// only the instant is known, which is then shown in the zone. Annotations
+/// after the zone, such as `[u-ca=iso8601]`, are skipped, unless they are
+/// marked as critical and not understood.
+///
+/// ```
+/// use chrono_tz::{parse_ixdtf, IxdtfError, Tz};
+///
+/// let dt = parse_ixdtf("2024-11-03T01:30:00-05:00[America/New_York]").unwrap();
+/// assert_eq!(dt.timezone(), Tz::America__New_York);
+/// assert_eq!(dt.to_rfc3339(), "2024-11-03T01:30:00-05:00");
+///
+/// // The zone has no such offset in November.
+/// assert_eq!(
+///     parse_ixdtf("2024-11-10T01:30:00-04:00[America/New_York]"),
+///     Err(IxdtfError::OffsetMismatch)
+/// );
+/// ```
+pub fn parse_ixdtf(s: &str) -> Result<DateTime<Tz>, IxdtfError> {
+    parse_ixdtf_with(s, OffsetConflict::Reject, Disambiguation::Compatible)
+}
+
+/// Parses a date and time with its zone, as in
+/// `2024-03-10T01:30:00-05:00[America/New_York]`, with policies for offsets
+/// that do not agree with the zone and local times that do not occur exactly
+/// once.
+///
+/// ```
+/// use chrono_tz::{parse_ixdtf_with, Disambiguation, OffsetConflict};
+///
+/// // An appointment made before Samoa moved west of the date line at the end of 2011.
+/// let s = "2012-06-01T09:00:00-11:00[Pacific/Apia]";
+/// let instant = parse_ixdtf_with(s, OffsetConflict::Use, Disambiguation::Compatible).unwrap();
+/// assert_eq!(instant.to_rfc3339(), "2012-06-02T09:00:00+13:00");
+/// let local = parse_ixdtf_with(s, OffsetConflict::Prefer, Disambiguation::Compatible).unwrap();
+/// assert_eq!(local.to_rfc3339(), "2012-06-01T09:00:00+13:00");
+/// ```
+pub fn parse_ixdtf_with(
+    s: &str,
+    offset_conflict: OffsetConflict,
+    disambiguation: Disambiguation,
+) -> Result<DateTime<Tz>, IxdtfError> {
+    let mut parser = Parser { s };
+    let local = parser.date_time()?;
+    let offset = parser.offset()?;
+    let (tz, critical) = parser.zone()?;
+    parser.annotations()?;
+
+    let offset = match offset {
+        Some(offset) => offset,
+        None => return Ok(tz.from_utc_datetime(&local)),
+    };
+    let candidates = match tz.offset_from_local_datetime(&local) {
+        LocalResult::Single(a) => [Some(a), None],
+        LocalResult::Ambiguous(a, b) => [Some(a), Some(b)],
+        LocalResult::None => [None, None],
+    }
+    .map(|candidate| candidate.map(|candidate| candidate.fix().local_minus_utc()));
+    // Offsets with seconds were written rounded to the minute, but one that
+    // matches exactly is preferred, as in Temporal.
+    let matched = candidates
+        .iter()
+        .flatten()
+        .find(|&&candidate| candidate == offset)
+        .or_else(|| {
+            candidates
+                .iter()
+                .flatten()
+                .find(|&&candidate| round_to_minutes(candidate) * 60 == offset)
+        })
+        .copied();
+    if matched.is_none() && (critical || offset_conflict == OffsetConflict::Reject) {
+        return Err(IxdtfError::OffsetMismatch);
+    }
+    let offset = match (offset_conflict, matched) {
+        (OffsetConflict::Use, _) => offset,
+        (OffsetConflict::Prefer | OffsetConflict::Reject, Some(matched)) => matched,
+        _ => {
+            return tz
+                .from_local_datetime_with(&local, disambiguation)
+                .map_err(IxdtfError::LocalTime)
+        }
+    };
+    let utc = local
+        .checked_sub_signed(Duration::seconds(i64::from(offset)))
+        .ok_or(IxdtfError::InvalidDateTime)?;
+    Ok(tz.from_utc_datetime(&utc))
+}
+
+/// An error encountered while parsing a date and time with its zone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IxdtfError {
+    /// The date, time or offset is malformed or out of range.
+    InvalidDateTime,
+    /// There is no zone in brackets after the offset.
+    MissingZone,
+    /// The zone is not in the database, or is a UTC offset rather than a
+    /// named zone.
+    UnknownZone,
+    /// An annotation after the zone is malformed.
+    InvalidAnnotation,
+    /// An annotation marked as critical is not understood.
+    UnsupportedAnnotation,
+    /// The zone does not have the offset at the local time.
+    OffsetMismatch,
+    /// The local time is skipped or repeated, and the disambiguation policy
+    /// rejects it.
+    LocalTime(LocalTimeError),
+}
+
+impl Display for IxdtfError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            IxdtfError::InvalidDateTime => f.write_str("invalid date, time or offset"),
+            IxdtfError::MissingZone => f.write_str("missing time zone annotation"),
+            IxdtfError::UnknownZone => f.write_str("unknown time zone"),
+            IxdtfError::InvalidAnnotation => f.write_str("invalid annotation"),
+            IxdtfError::UnsupportedAnnotation => f.write_str("unsupported critical annotation"),
+            IxdtfError::OffsetMismatch => f.write_str("offset does not match the time zone"),
+            IxdtfError::LocalTime(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IxdtfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IxdtfError::LocalTime(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Rounds an offset in seconds to the nearest minute, halfway cases away from
+/// zero, returning the minutes.
+fn round_to_minutes(offset: i32) -> i32 {
+    let minutes = (offset.unsigned_abs() + 30) / 60;
+    offset.signum() * minutes as i32
+}
+
+struct Parser<'a> {
+    s: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().first().copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.s = &self.s[1..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), IxdtfError> {
+        match self.eat(byte) {
+            true => Ok(()),
+            false => Err(IxdtfError::InvalidDateTime),
+        }
+    }
+
+    /// Parses `YYYY-MM-DDTHH:MM:SS[.fffffffff]`, or the same with a year of a
+    /// sign and six digits.
+    fn date_time(&mut self) -> Result<NaiveDateTime, IxdtfError> {
+        let year = match self.peek() {
+            Some(sign @ (b'+' | b'-')) => {
+                self.s = &self.s[1..];
+                match (sign, self.number(6)?) {
+                    (b'-', 0) => return Err(IxdtfError::InvalidDateTime),
+                    (b'-', year) => -year,
+                    (_, year) => year,
+                }
+            }
+            _ => self.number(4)?,
+        };
+        self.expect(b'-')?;
+        let month = self.number(2)?;
+        self.expect(b'-')?;
+        let day = self.number(2)?;
+        if !self.eat(b'T') && !self.eat(b't') {
+            return Err(IxdtfError::InvalidDateTime);
+        }
+        let hour = self.number(2)?;
+        self.expect(b':')?;
+        let minute = self.number(2)?;
+        self.expect(b':')?;
+        let second = self.number(2)?;
+        let mut nanos = 0;
+        if self.eat(b'.') {
+            let len = self.digits();
+            if !(1..=9).contains(&len) {
+                return Err(IxdtfError::InvalidDateTime);
+            }
+            nanos = self.number(len)? * 10_u32.pow(9 - len as u32) as i32;
+        }
+        let date = NaiveDate::from_ymd_opt(year, month as u32, day as u32);
+        // A leap second is kept by chrono as more than a second of nanoseconds.
+        let time = match second {
+            60 => NaiveTime::from_hms_nano_opt(
+                hour as u32,
+                minute as u32,
+                59,
+                nanos as u32 + 1_000_000_000,
+            ),
+            _ => NaiveTime::from_hms_nano_opt(
+                hour as u32,
+                minute as u32,
+                second as u32,
+                nanos as u32,
+            ),
+        };
+        match (date, time) {
+            (Some(date), Some(time)) => Ok(date.and_time(time)),
+            _ => Err(IxdtfError::InvalidDateTime),
+        }
+    }
+
+    /// Parses `Z` or `+HH:MM`, returning the offset in seconds, or `None` if
+    /// the offset to local time is unknown.
+    ///
+    /// As in RFC 3339, `-00:00` means the same as `Z`.
+    fn offset(&mut self) -> Result<Option<i32>, IxdtfError> {
+        if self.eat(b'Z') || self.eat(b'z') {
+            return Ok(None);
+        }
+        let sign = match self.peek() {
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return Err(IxdtfError::InvalidDateTime),
+        };
+        self.s = &self.s[1..];
+        let hours = self.number(2)?;
+        self.expect(b':')?;
+        let minutes = self.number(2)?;
+        if hours > 23 || minutes > 59 {
+            return Err(IxdtfError::InvalidDateTime);
+        }
+        match sign * (hours * 3600 + minutes * 60) {
+            0 if sign < 0 => Ok(None),
+            offset => Ok(Some(offset)),
+        }
+    }
+
+    /// Parses the zone in brackets, returning whether it is marked as critical.
+    fn zone(&mut self) -> Result<(Tz, bool), IxdtfError> {
+        if self.peek() != Some(b'[') {
+            return Err(IxdtfError::MissingZone);
+        }
+        let (critical, content) = self.annotation().ok_or(IxdtfError::InvalidAnnotation)?;
+        if content.contains('=') {
+            return Err(IxdtfError::MissingZone);
+        }
+        let tz = content.parse().map_err(|_| IxdtfError::UnknownZone)?;
+        Ok((tz, critical))
+    }
+
+    /// Parses the `key=value` annotations after the zone, up to the end.
+    fn annotations(&mut self) -> Result<(), IxdtfError> {
+        while !self.s.is_empty() {
+            let (critical, content) = self.annotation().ok_or(IxdtfError::InvalidAnnotation)?;
+            let (key, value) = content
+                .split_once('=')
+                .ok_or(IxdtfError::InvalidAnnotation)?;
+            let key_is_valid = key.bytes().enumerate().all(|(i, b)| match b {
+                b'a'..=b'z' | b'_' => true,
+                b'0'..=b'9' | b'-' => i > 0,
+                _ => false,
+            });
+            let value_is_valid = value.split('-').all(|part| {
+                (1..=8).contains(&part.len()) && part.bytes().all(|b| b.is_ascii_alphanumeric())
+            });
+            if key.is_empty() || !key_is_valid || !value_is_valid {
+                return Err(IxdtfError::InvalidAnnotation);
+            }
+            // Only the ISO 8601 calendar is understood.
+            if critical && (key, value) != ("u-ca", "iso8601") {
+                return Err(IxdtfError::UnsupportedAnnotation);
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses `[content]` or `[!content]`, returning whether it is marked as
+    /// critical.
+    fn annotation(&mut self) -> Option<(bool, &'a str)> {
+        if !self.eat(b'[') {
+            return None;
+        }
+        let critical = self.eat(b'!');
+        let (content, rest) = self.s.split_once(']')?;
+        self.s = rest;
+        Some((critical, content))
+    }
+
+    fn digits(&self) -> usize {
+        self.s.bytes().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    /// Parses a decimal number of exactly `len` digits.
+    fn number(&mut self, len: usize) -> Result<i32, IxdtfError> {
+        if self.digits() < len {
+            return Err(IxdtfError::InvalidDateTime);
+        }
+        let (digits, rest) = self.s.split_at(len);
+        self.s = rest;
+        Ok(digits
+            .bytes()
+            .fold(0, |n, digit| n * 10 + (digit - b'0') as i32))
+    }
+}
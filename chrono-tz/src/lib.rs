@@ -134,16 +134,75 @@
 #[cfg(feature = "serde")]
 mod serde;
 
-mod binary_search;
+mod abbreviation;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod batch;
+mod compact;
+#[cfg(feature = "std")]
+mod database;
+mod day;
 mod directory;
+mod disambiguation;
+#[cfg(feature = "std")]
+mod dynamic;
+#[cfg(feature = "geojson")]
+mod geojson;
+mod ixdtf;
+#[cfg(feature = "std")]
+mod local;
+mod location;
+mod names;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod nearest;
+mod offsets;
+mod posix;
+#[cfg(feature = "std")]
+mod provider;
 mod timezone_impl;
 mod timezones;
-
+mod transitions;
+#[cfg(feature = "runtime-tzdata")]
+mod tzdata;
+#[cfg(feature = "std")]
+mod tzif;
+mod windows;
+
+pub use crate::abbreviation::{zones_with_abbreviation, AbbreviationZones};
+#[cfg(feature = "arrow")]
+pub use crate::arrow::{
+    timestamps_from_local, timestamps_to_local, timestamps_to_local_dates, TimestampColumnError,
+};
+#[cfg(feature = "std")]
+pub use crate::database::{current_database, set_database, TzDatabase};
+pub use crate::day::LocalDay;
 pub use crate::directory::*;
+pub use crate::disambiguation::{Disambiguation, Fold, Gap, LocalResolution, LocalTimeError};
+#[cfg(feature = "std")]
+pub use crate::dynamic::{DynamicOffset, DynamicTz};
+#[cfg(feature = "geojson")]
+pub use crate::geojson::{BoundaryError, BoundaryIndex, SkippedFeature};
+pub use crate::ixdtf::{
+    format_ixdtf, parse_ixdtf, parse_ixdtf_with, IxdtfError, IxdtfFormat, OffsetConflict,
+};
+#[cfg(feature = "std")]
+pub use crate::local::{local_tz, local_tz_from, LocalTzError};
+pub use crate::location::{country_name, zones_for_country, Location};
+pub use crate::names::name_locales;
+pub use crate::offsets::{zones_with_offsets, OffsetZones};
+pub use crate::posix::{PosixOffset, PosixTz, PosixTzError};
+#[cfg(feature = "std")]
+pub use crate::provider::{CompiledTz, Fallback, TzProvider, ZoneinfoDir};
 pub use crate::timezone_impl::{OffsetComponents, OffsetName, TzOffset};
 pub use crate::timezones::ParseError;
 pub use crate::timezones::Tz;
 pub use crate::timezones::TZ_VARIANTS;
+pub use crate::transitions::{Transition, Transitions, TzTransitions};
+#[cfg(feature = "runtime-tzdata")]
+pub use crate::tzdata::{DynamicTzSet, TzdataError};
+#[cfg(feature = "std")]
+pub use crate::tzif::TzifError;
+pub use crate::windows::{windows_zone, windows_zones, WindowsZone};
 pub use crate::IANA_TZDB_VERSION;
 
 #[cfg(test)]
@@ -166,7 +225,7 @@ mod tests {
     use super::IANA_TZDB_VERSION;
     use super::US::Eastern;
     use super::UTC;
-    use chrono::{Duration, NaiveDate, TimeZone};
+    use chrono::{Duration, LocalResult, NaiveDate, TimeZone};
 
     #[test]
     fn london_to_berlin() {
@@ -314,6 +373,56 @@ mod tests {
         test_inverse(Tahiti, 1911, 1914);
     }
 
+    #[test]
+    fn inverse_london_after_2100() {
+        test_inverse(London, 2098, 2103);
+    }
+
+    #[test]
+    fn after_2100() {
+        let summer = London.with_ymd_and_hms(2150, 7, 1, 12, 0, 0).unwrap();
+        assert_eq!(summer.to_string(), "2150-07-01 12:00:00 BST");
+        let winter = London.with_ymd_and_hms(2150, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(winter.to_string(), "2150-01-01 12:00:00 GMT");
+        let summer = Adelaide.with_ymd_and_hms(3000, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(summer.to_string(), "3000-01-01 12:00:00 ACDT");
+        let summer = Berlin.with_ymd_and_hms(200_000, 7, 1, 12, 0, 0).unwrap();
+        assert_eq!(summer.to_string(), "+200000-07-01 12:00:00 CEST");
+
+        let gap = Eastern.with_ymd_and_hms(2200, 3, 9, 2, 30, 0);
+        assert_eq!(gap, LocalResult::None);
+        let fold = Eastern.with_ymd_and_hms(2200, 11, 2, 1, 30, 0);
+        assert_eq!(
+            fold.earliest().unwrap().to_string(),
+            "2200-11-02 01:30:00 EDT"
+        );
+        assert_eq!(
+            fold.latest().unwrap().to_string(),
+            "2200-11-02 01:30:00 EST"
+        );
+    }
+
+    #[test]
+    fn trailing_rules_continue_tables() {
+        use crate::timezone_impl::TimeSpans;
+
+        for tz in super::TZ_VARIANTS {
+            let timespans = tz.timespans();
+            let rule = match timespans.rule {
+                Some(rule) => rule,
+                None => continue,
+            };
+            let skip = timespans.rest.len().saturating_sub(20);
+            for (time, timespan) in timespans.rest.iter().skip(skip) {
+                let expected = match rule.rule.is_dst(time) {
+                    true => rule.dst,
+                    false => rule.std,
+                };
+                assert_eq!(timespan, expected, "{} at {}", tz, time);
+            }
+        }
+    }
+
     #[test]
     fn string_representation() {
         let dt = UTC
@@ -501,6 +610,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::zero_prefixed_literal)]
     fn test_numeric_names() {
         let dt = Scoresbysund
             .with_ymd_and_hms(2024, 05, 01, 0, 0, 0)
@@ -514,4 +624,217 @@ mod tests {
         assert_eq!(format!("{}", dt.offset()), "+0245");
         assert_eq!(format!("{:?}", dt.offset()), "+0245");
     }
+
+    #[test]
+    fn test_links() {
+        use crate::timezone_impl::TimeSpans;
+
+        assert_eq!(Tz::US__Pacific.canonical(), Tz::America__Los_Angeles);
+        assert!(Tz::US__Pacific.is_link());
+        assert!(!Tz::America__Los_Angeles.is_link());
+        assert_eq!(Tz::UTC.canonical(), Tz::Etc__UTC);
+        assert!(Tz::Europe__London.aliases().contains(&Tz::GB));
+        assert_eq!(Tz::GB.aliases()[0], Tz::Europe__London);
+        assert!(!Tz::GB.aliases().contains(&Tz::GB));
+        assert_eq!(Tz::America__Nuuk.aliases(), &[Tz::America__Godthab]);
+
+        for tz in super::TZ_VARIANTS {
+            let canonical = tz.canonical();
+            assert!(!canonical.is_link(), "{}", tz);
+            let (expected, actual) = (canonical.timespans(), tz.timespans());
+            assert_eq!(actual.first, expected.first, "{}", tz);
+            assert!(actual.rest.iter().eq(expected.rest.iter()), "{}", tz);
+            for alias in tz.aliases() {
+                assert_eq!(alias.canonical(), canonical, "{}", tz);
+            }
+            let expected = super::TZ_VARIANTS
+                .iter()
+                .filter(|other| **other != tz && other.canonical() == canonical)
+                .count();
+            assert_eq!(tz.aliases().len(), expected, "{}", tz);
+        }
+    }
+
+    #[test]
+    fn test_shared_tables() {
+        use crate::timezone_impl::{TimeSpans, Transitions};
+
+        for tz in super::TZ_VARIANTS {
+            match (tz.timespans().rest, tz.canonical().timespans().rest) {
+                (Transitions::Compact(actual), Transitions::Compact(expected)) => {
+                    assert!(core::ptr::eq(actual, expected), "{}", tz)
+                }
+                _ => panic!("{} is not compiled compactly", tz),
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact_lookups() {
+        use crate::timezone_impl::{TimeSpans, Transitions};
+
+        for tz in super::TZ_VARIANTS {
+            let compact = tz.timespans().rest;
+            let transitions = compact.iter().collect::<Vec<_>>();
+            let plain = Transitions::Plain(&transitions);
+            for (i, &(time, _)) in transitions.iter().enumerate() {
+                assert_eq!(compact.time(i), time, "{} at {}", tz, i);
+                for timestamp in time - 1..=time + 1 {
+                    let expected = plain.count_until(timestamp);
+                    assert_eq!(compact.count_until(timestamp), expected, "{}", tz);
+                }
+            }
+            let mut timestamp = -5_000_000_000;
+            while timestamp < 5_000_000_000 {
+                let expected = plain.count_until(timestamp);
+                assert_eq!(compact.count_until(timestamp), expected, "{}", tz);
+                timestamp += 86_400 * 7;
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_lookups() {
+        use crate::timezone_impl::TimeSpans;
+        use chrono::LocalResult;
+
+        // Links share the tables of their targets.
+        for tz in super::TZ_VARIANTS.iter().filter(|tz| !tz.is_link()) {
+            let timespans = tz.timespans();
+            let times = timespans
+                .rest
+                .iter()
+                .map(|(time, _)| time)
+                .collect::<Vec<_>>();
+            // Each timespan starts at the transition before it and ends at the one after it.
+            let spans = (0..timespans.len())
+                .map(|i| {
+                    let timespan = timespans.get(i);
+                    let offset = i64::from(timespan.utc_offset + timespan.dst_offset);
+                    (
+                        i.checked_sub(1).map(|i| times[i]),
+                        times.get(i).copied(),
+                        offset,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let utc_reference = |timestamp: i64| {
+                spans
+                    .iter()
+                    .rposition(|(begin, _, _)| begin.map_or(true, |begin| begin <= timestamp))
+                    .unwrap()
+            };
+            let local_reference = |timestamp: i64| {
+                let mut indices =
+                    spans
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, (begin, end, offset))| {
+                            let contains = begin.map_or(true, |begin| begin + offset <= timestamp)
+                                && end.map_or(true, |end| timestamp < end + offset);
+                            match contains {
+                                true => Some(i),
+                                false => None,
+                            }
+                        });
+                match (indices.next(), indices.next(), indices.next()) {
+                    (None, _, _) => LocalResult::None,
+                    (Some(index), None, _) => LocalResult::Single(index),
+                    (Some(earlier), Some(later), None) => LocalResult::Ambiguous(earlier, later),
+                    _ => panic!("{} has overlapping timespans", tz),
+                }
+            };
+
+            // Past the last transition, the trailing rule rather than the table decides.
+            let last = times.last().copied().unwrap_or(i64::MAX);
+            let mut instants = (-4_000_000_000..5_000_000_000)
+                .step_by(97 * 86_400 + 12_345)
+                .collect::<Vec<_>>();
+            for (i, &time) in times.iter().enumerate() {
+                instants.extend([time - 1, time, time + 1]);
+                for local in [time + spans[i].2, time + spans[i + 1].2] {
+                    instants.extend([local - 1, local, local + 1]);
+                }
+            }
+            for timestamp in instants {
+                if timespans.rule.is_none() || timestamp < last {
+                    let expected = utc_reference(timestamp);
+                    assert_eq!(
+                        timespans.utc_index(timestamp),
+                        expected,
+                        "{} at {}",
+                        tz,
+                        timestamp
+                    );
+                }
+                if timespans.rule.map_or(true, |rule| {
+                    let largest = core::cmp::max(rule.rule.std_offset, rule.rule.dst_offset);
+                    timestamp < last.saturating_add(i64::from(largest))
+                }) {
+                    let expected = local_reference(timestamp);
+                    assert_eq!(
+                        timespans.local_index(timestamp),
+                        expected,
+                        "{} at {}",
+                        tz,
+                        timestamp
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "tzif-writer")]
+    #[test]
+    fn test_generated_posix_tz_strings() {
+        use chrono::{Datelike, Offset};
+
+        for tz in super::TZ_VARIANTS {
+            let posix: super::PosixTz = tz.posix_tz().parse().unwrap();
+            // Casablanca has explicit transitions until 2087.
+            let mut dt = UTC.with_ymd_and_hms(2090, 1, 1, 0, 0, 0).unwrap();
+            while dt.year() < 2093 {
+                assert_eq!(
+                    dt.with_timezone(&posix).offset().fix(),
+                    dt.with_timezone(&tz).offset().fix(),
+                    "{} at {}",
+                    tz,
+                    dt
+                );
+                dt += Duration::hours(1);
+            }
+        }
+    }
+
+    #[cfg(feature = "tzif-writer")]
+    #[test]
+    fn test_posix_rules_match_zoneinfo() {
+        // The footers written into TZif files are evaluated by parse-zoneinfo,
+        // and the same strings are evaluated here when they are read back.
+        // No zone uses the Julian and ordinal day forms, so a few are added.
+        let extra = [
+            "<+03>-3<+04>,J60/-1,300/26",
+            "EST5EDT,0/0,J365/25",
+            "<-03>3<-02>,M3.5.0/-2,M10.5.0/-1",
+            "AEST-10AEDT,M10.1.0,M4.1.0/3",
+        ];
+        let footers = super::TZ_VARIANTS.iter().map(|tz| tz.posix_tz());
+        for footer in footers.chain(extra.iter().copied()) {
+            let posix: super::PosixTz = footer.parse().unwrap();
+            let rule = match posix.dst_rule() {
+                Some((rule, _, _)) => rule,
+                None => continue,
+            };
+            let zoneinfo = posix.to_zoneinfo();
+            for year in 1800..2500 {
+                assert_eq!(
+                    rule.transitions(year),
+                    zoneinfo.dst_transitions(i64::from(year)),
+                    "{} in {}",
+                    footer,
+                    year
+                );
+            }
+        }
+    }
 }
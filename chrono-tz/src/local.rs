@@ -0,0 +1,160 @@
+//! Detecting the time zone the host system is configured with.
+
+use core::fmt::{self, Display, Formatter};
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::timezones::Tz;
+
+/// How many symbolic links are followed from `/etc/localtime` before giving up.
+const MAX_SYMLINKS: usize = 8;
+
+/// An error encountered while detecting the local time zone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LocalTzError {
+    /// The system names a time zone that is not in the compiled database.
+    ///
+    /// This happens when the `TZ` environment variable holds a POSIX TZ string
+    /// rather than a zone name, or when zones were left out with the
+    /// `filter-by-regex` feature.
+    UnknownZone(String),
+    /// None of the usual places name a time zone.
+    NotConfigured,
+}
+
+impl Display for LocalTzError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LocalTzError::UnknownZone(name) => write!(f, "unknown local time zone {:?}", name),
+            LocalTzError::NotConfigured => f.write_str("no local time zone is configured"),
+        }
+    }
+}
+
+impl std::error::Error for LocalTzError {}
+
+/// Returns the time zone the host system is configured with.
+///
+/// The sources are tried in the order the C library uses them:
+///
+/// 1. the `TZ` environment variable, holding a zone name such as `Europe/London`,
+///    optionally preceded by a colon, or the path of a zoneinfo file;
+/// 2. the target of the `/etc/localtime` symbolic link, such as
+///    `/usr/share/zoneinfo/Europe/London`;
+/// 3. the zone name in `/etc/timezone`, as found on Debian-based systems.
+///
+/// An empty `TZ` variable means UTC. If `TZ` is set but does not name a known
+/// zone, an error is returned rather than falling back to the other sources.
+///
+/// ```no_run
+/// let tz = chrono_tz::local_tz().unwrap();
+/// println!("the local time zone is {}", tz.name());
+/// ```
+pub fn local_tz() -> Result<Tz, LocalTzError> {
+    let tz_var = env::var_os("TZ");
+    local_tz_from(Path::new("/"), tz_var.as_deref())
+}
+
+/// Returns the time zone configured in a file system tree, for a given value of
+/// the `TZ` environment variable.
+///
+/// This follows the same steps as [`local_tz`], but looks for `etc/localtime` and
+/// `etc/timezone` under `root`, and treats absolute paths in `tz_var` as relative
+/// to it. It is useful for inspecting containers and mounted disk images.
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// let tz = chrono_tz::local_tz_from(Path::new("/mnt/image"), None).unwrap();
+/// ```
+pub fn local_tz_from(root: &Path, tz_var: Option<&OsStr>) -> Result<Tz, LocalTzError> {
+    if let Some(tz_var) = tz_var {
+        return from_tz_var(root, tz_var);
+    }
+    if let Some(name) = localtime_link(root) {
+        return parse_zone(&name);
+    }
+    match fs::read_to_string(root.join("etc/timezone")) {
+        Ok(contents) => match contents.lines().next().map(str::trim) {
+            Some(name) if !name.is_empty() => parse_zone(name),
+            _ => Err(LocalTzError::NotConfigured),
+        },
+        Err(_) => Err(LocalTzError::NotConfigured),
+    }
+}
+
+fn from_tz_var(root: &Path, tz_var: &OsStr) -> Result<Tz, LocalTzError> {
+    let value = tz_var
+        .to_str()
+        .ok_or_else(|| LocalTzError::UnknownZone(tz_var.to_string_lossy().into_owned()))?;
+    let value = value.strip_prefix(':').unwrap_or(value);
+    if value.is_empty() {
+        return Ok(Tz::UTC);
+    }
+    if value.starts_with('/') {
+        let path = within(root, Path::new(value));
+        let name = zone_name(Path::new(value)).or_else(|| follow_links(root, path));
+        return match name {
+            Some(name) => parse_zone(&name),
+            None => Err(LocalTzError::UnknownZone(value.to_owned())),
+        };
+    }
+    parse_zone(value)
+}
+
+/// Returns the zone name that `etc/localtime` links to, if it is a link to a
+/// zoneinfo file.
+fn localtime_link(root: &Path) -> Option<String> {
+    follow_links(root, root.join("etc/localtime"))
+}
+
+/// Follows a chain of symbolic links until one points into a zoneinfo directory.
+fn follow_links(root: &Path, mut path: PathBuf) -> Option<String> {
+    for _ in 0..MAX_SYMLINKS {
+        let target = fs::read_link(&path).ok()?;
+        if let Some(name) = zone_name(&target) {
+            return Some(name);
+        }
+        path = match target.is_absolute() {
+            true => within(root, &target),
+            false => path.parent()?.join(target),
+        };
+    }
+    None
+}
+
+/// Extracts a zone name from the path of a zoneinfo file, such as
+/// `/usr/share/zoneinfo/Europe/London` or `../zoneinfo/posix/Europe/London`.
+fn zone_name(path: &Path) -> Option<String> {
+    let components: Vec<_> = path.components().collect();
+    let start = components
+        .iter()
+        .rposition(|c| c.as_os_str() == "zoneinfo")?
+        + 1;
+    let mut names = components[start..].iter().map(|c| match c {
+        Component::Normal(name) => name.to_str(),
+        _ => None,
+    });
+    let mut name = names.next()??.to_owned();
+    // The `posix` and `right` subdirectories hold copies of the whole database.
+    if name == "posix" || name == "right" {
+        name = names.next()??.to_owned();
+    }
+    for component in names {
+        name.push('/');
+        name.push_str(component?);
+    }
+    Some(name)
+}
+
+/// Resolves an absolute path within `root`.
+fn within(root: &Path, path: &Path) -> PathBuf {
+    root.join(path.strip_prefix("/").unwrap_or(path))
+}
+
+fn parse_zone(name: &str) -> Result<Tz, LocalTzError> {
+    name.parse()
+        .map_err(|_| LocalTzError::UnknownZone(name.to_owned()))
+}
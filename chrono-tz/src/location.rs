@@ -0,0 +1,105 @@
+//! Countries and locations of zones, from the `zone1970.tab`, `zone.tab` and
+//! `iso3166.tab` tables of the tz database.
+
+use crate::timezones::{country_zones, iso3166_name, Tz};
+
+/// Where a zone is, as described by the tables of the tz database.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Location {
+    pub(crate) countries: &'static [&'static str],
+    pub(crate) latitude: f64,
+    pub(crate) longitude: f64,
+    pub(crate) comment: Option<&'static str>,
+}
+
+impl Location {
+    /// The ISO 3166 alpha-2 codes of the countries whose clocks have agreed with
+    /// the zone since 1970.
+    ///
+    /// The country of the zone's principal location comes first, followed by
+    /// any others in alphabetical order.
+    pub fn countries(&self) -> &'static [&'static str] {
+        self.countries
+    }
+
+    /// The latitude of the zone's principal location, in degrees north.
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// The longitude of the zone's principal location, in degrees east.
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// The comment that tells apart the zones of a country, such as
+    /// `"Mountain (most areas)"`.
+    pub fn comment(&self) -> Option<&'static str> {
+        self.comment
+    }
+}
+
+impl Tz {
+    /// Returns the countries and principal location of this zone, or `None` for
+    /// zones that are not tied to a country, such as `Etc/UTC`.
+    ///
+    /// Links that are not listed in the tables themselves have the location of
+    /// the zone they point to.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// let location = Tz::America__Edmonton.location().unwrap();
+    /// assert_eq!(location.countries(), ["CA"]);
+    /// assert_eq!(location.comment(), Some("Mountain - AB, BC(E), NT(E), SK(W)"));
+    /// assert!((location.latitude() - 53.55).abs() < 0.01);
+    /// ```
+    pub fn location(self) -> Option<Location> {
+        self.location_data()
+    }
+}
+
+/// Returns the zones of a country, given its ISO 3166 alpha-2 code, in the order
+/// the tz database lists them.
+///
+/// Each region of the country with its own clock history has a zone, some of
+/// which are links to zones of neighbouring countries.
+///
+/// ```
+/// use chrono_tz::{zones_for_country, Tz};
+///
+/// let zones = zones_for_country("CA");
+/// assert_eq!(zones[0], Tz::America__St_Johns);
+/// assert!(zones.contains(&Tz::America__Toronto));
+/// assert_eq!(zones_for_country("no"), [Tz::Europe__Oslo]);
+/// assert!(zones_for_country("XX").is_empty());
+/// ```
+pub fn zones_for_country(code: &str) -> &'static [Tz] {
+    match uppercase(code) {
+        Some(code) => country_zones(as_str(&code)),
+        None => &[],
+    }
+}
+
+/// Returns the name of a country, given its ISO 3166 alpha-2 code.
+///
+/// ```
+/// assert_eq!(chrono_tz::country_name("CA"), Some("Canada"));
+/// ```
+pub fn country_name(code: &str) -> Option<&'static str> {
+    iso3166_name(as_str(&uppercase(code)?))
+}
+
+/// Converts a two-letter code to upper case.
+pub(crate) fn uppercase(code: &str) -> Option<[u8; 2]> {
+    match code.as_bytes() {
+        &[a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+            Some([a.to_ascii_uppercase(), b.to_ascii_uppercase()])
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn as_str(code: &[u8; 2]) -> &str {
+    core::str::from_utf8(code).unwrap()
+}
@@ -0,0 +1,176 @@
+//! Localized names of offsets, from the metazones and `timeZoneNames` of CLDR.
+//!
+//! Zones that have shared their clocks over a period, such as the zones of
+//! Pacific Time, belong to a metazone, which is what most names are given for.
+//! The locales that are compiled in can be limited with the `CHRONO_TZ_LOCALES`
+//! environment variable at build time.
+
+use chrono::{DateTime, TimeZone};
+
+use crate::timezone_impl::TimeSpans;
+use crate::timezones::{Tz, LOCALE_NAMES};
+
+/// The long names of a metazone or zone in a locale.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ZoneNames {
+    pub(crate) generic: Option<&'static str>,
+    pub(crate) standard: Option<&'static str>,
+    pub(crate) daylight: Option<&'static str>,
+}
+
+/// The names of a locale, where names of zones take precedence over those of
+/// their metazones.
+pub(crate) struct LocaleNames {
+    pub(crate) locale: &'static str,
+    pub(crate) zones: &'static [(Tz, ZoneNames)],
+    /// Sorted by metazone.
+    pub(crate) metazones: &'static [(&'static str, ZoneNames)],
+}
+
+impl Tz {
+    /// Returns the long name of the offset of this zone at an instant in a
+    /// locale, such as `"Pacific Daylight Time"`, or `None` if the locale has no
+    /// name for it.
+    ///
+    /// The name is that of the zone's metazone at the instant, or of the zone
+    /// itself in the few cases where CLDR gives it its own, like British Summer
+    /// Time. Locales are identifiers such as `"fr"` or `"fr-CA"`, and names they
+    /// lack are taken from their parent locales.
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use chrono_tz::Tz;
+    ///
+    /// let tz = Tz::America__Vancouver;
+    /// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(tz.long_name(&dt, "en"), Some("Pacific Daylight Time"));
+    /// assert_eq!(tz.long_name(&dt, "fr-CA"), Some("heure avancée du Pacifique"));
+    /// let tz = Tz::Europe__London;
+    /// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(tz.long_name(&dt, "en"), Some("British Summer Time"));
+    /// ```
+    pub fn long_name<T: TimeZone>(self, dt: &DateTime<T>, locale: &str) -> Option<&'static str> {
+        let utc = dt.timestamp();
+        if self.is_daylight(utc) {
+            self.find_name(utc, locale, |names| names.daylight)
+        } else {
+            self.find_name(utc, locale, |names| names.standard)
+        }
+    }
+
+    /// Returns the generic name of the offset of this zone at an instant in a
+    /// locale, which does not tell standard and daylight saving time apart, such
+    /// as `"Pacific Time"`.
+    ///
+    /// Metazones without daylight saving time, which CLDR gives no generic
+    /// name, have their standard name instead. See [`Tz::long_name`].
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use chrono_tz::Tz;
+    ///
+    /// let tz = Tz::America__Vancouver;
+    /// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(tz.generic_name(&dt, "de"), Some("Nordamerikanische Westküstenzeit"));
+    /// let tz = Tz::Asia__Kolkata;
+    /// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(tz.generic_name(&dt, "en"), Some("India Standard Time"));
+    /// ```
+    pub fn generic_name<T: TimeZone>(self, dt: &DateTime<T>, locale: &str) -> Option<&'static str> {
+        let utc = dt.timestamp();
+        self.find_name(utc, locale, |names| names.generic)
+            .or_else(|| self.find_name(utc, locale, |names| names.standard))
+    }
+
+    /// Returns the CLDR metazone of this zone at an instant, such as
+    /// `"America_Pacific"`.
+    pub fn metazone<T: TimeZone>(self, dt: &DateTime<T>) -> Option<&'static str> {
+        self.metazone_at(dt.timestamp())
+    }
+
+    fn metazone_at(self, utc: i64) -> Option<&'static str> {
+        let periods = self.metazone_periods();
+        let index = periods.partition_point(|&(start, _)| start <= utc);
+        periods[..index].last().and_then(|&(_, metazone)| metazone)
+    }
+
+    fn find_name(
+        self,
+        utc: i64,
+        locale: &str,
+        field: impl Fn(&ZoneNames) -> Option<&'static str>,
+    ) -> Option<&'static str> {
+        let zone_name = find_in_locale(locale, |names| {
+            let (_, zone_names) = names.zones.iter().find(|&&(tz, _)| tz == self)?;
+            field(zone_names)
+        });
+        zone_name.or_else(|| {
+            let metazone = self.metazone_at(utc)?;
+            find_in_locale(locale, |names| {
+                let index = names
+                    .metazones
+                    .binary_search_by_key(&metazone, |&(metazone, _)| metazone)
+                    .ok()?;
+                field(&names.metazones[index].1)
+            })
+        })
+    }
+
+    /// Returns whether CLDR considers the offset at an instant daylight saving
+    /// time.
+    ///
+    /// The tz database gives a few zones, such as `Europe/Dublin`, negative
+    /// daylight saving time in winter, whereas CLDR has them on standard time
+    /// then and on daylight saving time in summer.
+    fn is_daylight(self, utc: i64) -> bool {
+        let timespans = self.timespans();
+        let offset = timespans.get(timespans.utc_index(utc));
+        if offset.dst_offset != 0 {
+            return offset.dst_offset > 0;
+        }
+        let neighbours = [
+            timespans.previous_transition(utc).map(|time| time - 1),
+            timespans.next_transition(utc),
+        ];
+        neighbours.iter().flatten().any(|&time| {
+            let neighbour = timespans.get(timespans.utc_index(time));
+            neighbour.utc_offset == offset.utc_offset && neighbour.dst_offset < 0
+        })
+    }
+}
+
+/// Returns the locales whose names are compiled in.
+///
+/// ```
+/// assert!(chrono_tz::name_locales().any(|locale| locale == "en"));
+/// ```
+pub fn name_locales() -> impl Iterator<Item = &'static str> {
+    LOCALE_NAMES.iter().map(|names| names.locale)
+}
+
+/// Looks a name up in a locale, and then in each of its parents.
+fn find_in_locale(
+    locale: &str,
+    mut find: impl FnMut(&'static LocaleNames) -> Option<&'static str>,
+) -> Option<&'static str> {
+    let mut locale = locale;
+    loop {
+        let names = LOCALE_NAMES
+            .iter()
+            .find(|names| same_locale(names.locale, locale));
+        if let Some(name) = names.and_then(&mut find) {
+            return Some(name);
+        }
+        locale = &locale[..locale.rfind(['-', '_'])?];
+    }
+}
+
+/// Compares locale identifiers ignoring case and whether subtags are separated by
+/// hyphens or underscores.
+fn same_locale(a: &str, b: &str) -> bool {
+    let normalize = |byte: u8| match byte {
+        b'-' => b'_',
+        byte => byte.to_ascii_lowercase(),
+    };
+    a.len() == b.len() && a.bytes().map(normalize).eq(b.bytes().map(normalize))
+}
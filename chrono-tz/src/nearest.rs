@@ -0,0 +1,128 @@
+//! Finding the zones whose principal locations are closest to a point.
+//!
+//! Distances are measured along great circles of a spherical Earth. The
+//! trigonometric functions come from the standard library, or from `libm`
+//! without it.
+
+#[cfg(not(feature = "std"))]
+use libm::{atan2, sincos as sin_cos, sqrt};
+
+use crate::timezones::{Tz, TZ_VARIANTS};
+
+/// The mean radius of the Earth, in kilometres.
+const EARTH_RADIUS: f64 = 6371.0088;
+
+impl Tz {
+    /// Returns the zone whose principal location is closest to a point, given in
+    /// degrees north and east, or `None` if no zone has a location or the
+    /// coordinates are out of range.
+    ///
+    /// The latitude must be between -90 and 90, and the longitude between -180
+    /// and 180, both included.
+    ///
+    /// This is only a rough guess near borders, as each zone is represented by a
+    /// single city. It takes into account the zones listed in the tables of the
+    /// tz database, which include some links, such as `Europe/Vaduz`, that
+    /// stand for a country of their own.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// // Orléans
+    /// assert_eq!(Tz::nearest(47.90, 1.90), Some(Tz::Europe__Paris));
+    /// ```
+    pub fn nearest(latitude: f64, longitude: f64) -> Option<Tz> {
+        let mut nearest = [(Tz::UTC, 0.0)];
+        Tz::nearest_k(latitude, longitude, &mut nearest)
+            .first()
+            .map(|&(tz, _)| tz)
+    }
+
+    /// Finds the zones whose principal locations are closest to a point, given
+    /// in degrees north and east, as [`Tz::nearest`] does.
+    ///
+    /// Up to `nearest.len()` zones are written to `nearest`, closest first,
+    /// along with their distances in kilometres. The part of `nearest` that was
+    /// filled is returned, which is empty if the coordinates are out of range.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// // Windsor, Ontario, which is across the river from Detroit
+    /// let mut nearest = [(Tz::UTC, 0.0); 5];
+    /// let nearest = Tz::nearest_k(42.30, -83.02, &mut nearest);
+    /// assert_eq!(nearest.len(), 5);
+    /// assert_eq!(nearest[0].0, Tz::America__Detroit);
+    /// assert!(nearest[0].1 < 5.0);
+    /// assert!(nearest.iter().any(|&(tz, _)| tz == Tz::America__Toronto));
+    /// ```
+    pub fn nearest_k(latitude: f64, longitude: f64, nearest: &mut [(Tz, f64)]) -> &mut [(Tz, f64)] {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return &mut nearest[..0];
+        }
+        let point = unit_vector(latitude, longitude);
+        let mut len = 0;
+        for &tz in TZ_VARIANTS.iter() {
+            let location = match tz.location() {
+                Some(location) => location,
+                None => continue,
+            };
+            // Links that are not listed themselves share the location of their zone.
+            if tz.is_link() && tz.canonical().location() == Some(location) {
+                continue;
+            }
+            let distance = angle(
+                point,
+                unit_vector(location.latitude(), location.longitude()),
+            ) * EARTH_RADIUS;
+            // Insert into the sorted prefix, dropping the farthest zone if it is full.
+            let index = nearest[..len].partition_point(|&(_, d)| d <= distance);
+            if index == nearest.len() {
+                continue;
+            }
+            if len < nearest.len() {
+                len += 1;
+            }
+            nearest[index..len].rotate_right(1);
+            nearest[index] = (tz, distance);
+        }
+        &mut nearest[..len]
+    }
+}
+
+/// The point on the unit sphere at a latitude and longitude in degrees.
+fn unit_vector(latitude: f64, longitude: f64) -> [f64; 3] {
+    let (latitude, longitude) = (latitude.to_radians(), longitude.to_radians());
+    let (sin_lat, cos_lat) = sin_cos(latitude);
+    let (sin_lon, cos_lon) = sin_cos(longitude);
+    [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+}
+
+/// The angle in radians between two points on the unit sphere.
+fn angle(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let cross = [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ];
+    let sin = sqrt(cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]);
+    let cos = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    // Unlike the arc cosine of the dot product, this stays precise for points
+    // that are close together.
+    atan2(sin, cos)
+}
+
+#[cfg(feature = "std")]
+fn sin_cos(x: f64) -> (f64, f64) {
+    x.sin_cos()
+}
+
+#[cfg(feature = "std")]
+fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(feature = "std")]
+fn atan2(y: f64, x: f64) -> f64 {
+    y.atan2(x)
+}
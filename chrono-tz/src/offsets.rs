@@ -0,0 +1,113 @@
+//! Finding the zones consistent with offsets observed at a few instants, such as
+//! those a browser reports for January and July.
+
+use core::iter::FusedIterator;
+
+use chrono::{DateTime, FixedOffset, Offset, TimeZone};
+
+use crate::timezones::{Tz, TZ_VARIANTS};
+
+/// How likely a zone is to be the one meant, from the most likely.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Rank {
+    /// Zones with a location, which stand for a region of their own.
+    Located,
+    /// Zones without a location, such as `Etc/GMT+5`.
+    Unlocated,
+    Link,
+}
+
+impl Rank {
+    fn of(tz: Tz) -> Rank {
+        if tz.is_link() {
+            Rank::Link
+        } else if tz.location().is_some() {
+            Rank::Located
+        } else {
+            Rank::Unlocated
+        }
+    }
+
+    fn next(self) -> Option<Rank> {
+        match self {
+            Rank::Located => Some(Rank::Unlocated),
+            Rank::Unlocated => Some(Rank::Link),
+            Rank::Link => None,
+        }
+    }
+}
+
+/// An iterator over the zones whose offsets agree with every one of a set of
+/// samples.
+///
+/// This `struct` is created by [`zones_with_offsets`].
+#[derive(Clone, Debug)]
+pub struct OffsetZones<'a> {
+    samples: &'a [DateTime<FixedOffset>],
+    /// The rank of the zones being walked through, or `None` once all have been.
+    rank: Option<Rank>,
+    index: usize,
+}
+
+impl<'a> Iterator for OffsetZones<'a> {
+    type Item = Tz;
+
+    fn next(&mut self) -> Option<Tz> {
+        loop {
+            let rank = self.rank?;
+            let tz = match TZ_VARIANTS.get(self.index) {
+                Some(&tz) => tz,
+                None => {
+                    self.rank = rank.next();
+                    self.index = 0;
+                    continue;
+                }
+            };
+            self.index += 1;
+            if Rank::of(tz) == rank && self.samples.iter().all(|sample| matches(tz, sample)) {
+                return Some(tz);
+            }
+        }
+    }
+}
+
+impl<'a> FusedIterator for OffsetZones<'a> {}
+
+/// Returns whether a zone has the offset of a sample at its instant.
+fn matches(tz: Tz, sample: &DateTime<FixedOffset>) -> bool {
+    let offset = tz.offset_from_utc_datetime(&sample.naive_utc());
+    offset.fix() == *sample.offset()
+}
+
+/// Returns the zones whose offset from UTC agrees with every sample, each of
+/// which is an instant along with the offset observed then.
+///
+/// This can tell apart zones that differ only in their daylight saving time,
+/// given samples from both winter and summer, or over past years. With no
+/// samples, every zone is returned.
+///
+/// The zones are ranked by how likely they are to be meant, as the tz database
+/// has no figures on population: zones with a
+/// [`Location`](crate::Location) come first, followed by the other zones, such
+/// as `Etc/GMT+5`, and finally by links. Each group is in alphabetical order.
+///
+/// ```
+/// use chrono::{DateTime, FixedOffset};
+/// use chrono_tz::{zones_with_offsets, Tz};
+///
+/// let samples = [
+///     DateTime::<FixedOffset>::parse_from_rfc3339("2024-01-15T12:00:00+01:00").unwrap(),
+///     DateTime::<FixedOffset>::parse_from_rfc3339("2024-07-15T12:00:00+02:00").unwrap(),
+/// ];
+/// let zones: Vec<Tz> = zones_with_offsets(&samples).collect();
+/// assert!(zones.contains(&Tz::Europe__Berlin));
+/// assert!(!zones.contains(&Tz::Africa__Lagos));
+/// assert_eq!(zones[0], Tz::Africa__Ceuta);
+/// ```
+pub fn zones_with_offsets(samples: &[DateTime<FixedOffset>]) -> OffsetZones<'_> {
+    OffsetZones {
+        samples,
+        rank: Some(Rank::Located),
+        index: 0,
+    }
+}
@@ -0,0 +1,680 @@
+//! Time zones described by POSIX TZ strings, such as `EST5EDT,M3.2.0,M11.1.0`.
+//!
+//! The format is the one of the `TZ` environment variable in [POSIX], with the
+//! extensions of [RFC 8536] section 3.3: transition times may be negative or
+//! exceed 24 hours. Everything here works without the standard library, so
+//! abbreviations are kept inline and limited in length. That is also why the
+//! rules are evaluated here rather than by `parse_zoneinfo`, which needs it.
+//!
+//! [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/basedefs/V1_chap08.html
+//! [RFC 8536]: https://www.rfc-editor.org/rfc/rfc8536#section-3.3
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str::FromStr;
+
+use chrono::{
+    Datelike, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone,
+};
+
+use crate::timezone_impl::{OffsetComponents, OffsetName};
+
+/// The longest abbreviation that can be stored, in bytes.
+const MAX_ABBREVIATION_LEN: usize = 16;
+
+/// The number of days from 0001-01-01 to 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i64 = 719_163;
+
+/// The rules used when a TZ string names a daylight saving time abbreviation
+/// without saying when it applies, as in `EST5EDT`. These are the current
+/// United States rules, which is what most implementations fall back to.
+const DEFAULT_RULES: [RuleTransition; 2] = [
+    RuleTransition {
+        date: RuleDate::MonthWeekday {
+            month: 3,
+            week: 2,
+            weekday: 0,
+        },
+        time: 2 * 3600,
+    },
+    RuleTransition {
+        date: RuleDate::MonthWeekday {
+            month: 11,
+            week: 1,
+            weekday: 0,
+        },
+        time: 2 * 3600,
+    },
+];
+
+/// A time zone described by a POSIX TZ string.
+///
+/// Unlike [`Tz`](crate::Tz), which covers the whole history of a zone, a TZ
+/// string describes a single standard offset and, optionally, a daylight saving
+/// time rule that applies every year.
+///
+/// ```
+/// use chrono::TimeZone;
+/// use chrono_tz::{OffsetComponents, OffsetName, PosixTz};
+///
+/// let tz: PosixTz = "EST5EDT,M3.2.0,M11.1.0".parse().unwrap();
+/// let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(dt.to_rfc3339(), "2024-07-01T12:00:00-04:00");
+/// assert_eq!(dt.offset().abbreviation(), Some("EDT"));
+/// assert_eq!(dt.offset().dst_offset(), chrono::Duration::hours(1));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct PosixTz {
+    std_abbreviation: Abbreviation,
+    std_offset: i32,
+    dst: Option<(Abbreviation, DstRule)>,
+}
+
+impl PosixTz {
+    /// Returns the daylight saving time rule, with the standard and daylight
+    /// saving time abbreviations, if there is one.
+    #[cfg(feature = "std")]
+    pub(crate) fn dst_rule(&self) -> Option<(DstRule, &str, &str)> {
+        self.dst.as_ref().map(|(abbreviation, rule)| {
+            (*rule, self.std_abbreviation.as_str(), abbreviation.as_str())
+        })
+    }
+
+    /// Converts the zone to the form `parse_zoneinfo` writes into TZif footers.
+    #[cfg(feature = "tzif-writer")]
+    pub(crate) fn to_zoneinfo(self) -> parse_zoneinfo::posix::PosixTz {
+        use parse_zoneinfo::posix::{PosixDate, PosixDst, PosixTransition};
+
+        let transition = |transition: RuleTransition| PosixTransition {
+            date: match transition.date {
+                RuleDate::Julian(day) => PosixDate::Julian(day),
+                RuleDate::Ordinal(day) => PosixDate::Ordinal(day),
+                RuleDate::MonthWeekday {
+                    month,
+                    week,
+                    weekday,
+                } => PosixDate::MonthWeekday {
+                    month,
+                    week,
+                    weekday,
+                },
+            },
+            time: i64::from(transition.time),
+        };
+        parse_zoneinfo::posix::PosixTz {
+            std_abbreviation: self.std_abbreviation.as_str().to_owned(),
+            std_offset: i64::from(self.std_offset),
+            dst: self.dst.map(|(abbreviation, rule)| PosixDst {
+                abbreviation: abbreviation.as_str().to_owned(),
+                utc_offset: i64::from(rule.dst_offset),
+                start: transition(rule.start),
+                end: transition(rule.end),
+            }),
+        }
+    }
+
+    fn offset(&self, is_dst: bool) -> PosixOffset {
+        PosixOffset { tz: *self, is_dst }
+    }
+}
+
+impl Debug for PosixTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Formats the zone as a TZ string, leaving out the parts that have default
+/// values.
+impl Display for PosixTz {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.std_abbreviation)?;
+        write_hms(f, -self.std_offset)?;
+        if let Some((abbreviation, rule)) = &self.dst {
+            write!(f, "{}", abbreviation)?;
+            if rule.dst_offset != self.std_offset + 3600 {
+                write_hms(f, -rule.dst_offset)?;
+            }
+            write!(f, ",{},{}", rule.start, rule.end)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PosixTz {
+    type Err = PosixTzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { s: s.as_bytes() };
+        let std_abbreviation = parser.abbreviation()?;
+        let std_offset = -parser.offset()?;
+        let dst = if parser.s.is_empty() {
+            None
+        } else {
+            let abbreviation = parser.abbreviation()?;
+            let dst_offset = match parser.peek() {
+                Some(b'+' | b'-' | b'0'..=b'9') => -parser.offset()?,
+                _ => std_offset + 3600,
+            };
+            if dst_offset.abs() >= 24 * 3600 {
+                return Err(PosixTzError::InvalidOffset);
+            }
+            let [start, end] = if parser.s.is_empty() {
+                DEFAULT_RULES
+            } else {
+                parser.expect(b',')?;
+                let start = parser.rule_transition()?;
+                parser.expect(b',')?;
+                [start, parser.rule_transition()?]
+            };
+            let rule = DstRule {
+                std_offset,
+                dst_offset,
+                start,
+                end,
+            };
+            Some((abbreviation, rule))
+        };
+        if !parser.s.is_empty() {
+            return Err(PosixTzError::TrailingCharacters);
+        }
+        Ok(PosixTz {
+            std_abbreviation,
+            std_offset,
+            dst,
+        })
+    }
+}
+
+impl TimeZone for PosixTz {
+    type Offset = PosixOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        // See `Tz::offset_from_local_date` for why a single offset is preferred here.
+        let earliest = self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN));
+        let latest = self.offset_from_local_datetime(&local.and_hms_opt(23, 59, 59).unwrap());
+        use chrono::LocalResult::*;
+        match (earliest, latest) {
+            (result @ Single(_), _) => result,
+            (_, result @ Single(_)) => result,
+            (Ambiguous(offset, _), _) => Single(offset),
+            (_, Ambiguous(offset, _)) => Single(offset),
+            (None, None) => None,
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        match &self.dst {
+            Some((_, rule)) => rule
+                .local_result(local.and_utc().timestamp())
+                .map(|is_dst| self.offset(is_dst)),
+            None => LocalResult::Single(self.offset(false)),
+        }
+    }
+
+    #[allow(deprecated)]
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+        let is_dst = match &self.dst {
+            Some((_, rule)) => rule.is_dst(utc.and_utc().timestamp()),
+            None => false,
+        };
+        self.offset(is_dst)
+    }
+}
+
+/// The offset in effect in a [`PosixTz`] at a given time.
+///
+/// It exposes the same information as [`TzOffset`](crate::TzOffset) through
+/// [`OffsetComponents`] and [`OffsetName`]. As a TZ string does not identify a
+/// zone of the tz database, [`OffsetName::tz_id`] is always empty.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct PosixOffset {
+    tz: PosixTz,
+    is_dst: bool,
+}
+
+impl PosixOffset {
+    fn total_offset(&self) -> i32 {
+        match &self.tz.dst {
+            Some((_, rule)) if self.is_dst => rule.dst_offset,
+            _ => self.tz.std_offset,
+        }
+    }
+}
+
+impl OffsetComponents for PosixOffset {
+    fn base_utc_offset(&self) -> Duration {
+        Duration::seconds(self.tz.std_offset as i64)
+    }
+
+    fn dst_offset(&self) -> Duration {
+        Duration::seconds((self.total_offset() - self.tz.std_offset) as i64)
+    }
+}
+
+impl OffsetName for PosixOffset {
+    /// Always returns an empty string.
+    ///
+    /// A TZ string is not the name of a zone of the tz database, and the zone
+    /// only keeps its parsed form, so there is no string to borrow here. Use
+    /// the `Display` implementation of [`PosixTz`] to get the TZ string back.
+    fn tz_id(&self) -> &str {
+        ""
+    }
+
+    fn abbreviation(&self) -> Option<&str> {
+        match &self.tz.dst {
+            Some((abbreviation, _)) if self.is_dst => Some(abbreviation.as_str()),
+            _ => Some(self.tz.std_abbreviation.as_str()),
+        }
+    }
+}
+
+impl Offset for PosixOffset {
+    fn fix(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.total_offset()).unwrap()
+    }
+}
+
+impl Display for PosixOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.abbreviation().unwrap_or_default())
+    }
+}
+
+impl Debug for PosixOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// An error encountered while parsing a TZ string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PosixTzError {
+    /// An abbreviation is missing, shorter than three characters, too long,
+    /// or contains characters that are not allowed.
+    InvalidAbbreviation,
+    /// An offset is missing, malformed, or not less than 24 hours.
+    InvalidOffset,
+    /// The daylight saving time rule is malformed.
+    InvalidRule,
+    /// The string continues after a complete TZ string.
+    TrailingCharacters,
+}
+
+impl Display for PosixTzError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PosixTzError::InvalidAbbreviation => f.write_str("invalid time zone abbreviation"),
+            PosixTzError::InvalidOffset => f.write_str("invalid UTC offset"),
+            PosixTzError::InvalidRule => f.write_str("invalid daylight saving time rule"),
+            PosixTzError::TrailingCharacters => f.write_str("trailing characters in TZ string"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PosixTzError {}
+
+/// A time zone abbreviation, stored inline.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Abbreviation {
+    len: u8,
+    bytes: [u8; MAX_ABBREVIATION_LEN],
+}
+
+impl Abbreviation {
+    fn as_str(&self) -> &str {
+        // Only ASCII is ever stored.
+        core::str::from_utf8(&self.bytes[..self.len as usize]).unwrap()
+    }
+}
+
+impl Display for Abbreviation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let abbreviation = self.as_str();
+        if abbreviation.bytes().all(|b| b.is_ascii_alphabetic()) {
+            f.write_str(abbreviation)
+        } else {
+            write!(f, "<{}>", abbreviation)
+        }
+    }
+}
+
+/// A yearly switch to and from daylight saving time.
+///
+/// Offsets are in seconds east of UTC, and `dst_offset` is the total offset
+/// while daylight saving time is in effect.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct DstRule {
+    pub(crate) std_offset: i32,
+    pub(crate) dst_offset: i32,
+    pub(crate) start: RuleTransition,
+    pub(crate) end: RuleTransition,
+}
+
+impl DstRule {
+    /// Returns the UTC timestamps at which daylight saving time starts and ends
+    /// in the given year, unless the year is outside the range of `NaiveDate`.
+    pub(crate) fn transitions(&self, year: i32) -> Option<(i64, i64)> {
+        let start = self.start.local_timestamp(year)? - self.std_offset as i64;
+        let end = self.end.local_timestamp(year)? - self.dst_offset as i64;
+        Some((start, end))
+    }
+
+    /// Returns whether daylight saving time is in effect at a UTC timestamp.
+    pub(crate) fn is_dst(&self, timestamp: i64) -> bool {
+        // Transition times can be up to a week past the end of their year, so the
+        // last transition before the timestamp may belong to the year before.
+        let year = self.year_of(timestamp);
+        // When daylight saving time ends and starts again at the same instant, as
+        // it does in zones that keep it all year, the start wins.
+        let mut latest = None;
+        for (start, end) in (year - 2..=year + 1).filter_map(|year| self.transitions(year)) {
+            for (time, is_dst) in [(end, false), (start, true)] {
+                if time <= timestamp && latest.map_or(true, |(latest, _)| time >= latest) {
+                    latest = Some((time, is_dst));
+                }
+            }
+        }
+        latest.map_or(false, |(_, is_dst)| is_dst)
+    }
+
+    /// Returns the first time after a UTC timestamp at which daylight saving
+    /// time starts or ends.
+    ///
+    /// Transitions that change nothing, as in zones that keep daylight saving
+    /// time all year, are skipped, so there may not be one.
+    pub(crate) fn next_transition(&self, timestamp: i64) -> Option<i64> {
+        let year = self.year_of(timestamp);
+        (year - 1..=year + 2)
+            .filter_map(|year| self.transitions(year))
+            .flat_map(|(start, end)| [start, end])
+            .filter(|&time| time > timestamp && self.is_dst(time - 1) != self.is_dst(time))
+            .min()
+    }
+
+    /// Returns the last time before a UTC timestamp at which daylight saving
+    /// time started or ended, with the same caveat as `next_transition`.
+    pub(crate) fn previous_transition(&self, timestamp: i64) -> Option<i64> {
+        let year = self.year_of(timestamp);
+        (year - 2..=year + 1)
+            .filter_map(|year| self.transitions(year))
+            .flat_map(|(start, end)| [start, end])
+            .filter(|&time| time < timestamp && self.is_dst(time - 1) != self.is_dst(time))
+            .max()
+    }
+
+    /// Returns the local year in standard time at a UTC timestamp, clamped to
+    /// the range of `NaiveDate`.
+    fn year_of(&self, timestamp: i64) -> i32 {
+        let days = (timestamp + self.std_offset as i64).div_euclid(86_400);
+        i32::try_from(days + UNIX_EPOCH_DAYS_FROM_CE)
+            .ok()
+            .and_then(NaiveDate::from_num_days_from_ce_opt)
+            .map_or(
+                if days < 0 {
+                    NaiveDate::MIN
+                } else {
+                    NaiveDate::MAX
+                }
+                .year(),
+                |date| date.year(),
+            )
+    }
+
+    /// Returns whether a local timestamp falls in standard time, daylight
+    /// saving time, or both, in the order in which they occur.
+    pub(crate) fn local_result(&self, local: i64) -> LocalResult<bool> {
+        let std = !self.is_dst(local - self.std_offset as i64);
+        let dst = self.is_dst(local - self.dst_offset as i64);
+        match (std, dst) {
+            // The earlier of the two instants is the one with the larger offset.
+            (true, true) if self.dst_offset > self.std_offset => {
+                LocalResult::Ambiguous(true, false)
+            }
+            (true, true) => LocalResult::Ambiguous(false, true),
+            (true, false) => LocalResult::Single(false),
+            (false, true) => LocalResult::Single(true),
+            (false, false) => LocalResult::None,
+        }
+    }
+}
+
+/// The time of year at which daylight saving time starts or ends.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct RuleTransition {
+    pub(crate) date: RuleDate,
+    /// Seconds after local midnight, which can be negative or exceed a day.
+    pub(crate) time: i32,
+}
+
+impl RuleTransition {
+    fn local_timestamp(&self, year: i32) -> Option<i64> {
+        let date = self.date.date(year)?;
+        Some(date.and_time(NaiveTime::MIN).and_utc().timestamp() + self.time as i64)
+    }
+}
+
+impl Display for RuleTransition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date)?;
+        if self.time != 2 * 3600 {
+            f.write_str("/")?;
+            write_hms(f, self.time)?;
+        }
+        Ok(())
+    }
+}
+
+/// The day of the year on which a transition happens.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum RuleDate {
+    /// `Jn`: the day of the year from 1 to 365, never counting February 29.
+    Julian(u16),
+    /// `n`: the day of the year from 0 to 365, counting February 29.
+    Ordinal(u16),
+    /// `Mm.w.d`: day `d` of week `w` of month `m`, with Sunday as day 0 and
+    /// week 5 meaning the last one of the month.
+    MonthWeekday { month: u8, week: u8, weekday: u8 },
+}
+
+impl RuleDate {
+    /// Returns the date in the given year.
+    fn date(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            RuleDate::Julian(day) => {
+                let is_leap_year = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+                let leap_day = (is_leap_year && day >= 60) as u32;
+                NaiveDate::from_yo_opt(year, day as u32 + leap_day)
+            }
+            // Day 365 of a common year is the first day of the next one.
+            RuleDate::Ordinal(day) => {
+                NaiveDate::from_yo_opt(year, 1)?.checked_add_signed(Duration::days(day as i64))
+            }
+            RuleDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => {
+                let first = NaiveDate::from_ymd_opt(year, month as u32, 1)?;
+                let first_weekday = first.weekday().num_days_from_sunday() as i64;
+                let days = (weekday as i64 - first_weekday).rem_euclid(7) + (week as i64 - 1) * 7;
+                let mut date = first.checked_add_signed(Duration::days(days))?;
+                // Week 5 means the last one, which may be the fourth.
+                while date.month() != month as u32 {
+                    date = date.checked_sub_signed(Duration::days(7))?;
+                }
+                Some(date)
+            }
+        }
+    }
+}
+
+impl Display for RuleDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleDate::Julian(day) => write!(f, "J{}", day),
+            RuleDate::Ordinal(day) => write!(f, "{}", day),
+            RuleDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => write!(f, "M{}.{}.{}", month, week, weekday),
+        }
+    }
+}
+
+/// Writes a duration in seconds as `[-]h[:mm[:ss]]`.
+fn write_hms(f: &mut Formatter<'_>, seconds: i32) -> fmt::Result {
+    if seconds < 0 {
+        f.write_str("-")?;
+    }
+    let seconds = seconds.unsigned_abs();
+    write!(f, "{}", seconds / 3600)?;
+    if seconds % 3600 != 0 {
+        write!(f, ":{:02}", seconds / 60 % 60)?;
+    }
+    if seconds % 60 != 0 {
+        write!(f, ":{:02}", seconds % 60)?;
+    }
+    Ok(())
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.first().copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.s = &self.s[1..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), PosixTzError> {
+        match self.eat(byte) {
+            true => Ok(()),
+            false => Err(PosixTzError::InvalidRule),
+        }
+    }
+
+    /// Parses an alphabetic abbreviation, or a quoted one that can also contain
+    /// digits and signs.
+    fn abbreviation(&mut self) -> Result<Abbreviation, PosixTzError> {
+        let quoted = self.eat(b'<');
+        let len = self
+            .s
+            .iter()
+            .take_while(|&&b| match quoted {
+                true => b.is_ascii_alphanumeric() || b == b'+' || b == b'-',
+                false => b.is_ascii_alphabetic(),
+            })
+            .count();
+        let (abbreviation, rest) = self.s.split_at(len);
+        self.s = rest;
+        if !(3..=MAX_ABBREVIATION_LEN).contains(&len) || (quoted && !self.eat(b'>')) {
+            return Err(PosixTzError::InvalidAbbreviation);
+        }
+        let mut bytes = [0; MAX_ABBREVIATION_LEN];
+        bytes[..len].copy_from_slice(abbreviation);
+        Ok(Abbreviation {
+            len: len as u8,
+            bytes,
+        })
+    }
+
+    /// Parses an offset, in seconds west of UTC.
+    fn offset(&mut self) -> Result<i32, PosixTzError> {
+        match self.hms(24) {
+            Some(offset) if offset.abs() < 24 * 3600 => Ok(offset),
+            _ => Err(PosixTzError::InvalidOffset),
+        }
+    }
+
+    fn rule_transition(&mut self) -> Result<RuleTransition, PosixTzError> {
+        let date = if self.eat(b'J') {
+            match self.number(3) {
+                Some(day @ 1..=365) => RuleDate::Julian(day as u16),
+                _ => return Err(PosixTzError::InvalidRule),
+            }
+        } else if self.eat(b'M') {
+            let month = self.number(2);
+            self.expect(b'.')?;
+            let week = self.number(1);
+            self.expect(b'.')?;
+            let weekday = self.number(1);
+            match (month, week, weekday) {
+                (Some(month @ 1..=12), Some(week @ 1..=5), Some(weekday @ 0..=6)) => {
+                    RuleDate::MonthWeekday {
+                        month: month as u8,
+                        week: week as u8,
+                        weekday: weekday as u8,
+                    }
+                }
+                _ => return Err(PosixTzError::InvalidRule),
+            }
+        } else {
+            match self.number(3) {
+                Some(day @ 0..=365) => RuleDate::Ordinal(day as u16),
+                _ => return Err(PosixTzError::InvalidRule),
+            }
+        };
+        let time = if self.eat(b'/') {
+            self.hms(167).ok_or(PosixTzError::InvalidRule)?
+        } else {
+            2 * 3600
+        };
+        Ok(RuleTransition { date, time })
+    }
+
+    /// Parses `[+-]h[:mm[:ss]]` into seconds.
+    fn hms(&mut self, max_hours: i32) -> Option<i32> {
+        let sign = if self.eat(b'-') {
+            -1
+        } else {
+            self.eat(b'+');
+            1
+        };
+        let hours = self.number(3).filter(|&h| h <= max_hours)?;
+        let mut seconds = hours * 3600;
+        if self.eat(b':') {
+            seconds += self.number(2).filter(|&m| m < 60)? * 60;
+            if self.eat(b':') {
+                seconds += self.number(2).filter(|&s| s < 60)?;
+            }
+        }
+        Some(sign * seconds)
+    }
+
+    /// Parses a decimal number of at most `max_digits` digits.
+    fn number(&mut self, max_digits: usize) -> Option<i32> {
+        let len = self.s.iter().take_while(|b| b.is_ascii_digit()).count();
+        if len == 0 || len > max_digits {
+            return None;
+        }
+        let (digits, rest) = self.s.split_at(len);
+        self.s = rest;
+        Some(
+            digits
+                .iter()
+                .fold(0, |n, &digit| n * 10 + (digit - b'0') as i32),
+        )
+    }
+}
@@ -0,0 +1,172 @@
+//! Resolving zone names through interchangeable sources of zones, such as the
+//! compiled database or a zoneinfo directory.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::dynamic::DynamicTz;
+use crate::timezones::Tz;
+
+/// A source of zones, which resolves names such as `"Europe/London"` to them.
+///
+/// Providers can be chained with [`TzProvider::or`], so that names one does not
+/// know are looked up in another, or be put in a `Vec` to be asked in turn:
+///
+/// ```no_run
+/// use chrono_tz::{CompiledTz, TzProvider, ZoneinfoDir};
+///
+/// let provider = ZoneinfoDir::new("/usr/share/zoneinfo").or(CompiledTz);
+/// let tz = provider.resolve("America/Nuuk").unwrap();
+/// ```
+pub trait TzProvider {
+    /// Returns the zone with a name, or `None` if this provider does not have it.
+    fn resolve(&self, name: &str) -> Option<DynamicTz>;
+
+    /// Chains another provider, which is asked for the zones this one does not
+    /// have.
+    fn or<P: TzProvider>(self, fallback: P) -> Fallback<Self, P>
+    where
+        Self: Sized,
+    {
+        Fallback {
+            first: self,
+            fallback,
+        }
+    }
+}
+
+/// The zones compiled into the crate, which [`Tz::from_str`](core::str::FromStr)
+/// resolves.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompiledTz;
+
+impl TzProvider for CompiledTz {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        name.parse::<Tz>().ok().map(DynamicTz::from)
+    }
+}
+
+/// The TZif files of a zoneinfo directory, such as `/usr/share/zoneinfo`.
+///
+/// Files are read the first time their zone is asked for, and kept from then
+/// on, as are the names that have no file. Files that cannot be read as TZif
+/// count as missing, so that a fallback is used instead.
+#[derive(Debug)]
+pub struct ZoneinfoDir {
+    root: PathBuf,
+    cache: Mutex<HashMap<String, Option<DynamicTz>>>,
+}
+
+impl ZoneinfoDir {
+    /// Reads zones from a directory.
+    pub fn new<P: Into<PathBuf>>(root: P) -> ZoneinfoDir {
+        ZoneinfoDir {
+            root: root.into(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The directory zones are read from.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Forgets the zones read so far, so that files are read again, such as
+    /// after the system's database was updated.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    fn read(&self, name: &str) -> Option<DynamicTz> {
+        // Names must stay within the directory.
+        let path = Path::new(name);
+        if name.is_empty()
+            || !path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return None;
+        }
+        let data = fs::read(self.root.join(path)).ok()?;
+        DynamicTz::from_tzif(name, &data).ok()
+    }
+}
+
+impl TzProvider for ZoneinfoDir {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(tz) = cache.get(name) {
+            return tz.clone();
+        }
+        let tz = self.read(name);
+        cache.insert(name.to_owned(), tz.clone());
+        tz
+    }
+}
+
+/// Zones kept in memory, by name.
+impl TzProvider for HashMap<String, DynamicTz> {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        self.get(name).cloned()
+    }
+}
+
+/// Zones kept in memory, by name.
+impl TzProvider for BTreeMap<String, DynamicTz> {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        self.get(name).cloned()
+    }
+}
+
+#[cfg(feature = "runtime-tzdata")]
+impl TzProvider for crate::DynamicTzSet {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        self.get(name).cloned()
+    }
+}
+
+impl<P: TzProvider + ?Sized> TzProvider for &P {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        (**self).resolve(name)
+    }
+}
+
+impl<P: TzProvider + ?Sized> TzProvider for Box<P> {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        (**self).resolve(name)
+    }
+}
+
+/// Providers that are asked in turn, for chains that are only known at runtime.
+impl<P: TzProvider> TzProvider for [P] {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        self.iter().find_map(|provider| provider.resolve(name))
+    }
+}
+
+/// Providers that are asked in turn, for chains that are only known at runtime.
+impl<P: TzProvider> TzProvider for Vec<P> {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        self.as_slice().resolve(name)
+    }
+}
+
+/// Two providers, the second of which is asked for the zones the first does
+/// not have.
+///
+/// This `struct` is created by [`TzProvider::or`].
+#[derive(Clone, Debug)]
+pub struct Fallback<A, B> {
+    first: A,
+    fallback: B,
+}
+
+impl<A: TzProvider, B: TzProvider> TzProvider for Fallback<A, B> {
+    fn resolve(&self, name: &str) -> Option<DynamicTz> {
+        self.first
+            .resolve(name)
+            .or_else(|| self.fallback.resolve(name))
+    }
+}
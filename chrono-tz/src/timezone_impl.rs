@@ -1,11 +1,12 @@
-use core::cmp::Ordering;
+use core::cmp;
 use core::fmt::{Debug, Display, Error, Formatter, Write};
 
 use chrono::{
     Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
 };
 
-use crate::binary_search::binary_search;
+use crate::compact::{CompactIter, CompactTable};
+use crate::posix::DstRule;
 use crate::timezones::Tz;
 
 /// Returns [`Tz::UTC`].
@@ -15,6 +16,9 @@ impl Default for Tz {
     }
 }
 
+/// No offset is a day or more away from UTC.
+const DAY: i64 = 86_400;
+
 /// An Offset that applies for a period of time
 ///
 /// For example, [`::US::Eastern`] is composed of at least two
@@ -52,16 +56,16 @@ impl Display for FixedTimespan {
         let mins = (minutes % 60) as u8;
         let hours = (minutes / 60) as u8;
 
-        assert!(
-            secs == 0,
-            "numeric names are not used if the offset has fractional minutes"
-        );
-
+        // The compiled tz database never uses numeric names for offsets with fractional minutes,
+        // but zones loaded at runtime may leave their abbreviations empty.
         f.write_char(sign)?;
         write!(f, "{:02}", hours)?;
-        if mins != 0 {
+        if mins != 0 || secs != 0 {
             write!(f, "{:02}", mins)?;
         }
+        if secs != 0 {
+            write!(f, "{:02}", secs)?;
+        }
         Ok(())
     }
 }
@@ -74,8 +78,10 @@ impl Debug for FixedTimespan {
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct TzOffset {
-    tz: Tz,
-    offset: FixedTimespan,
+    pub(crate) tz: Tz,
+    pub(crate) offset: FixedTimespan,
+    // There is no hint of the timespan index here: chrono turns offsets back into
+    // zones with `from_offset` before each lookup, so one would never be read.
 }
 
 /// Detailed timezone offset components that expose any special conditions currently in effect.
@@ -150,19 +156,9 @@ pub trait OffsetName {
 }
 
 impl TzOffset {
-    fn new(tz: Tz, offset: FixedTimespan) -> Self {
+    pub(crate) fn new(tz: Tz, offset: FixedTimespan) -> Self {
         TzOffset { tz, offset }
     }
-
-    fn map_localresult(tz: Tz, result: LocalResult<FixedTimespan>) -> LocalResult<Self> {
-        match result {
-            LocalResult::None => LocalResult::None,
-            LocalResult::Single(s) => LocalResult::Single(TzOffset::new(tz, s)),
-            LocalResult::Ambiguous(a, b) => {
-                LocalResult::Ambiguous(TzOffset::new(tz, a), TzOffset::new(tz, b))
-            }
-        }
-    }
 }
 
 impl OffsetComponents for TzOffset {
@@ -227,87 +223,382 @@ impl Span {
             _ => false,
         }
     }
+}
 
-    fn cmp(&self, x: i64) -> Ordering {
-        match (self.begin, self.end) {
-            (Some(a), Some(b)) if a <= x && x < b => Ordering::Equal,
-            (Some(a), Some(b)) if a <= x && b <= x => Ordering::Less,
-            (Some(_), Some(_)) => Ordering::Greater,
-            (Some(a), None) if a <= x => Ordering::Equal,
-            (Some(_), None) => Ordering::Greater,
-            (None, Some(b)) if b <= x => Ordering::Less,
-            (None, Some(_)) => Ordering::Equal,
-            (None, None) => Ordering::Equal,
+#[derive(Copy, Clone)]
+pub struct FixedTimespanSet<'a> {
+    pub first: FixedTimespan,
+    pub(crate) rest: Transitions<'a>,
+    /// The rule that keeps applying after the last transition in `rest`, if any.
+    pub(crate) rule: Option<TrailingRule>,
+}
+
+/// A daylight saving time rule that continues a set of timespans indefinitely, like the TZ
+/// string footer of a TZif file.
+///
+/// Past the last transition, the timespans of a set are followed by `std` and `dst`, at the
+/// indices right after the last one.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct TrailingRule {
+    pub(crate) std: FixedTimespan,
+    pub(crate) dst: FixedTimespan,
+    pub(crate) rule: DstRule,
+}
+
+/// The transitions of a set of timespans, each with the timespan that starts at it.
+#[derive(Copy, Clone)]
+pub(crate) enum Transitions<'a> {
+    /// Transitions kept as they are, as by zones loaded at runtime.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    Plain(&'a [(i64, FixedTimespan)]),
+    /// The transitions of compiled zones, which are decoded as they are looked at.
+    Compact(&'static CompactTable),
+}
+
+impl<'a> Transitions<'a> {
+    pub(crate) fn len(&self) -> usize {
+        match *self {
+            Transitions::Plain(rest) => rest.len(),
+            Transitions::Compact(table) => table.len(),
+        }
+    }
+
+    /// The time of the transition at `index`.
+    pub(crate) fn time(&self, index: usize) -> i64 {
+        match *self {
+            Transitions::Plain(rest) => rest[index].0,
+            Transitions::Compact(table) => table.time(index),
+        }
+    }
+
+    /// The timespan starting at the transition at `index`.
+    pub(crate) fn timespan(&self, index: usize) -> FixedTimespan {
+        match *self {
+            Transitions::Plain(rest) => rest[index].1,
+            Transitions::Compact(table) => table.timespan(index),
+        }
+    }
+
+    /// The time of the transition after the one at `index`, which is at `time`.
+    fn time_after(&self, index: usize, time: i64) -> i64 {
+        match *self {
+            Transitions::Plain(rest) => rest[index + 1].0,
+            Transitions::Compact(table) => table.time_after(index, time),
+        }
+    }
+
+    /// The time of the last transition, if any.
+    pub(crate) fn last(&self) -> Option<i64> {
+        match self.len() {
+            0 => None,
+            len => Some(self.time(len - 1)),
+        }
+    }
+
+    /// Returns the number of transitions at or before `timestamp`.
+    pub(crate) fn count_until(&self, timestamp: i64) -> usize {
+        match *self {
+            Transitions::Plain(rest) => rest.partition_point(|&(time, _)| time <= timestamp),
+            Transitions::Compact(table) => table.count_until(timestamp),
+        }
+    }
+
+    /// Iterates over the transitions in order, with the timespan starting at each.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn iter(&self) -> TransitionsIter<'a> {
+        match *self {
+            Transitions::Plain(rest) => TransitionsIter::Plain(rest.iter()),
+            Transitions::Compact(table) => TransitionsIter::Compact(table.iter()),
         }
     }
 }
 
-#[derive(Copy, Clone)]
-pub struct FixedTimespanSet {
-    pub first: FixedTimespan,
-    pub rest: &'static [(i64, FixedTimespan)],
+/// An iterator over [`Transitions`].
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) enum TransitionsIter<'a> {
+    Plain(core::slice::Iter<'a, (i64, FixedTimespan)>),
+    Compact(CompactIter<'static>),
 }
 
-impl FixedTimespanSet {
-    fn len(&self) -> usize {
+impl Iterator for TransitionsIter<'_> {
+    type Item = (i64, FixedTimespan);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            TransitionsIter::Plain(iter) => iter.next().copied(),
+            TransitionsIter::Compact(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            TransitionsIter::Plain(iter) => iter.size_hint(),
+            TransitionsIter::Compact(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for TransitionsIter<'_> {}
+
+impl FixedTimespanSet<'_> {
+    /// The number of timespans, not counting those of the trailing rule.
+    pub(crate) fn len(&self) -> usize {
         1 + self.rest.len()
     }
 
-    fn utc_span(&self, index: usize) -> Span {
+    fn rule_index(&self, is_dst: bool) -> usize {
+        self.len() + is_dst as usize
+    }
+
+    // The local times of a timespan are its UTC times, shifted by its own offset.
+    fn local_span(&self, index: usize) -> Span {
         debug_assert!(index < self.len());
+        let timespan = self.get(index);
+        let offset = timespan.utc_offset as i64 + timespan.dst_offset as i64;
         Span {
-            begin: if index == 0 {
-                None
-            } else {
-                Some(self.rest[index - 1].0)
+            begin: match index {
+                0 => None,
+                i => Some(self.rest.time(i - 1) + offset),
             },
             end: if index == self.rest.len() {
                 None
             } else {
-                Some(self.rest[index].0)
+                Some(self.rest.time(index) + offset)
             },
         }
     }
 
-    fn local_span(&self, index: usize) -> Span {
-        debug_assert!(index < self.len());
-        Span {
-            begin: if index == 0 {
-                None
-            } else {
-                let span = self.rest[index - 1];
-                Some(span.0 + span.1.utc_offset as i64 + span.1.dst_offset as i64)
-            },
-            end: if index == self.rest.len() {
-                None
-            } else if index == 0 {
-                Some(
-                    self.rest[index].0
-                        + self.first.utc_offset as i64
-                        + self.first.dst_offset as i64,
-                )
-            } else {
-                Some(
-                    self.rest[index].0
-                        + self.rest[index - 1].1.utc_offset as i64
-                        + self.rest[index - 1].1.dst_offset as i64,
-                )
+    pub(crate) fn get(&self, index: usize) -> FixedTimespan {
+        match (index, self.rule) {
+            (0, _) => self.first,
+            (i, _) if i < self.len() => self.rest.timespan(i - 1),
+            (i, Some(rule)) if i == self.len() => rule.std,
+            (_, Some(rule)) => rule.dst,
+            (_, None) => panic!("timespan index out of range"),
+        }
+    }
+
+    // Any i64 falls within exactly one timespan: the one starting at the last transition
+    // at or before it, or the first one if there is no such transition.
+    pub(crate) fn utc_index(&self, timestamp: i64) -> usize {
+        self.rule_utc_index(timestamp)
+            .unwrap_or_else(|| self.rest.count_until(timestamp))
+    }
+
+    /// Returns the timespan of the trailing rule that `timestamp` falls within, if it is past
+    /// the last transition.
+    fn rule_utc_index(&self, timestamp: i64) -> Option<usize> {
+        let rule = self.rule?;
+        match self.rest.last().map_or(true, |last| timestamp >= last) {
+            true => Some(self.rule_index(rule.rule.is_dst(timestamp))),
+            false => None,
+        }
+    }
+
+    /// Returns the time of the first transition strictly after `timestamp` that changes the
+    /// timespan in effect.
+    pub(crate) fn next_transition(&self, timestamp: i64) -> Option<i64> {
+        let start = self.rest.count_until(timestamp);
+        let changes = (start..self.rest.len()).find(|&i| self.get(i) != self.rest.timespan(i));
+        if let Some(i) = changes {
+            return Some(self.rest.time(i));
+        }
+        let rule = self.rule?;
+        let after = match self.rest.last() {
+            Some(last) => cmp::max(timestamp, last),
+            None => timestamp,
+        };
+        rule.rule.next_transition(after)
+    }
+
+    /// Returns the time of the last transition strictly before `timestamp` that changed the
+    /// timespan in effect.
+    pub(crate) fn previous_transition(&self, timestamp: i64) -> Option<i64> {
+        let last = self.rest.last();
+        if let Some(rule) = self.rule {
+            let previous = rule.rule.previous_transition(timestamp);
+            if let Some(time) = previous.filter(|&time| last.map_or(true, |last| time > last)) {
+                return Some(time);
+            }
+        }
+        let end = self.rest.count_until(timestamp.saturating_sub(1));
+        (0..end)
+            .rev()
+            .find(|&i| self.get(i) != self.rest.timespan(i))
+            .map(|i| self.rest.time(i))
+    }
+
+    /// Returns the time of the transition that skips or repeats a local timestamp, if any.
+    ///
+    /// This is the transition whose local times before and after, as bounded by `local_span`,
+    /// lie on either side of `timestamp`.
+    pub(crate) fn local_transition(&self, timestamp: i64) -> Option<i64> {
+        // No offset is anywhere near two days, which bounds how far away the transition can be.
+        const WINDOW: i64 = 2 * 86_400;
+        let mut time = timestamp - WINDOW;
+        while let Some(transition) = self.next_transition(time) {
+            if transition > timestamp + WINDOW {
+                break;
+            }
+            let offset = |index: usize| {
+                let span = self.get(index);
+                (span.utc_offset + span.dst_offset) as i64
+            };
+            let before = transition + offset(self.utc_index(transition - 1));
+            let after = transition + offset(self.utc_index(transition));
+            if cmp::min(before, after) <= timestamp && timestamp < cmp::max(before, after) {
+                return Some(transition);
+            }
+            time = transition;
+        }
+        None
+    }
+
+    // Find the timespans that the local datetime falls into: none if it was skipped, two if
+    // it is ambiguous, and one otherwise.
+    pub(crate) fn local_index(&self, timestamp: i64) -> LocalResult<usize> {
+        self.rule_local_index(timestamp).unwrap_or_else(|| {
+            let start = self.rest.count_until(timestamp - DAY);
+            let end = self.rest.count_until(timestamp + DAY);
+            self.local_index_between(timestamp, start, end)
+        })
+    }
+
+    /// Returns the timespans of the trailing rule that the local `timestamp` falls into, if
+    /// the rule decides.
+    fn rule_local_index(&self, timestamp: i64) -> Option<LocalResult<usize>> {
+        // The rule decides once both of the UTC instants the local time could refer to are
+        // past the last transition. Until then, the last timespan lasts long enough that there
+        // is no need for it.
+        let rule = self.rule?;
+        let largest_offset = cmp::max(rule.rule.std_offset, rule.rule.dst_offset) as i64;
+        match self
+            .rest
+            .last()
+            .map_or(true, |last| timestamp >= last + largest_offset)
+        {
+            true => Some(
+                rule.rule
+                    .local_result(timestamp)
+                    .map(|is_dst| self.rule_index(is_dst)),
+            ),
+            false => None,
+        }
+    }
+
+    /// Returns the timespans that the local `timestamp` falls into among those from `start`
+    /// to `end`, which are in effect a day before and a day after it when taken as UTC.
+    fn local_index_between(&self, timestamp: i64, start: usize, end: usize) -> LocalResult<usize> {
+        // Offsets are less than a day, so only these timespans can contain the local time,
+        // and a timespan in effect for the whole of that time contains it.
+        if start == end {
+            return LocalResult::Single(start);
+        }
+        let mut indices = (start..=end).filter(|&i| self.local_span(i).contains(timestamp));
+        match (indices.next(), indices.next()) {
+            (Some(earlier), Some(later)) => LocalResult::Ambiguous(earlier, later),
+            (Some(index), None) => LocalResult::Single(index),
+            _ => LocalResult::None,
+        }
+    }
+}
+
+/// Looks up many instants in a set of timespans, walking forward through its transitions
+/// rather than starting over for each instant when they come in increasing order.
+pub(crate) struct SpanCursor<'a> {
+    timespans: FixedTimespanSet<'a>,
+    earlier: TransitionCounter<'a>,
+    later: TransitionCounter<'a>,
+}
+
+impl<'a> SpanCursor<'a> {
+    pub(crate) fn new(timespans: FixedTimespanSet<'a>) -> Self {
+        SpanCursor {
+            timespans,
+            earlier: TransitionCounter::new(timespans.rest),
+            later: TransitionCounter::new(timespans.rest),
+        }
+    }
+
+    /// Returns the same as [`FixedTimespanSet::utc_index`].
+    pub(crate) fn utc_index(&mut self, timestamp: i64) -> usize {
+        match self.timespans.rule_utc_index(timestamp) {
+            Some(index) => index,
+            None => self.earlier.count_until(timestamp),
+        }
+    }
+
+    /// Returns the same as [`FixedTimespanSet::local_index`].
+    pub(crate) fn local_index(&mut self, timestamp: i64) -> LocalResult<usize> {
+        match self.timespans.rule_local_index(timestamp) {
+            Some(result) => result,
+            None => {
+                let start = self.earlier.count_until(timestamp - DAY);
+                let end = self.later.count_until(timestamp + DAY);
+                self.timespans.local_index_between(timestamp, start, end)
+            }
+        }
+    }
+}
+
+/// Counts the transitions at or before instants, walking forward from the previous count when
+/// they come in increasing order.
+struct TransitionCounter<'a> {
+    rest: Transitions<'a>,
+    count: usize,
+    /// The times of the transitions right before and at `count`, if there are any.
+    previous: Option<i64>,
+    next: Option<i64>,
+}
+
+impl<'a> TransitionCounter<'a> {
+    fn new(rest: Transitions<'a>) -> Self {
+        TransitionCounter {
+            rest,
+            count: 0,
+            previous: None,
+            next: match rest.len() {
+                0 => None,
+                _ => Some(rest.time(0)),
             },
         }
     }
 
-    fn get(&self, index: usize) -> FixedTimespan {
-        debug_assert!(index < self.len());
-        if index == 0 {
-            self.first
-        } else {
-            self.rest[index - 1].1
+    fn count_until(&mut self, timestamp: i64) -> usize {
+        // Past a few transitions, counting from scratch is faster than walking.
+        const MAX_STEPS: usize = 16;
+        if self.previous.map_or(false, |previous| timestamp < previous) {
+            return self.seek(timestamp);
+        }
+        let mut steps = 0;
+        while let Some(next) = self.next.filter(|&next| next <= timestamp) {
+            if steps == MAX_STEPS {
+                return self.seek(timestamp);
+            }
+            self.next = match self.count + 1 < self.rest.len() {
+                true => Some(self.rest.time_after(self.count, next)),
+                false => None,
+            };
+            self.previous = Some(next);
+            self.count += 1;
+            steps += 1;
         }
+        self.count
+    }
+
+    fn seek(&mut self, timestamp: i64) -> usize {
+        self.count = self.rest.count_until(timestamp);
+        self.previous = self.count.checked_sub(1).map(|i| self.rest.time(i));
+        self.next = match self.count < self.rest.len() {
+            true => Some(self.rest.time(self.count)),
+            false => None,
+        };
+        self.count
     }
 }
 
 pub trait TimeSpans {
-    fn timespans(&self) -> FixedTimespanSet;
+    fn timespans(&self) -> FixedTimespanSet<'static>;
 }
 
 impl TimeZone for Tz {
@@ -358,33 +649,11 @@ impl TimeZone for Tz {
         }
     }
 
-    // First search for a timespan that the local datetime falls into, then, if it exists,
-    // check the two surrounding timespans (if they exist) to see if there is any ambiguity.
     fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
-        let timestamp = local.and_utc().timestamp();
         let timespans = self.timespans();
-        let index = binary_search(0, timespans.len(), |i| {
-            timespans.local_span(i).cmp(timestamp)
-        });
-        TzOffset::map_localresult(
-            *self,
-            match index {
-                Ok(0) if timespans.len() == 1 => LocalResult::Single(timespans.get(0)),
-                Ok(0) if timespans.local_span(1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(0), timespans.get(1))
-                }
-                Ok(0) => LocalResult::Single(timespans.get(0)),
-                Ok(i) if timespans.local_span(i - 1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(i - 1), timespans.get(i))
-                }
-                Ok(i) if i == timespans.len() - 1 => LocalResult::Single(timespans.get(i)),
-                Ok(i) if timespans.local_span(i + 1).contains(timestamp) => {
-                    LocalResult::Ambiguous(timespans.get(i), timespans.get(i + 1))
-                }
-                Ok(i) => LocalResult::Single(timespans.get(i)),
-                Err(_) => LocalResult::None,
-            },
-        )
+        timespans
+            .local_index(local.and_utc().timestamp())
+            .map(|i| TzOffset::new(*self, timespans.get(i)))
     }
 
     #[allow(deprecated)]
@@ -393,13 +662,9 @@ impl TimeZone for Tz {
         self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
     }
 
-    // Binary search for the required timespan. Any i64 is guaranteed to fall within
-    // exactly one timespan, no matter what (so the `unwrap` is safe).
     fn offset_from_utc_datetime(&self, dt: &NaiveDateTime) -> Self::Offset {
-        let timestamp = dt.and_utc().timestamp();
         let timespans = self.timespans();
-        let index =
-            binary_search(0, timespans.len(), |i| timespans.utc_span(i).cmp(timestamp)).unwrap();
+        let index = timespans.utc_index(dt.and_utc().timestamp());
         TzOffset::new(*self, timespans.get(index))
     }
 }
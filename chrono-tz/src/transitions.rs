@@ -0,0 +1,203 @@
+//! Iterating over the changes of offset in a time zone.
+
+use core::cmp;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::timezone_impl::{TimeSpans, TzOffset};
+use crate::timezones::Tz;
+
+/// A change of offset in a time zone, such as the start or end of daylight
+/// saving time.
+///
+/// Both the offset in effect before the change and the one in effect from it
+/// onwards are given, along with their abbreviations through
+/// [`OffsetName`](crate::OffsetName).
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Transition {
+    utc: DateTime<Utc>,
+    before: TzOffset,
+    after: TzOffset,
+}
+
+impl Transition {
+    /// Panics if the timestamp is out of the range of `DateTime<Utc>`.
+    pub(crate) fn new(tz: Tz, timestamp: i64) -> Transition {
+        let utc = Utc.timestamp_opt(timestamp, 0).unwrap();
+        let timespans = tz.timespans();
+        let before = timespans.get(timespans.utc_index(timestamp - 1));
+        let after = timespans.get(timespans.utc_index(timestamp));
+        Transition {
+            utc,
+            before: TzOffset::new(tz, before),
+            after: TzOffset::new(tz, after),
+        }
+    }
+
+    /// The instant at which the new offset takes effect.
+    pub fn utc(&self) -> DateTime<Utc> {
+        self.utc
+    }
+
+    /// The offset in effect until the transition.
+    pub fn before(&self) -> &TzOffset {
+        &self.before
+    }
+
+    /// The offset in effect from the transition onwards.
+    pub fn after(&self) -> &TzOffset {
+        &self.after
+    }
+}
+
+impl Debug for Transition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {:?} -> {:?}", self.utc, self.before, self.after)
+    }
+}
+
+/// An iterator over the transitions of a time zone within a range of time.
+///
+/// This `struct` is created by [`Tz::transitions`]. It yields transitions in
+/// chronological order, and can be walked backwards as well.
+#[derive(Clone, Debug)]
+pub struct Transitions {
+    tz: Tz,
+    /// The transitions still to be yielded lie strictly between these UTC
+    /// timestamps, which never reach past the range of `DateTime<Utc>`.
+    after: i64,
+    before: i64,
+}
+
+impl Iterator for Transitions {
+    type Item = Transition;
+
+    fn next(&mut self) -> Option<Transition> {
+        let time = self.tz.timespans().next_transition(self.after)?;
+        if time >= self.before {
+            return None;
+        }
+        self.after = time;
+        Some(Transition::new(self.tz, time))
+    }
+}
+
+impl DoubleEndedIterator for Transitions {
+    fn next_back(&mut self) -> Option<Transition> {
+        let time = self.tz.timespans().previous_transition(self.before)?;
+        if time <= self.after {
+            return None;
+        }
+        self.before = time;
+        Some(Transition::new(self.tz, time))
+    }
+}
+
+impl FusedIterator for Transitions {}
+
+impl Tz {
+    /// Returns an iterator over the transitions of this time zone within a range
+    /// of time.
+    ///
+    /// Past the end of the compiled tables, transitions keep following the zone's
+    /// current daylight saving time rule, so an unbounded range has no end for
+    /// zones that still observe it.
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use chrono_tz::{OffsetName, Tz};
+    ///
+    /// let tz = Tz::Europe__London;
+    /// let start = tz.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// let end = tz.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    /// let transitions: Vec<_> = tz.transitions(start..end).collect();
+    /// assert_eq!(transitions.len(), 2);
+    /// assert_eq!(transitions[0].utc().to_string(), "2024-03-31 01:00:00 UTC");
+    /// assert_eq!(transitions[0].before().abbreviation(), Some("GMT"));
+    /// assert_eq!(transitions[0].after().abbreviation(), Some("BST"));
+    /// assert_eq!(transitions[1].utc().to_string(), "2024-10-27 01:00:00 UTC");
+    /// ```
+    pub fn transitions<T: TimeZone, R: RangeBounds<DateTime<T>>>(self, range: R) -> Transitions {
+        // Transitions happen on whole seconds, so a bound with a fractional part
+        // can be rounded towards the outside of the range.
+        let after = match range.start_bound() {
+            Bound::Included(dt) if dt.timestamp_subsec_nanos() == 0 => dt.timestamp() - 1,
+            Bound::Included(dt) | Bound::Excluded(dt) => dt.timestamp(),
+            Bound::Unbounded => i64::MIN,
+        };
+        let before = match range.end_bound() {
+            Bound::Included(dt) => dt.timestamp() + 1,
+            Bound::Excluded(dt) if dt.timestamp_subsec_nanos() == 0 => dt.timestamp(),
+            Bound::Excluded(dt) => dt.timestamp() + 1,
+            Bound::Unbounded => i64::MAX,
+        };
+        Transitions {
+            tz: self,
+            after: cmp::max(after, DateTime::<Utc>::MIN_UTC.timestamp() - 1),
+            before: cmp::min(before, DateTime::<Utc>::MAX_UTC.timestamp() + 1),
+        }
+    }
+
+    /// Returns the first transition of this time zone strictly after an instant,
+    /// or `None` if the offset never changes again.
+    ///
+    /// The offset in effect at `dt` stays valid until the returned transition.
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use chrono_tz::Tz;
+    ///
+    /// let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    /// let next = Tz::America__New_York.next_transition(&now).unwrap();
+    /// assert_eq!(next.utc().to_string(), "2024-11-03 06:00:00 UTC");
+    /// assert_eq!(Tz::UTC.next_transition(&now), None);
+    /// ```
+    pub fn next_transition<T: TimeZone>(self, dt: &DateTime<T>) -> Option<Transition> {
+        self.transitions((Bound::Excluded(dt), Bound::Unbounded))
+            .next()
+    }
+
+    /// Returns the last transition of this time zone strictly before an instant,
+    /// or `None` if the offset has never changed.
+    ///
+    /// The offset in effect at `dt` has been valid since the returned transition.
+    pub fn previous_transition<T: TimeZone>(self, dt: &DateTime<T>) -> Option<Transition> {
+        self.transitions((Bound::Unbounded, Bound::Excluded(dt)))
+            .next_back()
+    }
+}
+
+/// Finding the transitions around a date and time in its own time zone.
+///
+/// ```
+/// use chrono::TimeZone;
+/// use chrono_tz::{OffsetName, Tz, TzTransitions};
+///
+/// let dt = Tz::Europe__London.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+/// let previous = dt.previous_transition().unwrap();
+/// let next = dt.next_transition().unwrap();
+/// assert_eq!(previous.after().abbreviation(), Some("BST"));
+/// assert_eq!(next.utc().to_string(), "2024-10-27 01:00:00 UTC");
+/// ```
+pub trait TzTransitions {
+    /// Returns the first transition strictly after this instant, as
+    /// [`Tz::next_transition`] does.
+    fn next_transition(&self) -> Option<Transition>;
+
+    /// Returns the last transition strictly before this instant, as
+    /// [`Tz::previous_transition`] does.
+    fn previous_transition(&self) -> Option<Transition>;
+}
+
+impl TzTransitions for DateTime<Tz> {
+    fn next_transition(&self) -> Option<Transition> {
+        self.timezone().next_transition(self)
+    }
+
+    fn previous_transition(&self) -> Option<Transition> {
+        self.timezone().previous_transition(self)
+    }
+}
@@ -0,0 +1,279 @@
+//! Compiling zones at runtime from the source files of the tz database, such as
+//! `europe` and `northamerica`, the way the build script compiles [`Tz`](crate::Tz).
+//!
+//! This lets a program pick up a new release of the database without being
+//! rebuilt. The files are parsed with `parse-zoneinfo`, like at build time.
+
+use core::fmt::{self, Display, Formatter};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use parse_zoneinfo::line::{Line, LineParser};
+use parse_zoneinfo::posix::TablePosix;
+use parse_zoneinfo::table::{Saving, Table, TableBuilder};
+use parse_zoneinfo::transitions::{self, TableTransitions};
+
+use crate::dynamic::DynamicTz;
+use crate::posix::PosixTz;
+use crate::timezone_impl::{FixedTimespan, TrailingRule};
+
+/// The source files that the compiled zones are built from, in the order they
+/// are read in.
+const SOURCE_FILES: [&str; 9] = [
+    "africa",
+    "antarctica",
+    "asia",
+    "australasia",
+    "backward",
+    "etcetera",
+    "europe",
+    "northamerica",
+    "southamerica",
+];
+
+/// An error encountered while compiling zones from tz database sources.
+#[derive(Debug)]
+pub enum TzdataError {
+    /// A source file could not be read.
+    Io(io::Error),
+    /// A line could not be parsed, or does not fit with the lines before it, such
+    /// as a zone line referring to rules that have not been defined yet.
+    InvalidLine {
+        /// The line, without its comment.
+        line: String,
+        /// Why the line is invalid.
+        reason: String,
+    },
+    /// The zone with this name cannot be compiled, because one of its lines other
+    /// than the last has no end, or one of its offsets is a day or more.
+    InvalidZone(String),
+    /// The link with this name does not lead to a zone.
+    BrokenLink(String),
+}
+
+impl Display for TzdataError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TzdataError::Io(e) => write!(f, "cannot read tz database source: {}", e),
+            TzdataError::InvalidLine { line, reason } => {
+                write!(f, "invalid line {:?}: {}", line, reason)
+            }
+            TzdataError::InvalidZone(name) => write!(f, "invalid zone {}", name),
+            TzdataError::BrokenLink(name) => write!(f, "link {} does not lead to a zone", name),
+        }
+    }
+}
+
+impl std::error::Error for TzdataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TzdataError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The zones and links of a release of the tz database, compiled at runtime.
+///
+/// Each zone is a [`DynamicTz`], which converts dates exactly like the
+/// [`Tz`](crate::Tz) of the same name would if it had been compiled from the
+/// same sources. Links are zones of their own, under their own names.
+///
+/// ```
+/// use chrono::TimeZone;
+/// use chrono_tz::{DynamicTzSet, OffsetName};
+///
+/// let source = "
+/// Rule   EU   1981 max  -  Mar lastSun 1:00u 1:00 S
+/// Rule   EU   1996 max  -  Oct lastSun 1:00u 0    -
+/// Zone   Europe/Paris 1:00  EU    CE%sT
+/// Link   Europe/Paris Europe/Monaco
+/// ";
+/// let zones = DynamicTzSet::from_sources([source]).unwrap();
+/// let monaco = zones.get("Europe/Monaco").unwrap();
+/// let dt = monaco.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(dt.offset().abbreviation(), Some("CEST"));
+/// assert_eq!(dt.offset().tz_id(), "Europe/Monaco");
+/// ```
+#[derive(Clone, Debug)]
+pub struct DynamicTzSet {
+    zones: BTreeMap<String, DynamicTz>,
+}
+
+impl DynamicTzSet {
+    /// Compiles the zones of the contents of tz database source files.
+    ///
+    /// As with `zic`, rules may be defined after the continuation lines that
+    /// use them, even in a later file, but the first line of a zone must only
+    /// use rules that are already defined.
+    pub fn from_sources<'a, I: IntoIterator<Item = &'a str>>(
+        sources: I,
+    ) -> Result<DynamicTzSet, TzdataError> {
+        let parser = LineParser::default();
+        let mut builder = TableBuilder::new();
+        for source in sources {
+            for line in source.lines() {
+                // The parser does not handle comments.
+                let line = match line.find('#') {
+                    Some(index) => &line[..index],
+                    None => line,
+                };
+                let invalid = |reason: &dyn Display| TzdataError::InvalidLine {
+                    line: line.to_owned(),
+                    reason: reason.to_string(),
+                };
+                let result = match parser.parse_str(line).map_err(|e| invalid(&e))? {
+                    Line::Zone(zone) => builder.add_zone_line(zone),
+                    Line::Continuation(cont) => builder.add_continuation_line(cont),
+                    Line::Rule(rule) => builder.add_rule_line(rule),
+                    Line::Link(link) => builder.add_link_line(link),
+                    Line::Space => Ok(()),
+                };
+                result.map_err(|e| invalid(&e))?;
+            }
+        }
+        DynamicTzSet::from_table(&builder.build())
+    }
+
+    /// Compiles the zones of the source files in a directory, such as an
+    /// extracted `tzdata` release.
+    ///
+    /// The same files are read as at build time: `africa`, `antarctica`,
+    /// `asia`, `australasia`, `backward`, `etcetera`, `europe`,
+    /// `northamerica` and `southamerica`.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<DynamicTzSet, TzdataError> {
+        let sources = SOURCE_FILES
+            .iter()
+            .map(|file| fs::read_to_string(dir.as_ref().join(file)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(TzdataError::Io)?;
+        DynamicTzSet::from_sources(sources.iter().map(String::as_str))
+    }
+
+    fn from_table(table: &Table) -> Result<DynamicTzSet, TzdataError> {
+        let mut zones = BTreeMap::new();
+        for (name, zoneset) in &table.zonesets {
+            // Computing the timespans would panic on a line without an end
+            // before the last, or on rules that are not defined. Continuation
+            // lines may use rules defined further on, so the rules can only be
+            // checked once all lines have been read.
+            let (_, previous) = zoneset.split_last().unwrap();
+            let unknown_rules = zoneset.iter().any(|info| match info.saving {
+                Saving::Multiple(ref rules) => !table.rulesets.contains_key(rules),
+                _ => false,
+            });
+            if previous.iter().any(|info| info.end_time.is_none()) || unknown_rules {
+                return Err(TzdataError::InvalidZone(name.clone()));
+            }
+            let tz = compile(table, name).ok_or_else(|| TzdataError::InvalidZone(name.clone()))?;
+            zones.insert(name.clone(), tz);
+        }
+        let mut links = BTreeMap::new();
+        for name in table.links.keys() {
+            // Zones take precedence over links of the same name.
+            if zones.contains_key(name) {
+                continue;
+            }
+            // Links may lead to other links, and must end at a zone.
+            let mut target = name;
+            for _ in 0..table.links.len() {
+                match table.links.get(target) {
+                    Some(next) if !zones.contains_key(target) => target = next,
+                    _ => break,
+                }
+            }
+            let tz = zones
+                .get(target)
+                .ok_or_else(|| TzdataError::BrokenLink(name.clone()))?
+                .with_name(name);
+            links.insert(name.clone(), tz);
+        }
+        zones.append(&mut links);
+        Ok(DynamicTzSet { zones })
+    }
+
+    /// Returns the zone or link with a name.
+    pub fn get(&self, name: &str) -> Option<&DynamicTz> {
+        self.zones.get(name)
+    }
+
+    /// Returns the names of all zones and links, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.zones.keys().map(String::as_str)
+    }
+
+    /// Returns all zones and links, in the alphabetical order of their names.
+    pub fn iter(&self) -> impl Iterator<Item = &DynamicTz> {
+        self.zones.values()
+    }
+
+    /// The number of zones and links.
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    /// Returns whether there are no zones at all.
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+}
+
+/// Compiles a zone of a table, as the build script does, or returns `None` if
+/// one of its offsets is out of range.
+fn compile(table: &Table, name: &str) -> Option<DynamicTz> {
+    let timespans = table.timespans(name)?;
+    // Numeric abbreviations are left unnamed, as their offsets tell them.
+    let abbreviation = |name: &str| match name {
+        "%z" => None,
+        name => Some(name.to_owned()),
+    };
+    let convert = |timespan: &transitions::FixedTimespan| {
+        let utc_offset = i32::try_from(timespan.utc_offset).ok()?;
+        let dst_offset = i32::try_from(timespan.dst_offset).ok()?;
+        let total = utc_offset.checked_add(dst_offset)?;
+        if total <= -86_400 || total >= 86_400 {
+            return None;
+        }
+        Some(FixedTimespan {
+            utc_offset,
+            dst_offset,
+            name: None,
+        })
+    };
+
+    let first = convert(&timespans.first)?;
+    let mut abbreviations = vec![abbreviation(&timespans.first.name)];
+    let mut rest = Vec::with_capacity(timespans.rest.len());
+    for (time, timespan) in &timespans.rest {
+        rest.push((*time, convert(timespan)?));
+        abbreviations.push(abbreviation(&timespan.name));
+    }
+
+    // The rule after the last transition names its timespans like the last
+    // ones with the same offsets, so that numeric abbreviations stay unnamed.
+    let footer = table
+        .posix_tz(name)
+        .map(|tz| tz.to_string().parse::<PosixTz>())
+        .transpose()
+        .ok()?;
+    let rule = footer.as_ref().and_then(PosixTz::dst_rule);
+    let rule = rule.map(|(rule, std_abbreviation, dst_abbreviation)| {
+        let rule = TrailingRule::new(rule);
+        for (timespan, posix_abbreviation) in
+            [(rule.std, std_abbreviation), (rule.dst, dst_abbreviation)]
+        {
+            let last = timespans.rest.iter().rev().map(|(_, t)| t).find(|t| {
+                t.utc_offset == i64::from(timespan.utc_offset)
+                    && t.dst_offset == i64::from(timespan.dst_offset)
+            });
+            abbreviations.push(match last {
+                Some(last) => abbreviation(&last.name),
+                None => Some(posix_abbreviation.to_owned()),
+            });
+        }
+        rule
+    });
+    Some(DynamicTz::new(name, first, rest, rule, abbreviations))
+}
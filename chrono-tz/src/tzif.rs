@@ -0,0 +1,464 @@
+//! Reading zones from TZif files, as described in [RFC 8536].
+//!
+//! TZif is the binary format produced by `zic` and found under
+//! `/usr/share/zoneinfo` on most Unix systems. Versions 1 to 4 of the format are
+//! supported. Since a file may come from anywhere, every count and index in it is
+//! checked before use, and anything malformed results in a [`TzifError`].
+//!
+//! [RFC 8536]: https://www.rfc-editor.org/rfc/rfc8536
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::dynamic::DynamicTz;
+use crate::posix::PosixTz;
+use crate::timezone_impl::{FixedTimespan, TrailingRule};
+
+/// The maximum number of transitions accepted in a single file.
+///
+/// Files compiled from the tz database have a few hundred at most.
+const MAX_TRANSITIONS: usize = 1 << 16;
+
+/// The maximum number of local time types, as mandated by RFC 8536.
+const MAX_TYPES: usize = 256;
+
+/// The maximum number of bytes of time zone designations accepted in a single file.
+const MAX_DESIGNATION_BYTES: usize = 1 << 12;
+
+/// The maximum length of the TZ string footer accepted in a single file.
+const MAX_FOOTER_BYTES: usize = 1 << 10;
+
+/// An error encountered while reading a TZif file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TzifError {
+    /// The data does not start with the `TZif` magic bytes.
+    InvalidMagic,
+    /// The version byte is not one of the known TZif versions.
+    UnsupportedVersion(u8),
+    /// The data ends before all the records announced in the header.
+    UnexpectedEof,
+    /// The header announces more records than this reader accepts.
+    TooLarge,
+    /// The counts in the header are inconsistent with each other.
+    InvalidHeader,
+    /// The transition or leap second times are not in strictly ascending order.
+    UnsortedTransitions,
+    /// A transition refers to a local time type that does not exist.
+    InvalidTypeIndex,
+    /// A local time type has an invalid DST flag, or a UTC offset outside of what
+    /// chrono can represent.
+    InvalidLocalTimeType,
+    /// A time zone designation is out of bounds, unterminated, or not ASCII.
+    InvalidDesignation,
+    /// The TZ string footer of a version 2+ file is missing or malformed.
+    InvalidFooter,
+}
+
+impl Display for TzifError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TzifError::InvalidMagic => f.write_str("not a TZif file"),
+            TzifError::UnsupportedVersion(v) => write!(f, "unsupported TZif version {:#04x}", v),
+            TzifError::UnexpectedEof => f.write_str("unexpected end of TZif data"),
+            TzifError::TooLarge => f.write_str("TZif file is too large"),
+            TzifError::InvalidHeader => f.write_str("inconsistent TZif header"),
+            TzifError::UnsortedTransitions => f.write_str("TZif transitions are not sorted"),
+            TzifError::InvalidTypeIndex => f.write_str("invalid local time type index"),
+            TzifError::InvalidLocalTimeType => f.write_str("invalid local time type"),
+            TzifError::InvalidDesignation => f.write_str("invalid time zone designation"),
+            TzifError::InvalidFooter => f.write_str("invalid TZif footer"),
+        }
+    }
+}
+
+impl std::error::Error for TzifError {}
+
+impl DynamicTz {
+    /// Reads a zone from the contents of a TZif file.
+    ///
+    /// `name` is the identifier reported through [`OffsetName::tz_id`](crate::OffsetName::tz_id),
+    /// as TZif files do not record the name of their zone.
+    ///
+    /// Files with leap second records, such as those under `right/`, count leap seconds in their
+    /// transition times. chrono does not, so these times are converted back with the records,
+    /// which are otherwise ignored.
+    ///
+    /// ```
+    /// use chrono_tz::{DynamicTz, TzifError};
+    ///
+    /// let result = DynamicTz::from_tzif("Europe/London", b"not a zoneinfo file");
+    /// assert_eq!(result, Err(TzifError::InvalidMagic));
+    /// ```
+    pub fn from_tzif(name: &str, data: &[u8]) -> Result<DynamicTz, TzifError> {
+        let mut reader = Reader { data };
+        let mut header = reader.header()?;
+        let time_size = if header.version == 1 {
+            4
+        } else {
+            // Skip the version 1 data block: it is superseded by the 64-bit one.
+            reader.take(header.block_len(4))?;
+            header = reader.header()?;
+            8
+        };
+        let block = reader.block(&header, time_size)?;
+        let footer = match header.version {
+            1 => None,
+            _ => match reader.footer()? {
+                "" => None,
+                footer => Some(
+                    footer
+                        .parse::<PosixTz>()
+                        .map_err(|_| TzifError::InvalidFooter)?,
+                ),
+            },
+        };
+        Ok(block.into_zone(name, footer))
+    }
+}
+
+#[cfg(feature = "tzif-writer")]
+impl crate::Tz {
+    /// Compiles this zone into a TZif file.
+    ///
+    /// The file is written the way `zic -b slim` writes it: transitions that the TZ string
+    /// footer describes are left out, and the version 1 data block is empty. It can be read
+    /// back with [`DynamicTz::from_tzif`].
+    ///
+    /// To write a zone that was parsed with `parse-zoneinfo` instead, use
+    /// [`parse_zoneinfo::tzif::write`].
+    ///
+    /// ```no_run
+    /// use std::fs;
+    /// use std::path::Path;
+    ///
+    /// use chrono_tz::TZ_VARIANTS;
+    ///
+    /// for tz in TZ_VARIANTS {
+    ///     let path = Path::new("zoneinfo").join(tz.name());
+    ///     fs::create_dir_all(path.parent().unwrap())?;
+    ///     fs::write(path, tz.to_tzif())?;
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn to_tzif(self) -> Vec<u8> {
+        use crate::timezone_impl::TimeSpans;
+        use parse_zoneinfo::transitions;
+
+        fn convert(timespan: &FixedTimespan) -> transitions::FixedTimespan {
+            transitions::FixedTimespan {
+                utc_offset: timespan.utc_offset.into(),
+                dst_offset: timespan.dst_offset.into(),
+                name: timespan.to_string(),
+            }
+        }
+
+        let timespans = self.timespans();
+        let timespans = transitions::FixedTimespanSet {
+            first: convert(&timespans.first),
+            rest: timespans
+                .rest
+                .iter()
+                .map(|(time, timespan)| (time, convert(&timespan)))
+                .collect(),
+        };
+        let footer = match self.posix_tz() {
+            "" => None,
+            footer => Some(
+                footer
+                    .parse::<PosixTz>()
+                    .expect("invalid generated TZ string")
+                    .to_zoneinfo(),
+            ),
+        };
+
+        let mut data = Vec::new();
+        parse_zoneinfo::tzif::write(&mut data, &timespans, footer.as_ref())
+            .expect("compiled zone cannot be written as TZif");
+        data
+    }
+}
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    /// Length of the data block following this header, for transition times of the given size.
+    fn block_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+struct LocalTimeType {
+    utoff: i32,
+    is_dst: bool,
+    designation: Option<String>,
+}
+
+struct Block {
+    transitions: Vec<(i64, usize)>,
+    types: Vec<LocalTimeType>,
+}
+
+impl Block {
+    fn into_zone(self, name: &str, footer: Option<PosixTz>) -> DynamicTz {
+        // TZif files only record the total offset and a DST flag, so the standard part of a
+        // DST offset is taken from the closest standard time before it, then from the type
+        // before the first transition, then from the closest one after it. Both are found for
+        // every transition in one pass each way.
+        let types = self.transitions.iter().map(|&(_, t)| &self.types[t]);
+        let mut standard_offsets = Vec::with_capacity(self.transitions.len());
+        let mut before = Some(&self.types[0]).filter(|t| !t.is_dst).map(|t| t.utoff);
+        for t in types.clone() {
+            standard_offsets.push(before);
+            if !t.is_dst {
+                before = Some(t.utoff);
+            }
+        }
+        let mut after = None;
+        for (t, offset) in types.zip(&mut standard_offsets).rev() {
+            if !t.is_dst {
+                after = Some(t.utoff);
+            }
+            *offset = offset.or(after);
+        }
+        let standard_offset = |position: usize| {
+            standard_offsets[position]
+                .unwrap_or(self.types[self.transitions[position].1].utoff - 3600)
+        };
+        let timespan = |t: &LocalTimeType, position: Option<usize>| match position {
+            Some(position) if t.is_dst => {
+                let utc_offset = standard_offset(position);
+                FixedTimespan {
+                    utc_offset,
+                    dst_offset: t.utoff - utc_offset,
+                    name: None,
+                }
+            }
+            _ => FixedTimespan {
+                utc_offset: t.utoff,
+                dst_offset: 0,
+                name: None,
+            },
+        };
+
+        // The footer takes over after the last transition, if it has a rule to follow.
+        let rule = footer.as_ref().and_then(PosixTz::dst_rule);
+
+        // Local time type 0 applies before the first transition.
+        let first = &self.types[0];
+        let mut abbreviations = vec![first.designation.clone()];
+        let mut rest: Vec<(i64, FixedTimespan)> = Vec::with_capacity(self.transitions.len());
+        let mut previous = (timespan(first, None), 0);
+        for (position, &(time, t)) in self.transitions.iter().enumerate() {
+            let span = timespan(&self.types[t], Some(position));
+            // Transitions that change nothing are dropped, like the compiled zones do, except
+            // for the last one when it marks where the footer's rule starts.
+            let is_last = position + 1 == self.transitions.len();
+            if span == previous.0
+                && self.types[t].designation == self.types[previous.1].designation
+                && !(is_last && rule.is_some())
+            {
+                continue;
+            }
+            rest.push((time, span));
+            abbreviations.push(self.types[t].designation.clone());
+            previous = (span, t);
+        }
+        let first = timespan(first, None);
+
+        let rule = rule.map(|(rule, std_abbreviation, dst_abbreviation)| {
+            abbreviations.push(Some(std_abbreviation.to_owned()));
+            abbreviations.push(Some(dst_abbreviation.to_owned()));
+            TrailingRule::new(rule)
+        });
+        DynamicTz::new(name, first, rest, rule, abbreviations)
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TzifError> {
+        if self.data.len() < len {
+            return Err(TzifError::UnexpectedEof);
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, TzifError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32, TzifError> {
+        let bytes = self.take(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn i64(&mut self) -> Result<i64, TzifError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(i64::from_be_bytes(bytes))
+    }
+
+    fn count(&mut self, max: usize) -> Result<usize, TzifError> {
+        let count = self.i32()? as u32 as usize;
+        if count > max {
+            return Err(TzifError::TooLarge);
+        }
+        Ok(count)
+    }
+
+    fn header(&mut self) -> Result<Header, TzifError> {
+        if self.take(4).map_err(|_| TzifError::InvalidMagic)? != b"TZif" {
+            return Err(TzifError::InvalidMagic);
+        }
+        let version = match self.u8()? {
+            0 => 1,
+            v @ b'2'..=b'4' => v - b'0',
+            v => return Err(TzifError::UnsupportedVersion(v)),
+        };
+        self.take(15)?;
+        let header = Header {
+            version,
+            isutcnt: self.count(MAX_TYPES)?,
+            isstdcnt: self.count(MAX_TYPES)?,
+            leapcnt: self.count(MAX_TRANSITIONS)?,
+            timecnt: self.count(MAX_TRANSITIONS)?,
+            typecnt: self.count(MAX_TYPES)?,
+            charcnt: self.count(MAX_DESIGNATION_BYTES)?,
+        };
+        if header.typecnt == 0
+            || header.charcnt == 0
+            || (header.isutcnt != 0 && header.isutcnt != header.typecnt)
+            || (header.isstdcnt != 0 && header.isstdcnt != header.typecnt)
+        {
+            return Err(TzifError::InvalidHeader);
+        }
+        Ok(header)
+    }
+
+    fn time(&mut self, time_size: usize) -> Result<i64, TzifError> {
+        match time_size {
+            4 => self.i32().map(i64::from),
+            _ => self.i64(),
+        }
+    }
+
+    fn block(&mut self, header: &Header, time_size: usize) -> Result<Block, TzifError> {
+        // Check the length up front, so that nothing is allocated for truncated data.
+        if self.data.len() < header.block_len(time_size) {
+            return Err(TzifError::UnexpectedEof);
+        }
+
+        let mut times = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            times.push(self.time(time_size)?);
+        }
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for time in times {
+            let index = self.u8()? as usize;
+            if index >= header.typecnt {
+                return Err(TzifError::InvalidTypeIndex);
+            }
+            transitions.push((time, index));
+        }
+
+        let mut raw_types = Vec::with_capacity(header.typecnt);
+        for _ in 0..header.typecnt {
+            let utoff = self.i32()?;
+            let is_dst = match self.u8()? {
+                0 => false,
+                1 => true,
+                _ => return Err(TzifError::InvalidLocalTimeType),
+            };
+            let index = self.u8()? as usize;
+            // chrono's `FixedOffset` only accepts offsets strictly within a day.
+            if utoff <= -86_400 || utoff >= 86_400 {
+                return Err(TzifError::InvalidLocalTimeType);
+            }
+            raw_types.push((utoff, is_dst, index));
+        }
+
+        let designations = self.take(header.charcnt)?;
+        let mut types = Vec::with_capacity(header.typecnt);
+        for (utoff, is_dst, index) in raw_types {
+            let designation = designations
+                .get(index..)
+                .and_then(|d| d.iter().position(|&b| b == 0).map(|end| &d[..end]))
+                .filter(|d| d.is_ascii())
+                .ok_or(TzifError::InvalidDesignation)?;
+            types.push(LocalTimeType {
+                utoff,
+                is_dst,
+                designation: match designation {
+                    b"" => None,
+                    d => Some(d.iter().map(|&b| b as char).collect()),
+                },
+            });
+        }
+
+        // Each leap second record gives the total of leap seconds from its time on, which the
+        // transition times from then on include.
+        let mut leap_seconds = Vec::with_capacity(header.leapcnt);
+        for _ in 0..header.leapcnt {
+            let time = self.time(time_size)?;
+            let correction = self.i32()?;
+            if leap_seconds.last().map_or(false, |&(last, _)| last >= time) {
+                return Err(TzifError::UnsortedTransitions);
+            }
+            leap_seconds.push((time, i64::from(correction)));
+        }
+        if !leap_seconds.is_empty() {
+            let mut leap_seconds = leap_seconds.iter().peekable();
+            let mut correction = 0;
+            for (time, _) in &mut transitions {
+                while let Some(&&(_, total)) = leap_seconds.peek().filter(|(at, _)| at <= time) {
+                    correction = total;
+                    leap_seconds.next();
+                }
+                *time -= correction;
+            }
+        }
+        if transitions.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(TzifError::UnsortedTransitions);
+        }
+
+        // The standard/wall and UT/local indicators only matter for POSIX TZ strings without
+        // rules, which TZif files do not rely on anymore.
+        self.take(header.isstdcnt + header.isutcnt)?;
+        Ok(Block { transitions, types })
+    }
+
+    fn footer(&mut self) -> Result<&'a str, TzifError> {
+        if self.u8().map_err(|_| TzifError::InvalidFooter)? != b'\n' {
+            return Err(TzifError::InvalidFooter);
+        }
+        let end = self
+            .data
+            .iter()
+            .take(MAX_FOOTER_BYTES + 1)
+            .position(|&b| b == b'\n')
+            .ok_or(TzifError::InvalidFooter)?;
+        let footer = self.take(end)?;
+        self.take(1)?;
+        core::str::from_utf8(footer)
+            .ok()
+            .filter(|f| f.is_ascii())
+            .ok_or(TzifError::InvalidFooter)
+    }
+}
@@ -0,0 +1,120 @@
+//! Windows time zone IDs, from the `windowsZones.xml` table of CLDR.
+
+use core::cmp::Ordering;
+
+use crate::timezones::{Tz, WINDOWS_ZONES};
+
+/// A Windows time zone, such as `"Pacific Standard Time"`, and the zones that
+/// CLDR maps it to in each territory.
+///
+/// CLDR names zones by their long-standing identifiers, some of which, like
+/// `Asia/Calcutta`, are now links. [`Tz::canonical`] gives the current name.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WindowsZone {
+    pub(crate) id: &'static str,
+    /// The zones by territory, starting with the default under `"001"`.
+    pub(crate) territories: &'static [(&'static str, &'static [Tz])],
+}
+
+impl WindowsZone {
+    /// The Windows ID, such as `"Pacific Standard Time"`.
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    /// The zone to use when the territory is not known, which CLDR lists under
+    /// the `"001"` territory.
+    pub fn default_zone(&self) -> Tz {
+        self.territories[0].1[0]
+    }
+
+    /// Returns the zones of the Windows zone in a territory, given its ISO 3166
+    /// alpha-2 code, or an empty slice if it is not used there.
+    ///
+    /// The first zone is the one to use for the territory. CLDR also lists
+    /// zones under the `"ZZ"` territory, which stands for no particular place,
+    /// and the default zone under `"001"`.
+    ///
+    /// ```
+    /// use chrono_tz::{windows_zone, Tz};
+    ///
+    /// let eastern = windows_zone("Eastern Standard Time").unwrap();
+    /// assert_eq!(eastern.territory_zones("CA")[0], Tz::America__Toronto);
+    /// assert!(eastern.territory_zones("us").contains(&Tz::America__Detroit));
+    /// assert!(eastern.territory_zones("FR").is_empty());
+    /// ```
+    pub fn territory_zones(&self, territory: &str) -> &'static [Tz] {
+        self.territories
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(territory))
+            .map_or(&[], |&(_, zones)| zones)
+    }
+
+    /// The territories that the Windows zone is used in and their zones, in the
+    /// order of the table, starting with `"001"`.
+    pub fn territories(&self) -> &'static [(&'static str, &'static [Tz])] {
+        self.territories
+    }
+}
+
+impl Tz {
+    /// Returns the Windows ID of this zone, or `None` if CLDR does not map it
+    /// to one.
+    ///
+    /// Links that are not listed themselves take the ID of the zone they point
+    /// to.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// assert_eq!(Tz::America__Vancouver.windows_id(), Some("Pacific Standard Time"));
+    /// assert_eq!(Tz::Asia__Kolkata.windows_id(), Some("India Standard Time"));
+    /// assert_eq!(Tz::Antarctica__Troll.windows_id(), None);
+    /// ```
+    pub fn windows_id(self) -> Option<&'static str> {
+        self.windows_id_data()
+    }
+
+    /// Returns the default zone of a Windows ID, ignoring case, as
+    /// [`WindowsZone::default_zone`] does.
+    ///
+    /// ```
+    /// use chrono_tz::Tz;
+    ///
+    /// let tz = Tz::from_windows_id("India Standard Time").unwrap();
+    /// assert_eq!(tz, Tz::Asia__Calcutta);
+    /// assert_eq!(tz.canonical(), Tz::Asia__Kolkata);
+    /// assert_eq!(Tz::from_windows_id("Mars Standard Time"), None);
+    /// ```
+    pub fn from_windows_id(id: &str) -> Option<Tz> {
+        windows_zone(id).map(|zone| zone.default_zone())
+    }
+}
+
+/// Returns the Windows zone with an ID, ignoring case.
+///
+/// ```
+/// use chrono_tz::{windows_zone, Tz};
+///
+/// let zone = windows_zone("w. europe standard time").unwrap();
+/// assert_eq!(zone.id(), "W. Europe Standard Time");
+/// assert_eq!(zone.default_zone(), Tz::Europe__Berlin);
+/// assert_eq!(zone.territory_zones("NL"), [Tz::Europe__Amsterdam]);
+/// ```
+pub fn windows_zone(id: &str) -> Option<&'static WindowsZone> {
+    WINDOWS_ZONES
+        .binary_search_by(|zone| cmp_ignore_case(zone.id, id))
+        .ok()
+        .map(|index| &WINDOWS_ZONES[index])
+}
+
+/// Returns all Windows zones, in the order of their IDs ignoring case.
+pub fn windows_zones() -> &'static [WindowsZone] {
+    WINDOWS_ZONES
+}
+
+fn cmp_ignore_case(a: &str, b: &str) -> Ordering {
+    a.bytes()
+        .map(|b| b.to_ascii_lowercase())
+        .cmp(b.bytes().map(|b| b.to_ascii_lowercase()))
+}
@@ -0,0 +1,76 @@
+//! Tests for looking zones up by abbreviation.
+
+use chrono::{Offset, TimeZone};
+use chrono_tz::{zones_with_abbreviation, Tz};
+
+fn zones(abbreviation: &str, (year, month, day): (i32, u32, u32), region: Option<&str>) -> Vec<Tz> {
+    let instant = Tz::UTC
+        .with_ymd_and_hms(year, month, day, 12, 0, 0)
+        .unwrap();
+    zones_with_abbreviation(abbreviation, &instant, region)
+        .map(|(tz, _)| tz)
+        .collect()
+}
+
+#[test]
+fn active_abbreviation() {
+    // Ireland is on Irish Standard Time in summer only, and Israel in winter
+    // only.
+    let winter = zones("IST", (2024, 1, 15), None);
+    assert!(winter.contains(&Tz::Asia__Kolkata));
+    assert!(winter.contains(&Tz::Asia__Jerusalem));
+    assert!(!winter.contains(&Tz::Europe__Dublin));
+    let summer = zones("IST", (2024, 7, 15), None);
+    assert!(summer.contains(&Tz::Asia__Kolkata));
+    assert!(summer.contains(&Tz::Europe__Dublin));
+    assert!(!summer.contains(&Tz::Asia__Jerusalem));
+
+    // Every zone using the abbreviation is found, links included.
+    let bst = zones("BST", (2024, 7, 15), None);
+    assert!(bst.contains(&Tz::Europe__London));
+    assert!(bst.contains(&Tz::GB));
+    assert!(zones("BST", (2024, 1, 15), None).is_empty());
+
+    assert!(zones("XYZ", (2024, 1, 15), None).is_empty());
+    assert!(zones("cst", (2024, 1, 15), None).is_empty());
+}
+
+#[test]
+fn implied_offsets() {
+    let instant = Tz::UTC.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+    for (tz, offset) in zones_with_abbreviation("CST", &instant, None) {
+        let seconds = offset.fix().local_minus_utc();
+        match tz.canonical() {
+            Tz::America__Chicago => assert_eq!(seconds, -6 * 3600),
+            Tz::America__Havana => assert_eq!(seconds, -5 * 3600),
+            Tz::Asia__Shanghai | Tz::Asia__Taipei => assert_eq!(seconds, 8 * 3600),
+            _ => {}
+        }
+        assert_eq!(offset, tz.offset_from_utc_datetime(&instant.naive_utc()));
+    }
+}
+
+#[test]
+fn region_ranking() {
+    // Zones come in alphabetical order, followed by links.
+    let all = zones("CST", (2024, 1, 15), None);
+    let mut sorted = all.clone();
+    sorted.sort_by_key(|tz| (tz.is_link(), tz.name()));
+    assert_eq!(all, sorted);
+
+    // The zones of the region come first, and the others follow in order.
+    for region in ["CU", "cu"] {
+        let ranked = zones("CST", (2024, 1, 15), Some(region));
+        assert_eq!(ranked[0], Tz::America__Havana);
+        assert_eq!(ranked.len(), all.len());
+        let rest = all
+            .iter()
+            .copied()
+            .filter(|&tz| tz != Tz::America__Havana && tz != Tz::Cuba)
+            .collect::<Vec<_>>();
+        assert_eq!(ranked[2..], rest[..]);
+    }
+
+    // A region without any of the zones changes nothing.
+    assert_eq!(zones("CST", (2024, 1, 15), Some("FR")), all);
+}
@@ -0,0 +1,161 @@
+//! Tests for the Arrow timestamp column kernels.
+
+#![cfg(feature = "arrow")]
+
+use arrow_array::types::ArrowTimestampType;
+use arrow_array::{
+    Array, PrimitiveArray, TimestampMicrosecondArray, TimestampMillisecondArray,
+    TimestampNanosecondArray, TimestampSecondArray,
+};
+use arrow_schema::ArrowError;
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::{
+    timestamps_from_local, timestamps_to_local, timestamps_to_local_dates, Disambiguation,
+    LocalTimeError, TimestampColumnError, Tz,
+};
+
+const ZONES: [Tz; 5] = [
+    Tz::America__New_York,
+    Tz::Europe__London,
+    Tz::Australia__Lord_Howe,
+    Tz::Asia__Kolkata,
+    Tz::UTC,
+];
+
+/// Seconds every few days from 1900 to 2100, with some before the epoch and
+/// around transitions.
+fn seconds() -> Vec<i64> {
+    let start = Utc
+        .with_ymd_and_hms(1900, 1, 1, 0, 0, 0)
+        .unwrap()
+        .timestamp();
+    let end = Utc
+        .with_ymd_and_hms(2100, 1, 1, 0, 0, 0)
+        .unwrap()
+        .timestamp();
+    (start..end).step_by(3 * 86_400 + 1_799).collect()
+}
+
+fn naive(seconds: i64, nanos: u32) -> NaiveDateTime {
+    Utc.timestamp_opt(seconds, nanos).unwrap().naive_utc()
+}
+
+fn check_units<T: ArrowTimestampType>(units: i64) {
+    let values = seconds()
+        .into_iter()
+        .map(|s| s * units + units / 3)
+        .collect::<Vec<_>>();
+    let array = PrimitiveArray::<T>::from_iter_values(values.iter().copied());
+    let nanos = (units / 3 * (1_000_000_000 / units)) as u32;
+    for tz in ZONES {
+        let local = timestamps_to_local(&array, tz).unwrap();
+        let dates = timestamps_to_local_dates(&array, tz).unwrap();
+        for (row, &value) in values.iter().enumerate() {
+            let secs = value.div_euclid(units);
+            let expected = tz.from_utc_datetime(&naive(secs, nanos)).naive_local();
+            assert_eq!(
+                local.value(row),
+                expected.and_utc().timestamp() * units + units / 3
+            );
+            assert_eq!(dates.value_as_date(row), Some(expected.date()));
+        }
+
+        let utc = timestamps_from_local(&array, tz, Disambiguation::Compatible).unwrap();
+        assert_eq!(utc.timezone(), Some(tz.name()));
+        for (row, &value) in values.iter().enumerate() {
+            let secs = value.div_euclid(units);
+            let expected = tz
+                .from_local_datetime_with(&naive(secs, nanos), Disambiguation::Compatible)
+                .unwrap();
+            assert_eq!(utc.value(row), expected.timestamp() * units + units / 3);
+        }
+    }
+}
+
+#[test]
+fn matches_scalar_conversions() {
+    check_units::<arrow_array::types::TimestampSecondType>(1);
+    check_units::<arrow_array::types::TimestampMillisecondType>(1_000);
+    check_units::<arrow_array::types::TimestampMicrosecondType>(1_000_000);
+    check_units::<arrow_array::types::TimestampNanosecondType>(1_000_000_000);
+}
+
+#[test]
+fn nulls_are_kept() {
+    let array = TimestampMillisecondArray::from(vec![None, Some(1_704_067_200_000), None, None]);
+    let tz = Tz::Asia__Tokyo;
+    let local = timestamps_to_local(&array, tz).unwrap();
+    assert_eq!(local.nulls(), array.nulls());
+    assert_eq!(local.value(1), 1_704_067_200_000 + 9 * 3_600_000);
+
+    let utc = timestamps_from_local(&array, tz, Disambiguation::Reject).unwrap();
+    assert_eq!(utc.nulls(), array.nulls());
+    assert_eq!(utc.value(1), 1_704_067_200_000 - 9 * 3_600_000);
+
+    let dates = timestamps_to_local_dates(&array, tz).unwrap();
+    assert_eq!(dates.nulls(), array.nulls());
+    assert_eq!(dates.value_as_date(1), NaiveDate::from_ymd_opt(2024, 1, 1));
+}
+
+#[test]
+fn gaps_and_folds() {
+    let tz = Tz::America__New_York;
+    // 2024-03-10 02:30:00.250 is skipped, and 2024-11-03 01:30:00.250 is repeated.
+    let gap = NaiveDate::from_ymd_opt(2024, 3, 10)
+        .unwrap()
+        .and_hms_milli_opt(2, 30, 0, 250)
+        .unwrap();
+    let fold = NaiveDate::from_ymd_opt(2024, 11, 3)
+        .unwrap()
+        .and_hms_milli_opt(1, 30, 0, 250)
+        .unwrap();
+    let array = TimestampMicrosecondArray::from(vec![
+        gap.and_utc().timestamp_micros(),
+        fold.and_utc().timestamp_micros(),
+    ]);
+    for disambiguation in [
+        Disambiguation::Compatible,
+        Disambiguation::Earlier,
+        Disambiguation::Later,
+    ] {
+        let utc = timestamps_from_local(&array, tz, disambiguation).unwrap();
+        for (row, local) in [gap, fold].iter().enumerate() {
+            let expected = tz.from_local_datetime_with(local, disambiguation).unwrap();
+            assert_eq!(utc.value(row), expected.timestamp_micros());
+        }
+    }
+
+    let error = timestamps_from_local(&array, tz, Disambiguation::Reject).unwrap_err();
+    match error {
+        TimestampColumnError::LocalTime { row: 0, error } => match *error {
+            LocalTimeError::Gap(gap) => {
+                assert_eq!(gap.local().and_utc().timestamp_micros(), array.value(0))
+            }
+            error => panic!("unexpected error: {:?}", error),
+        },
+        error => panic!("unexpected error: {:?}", error),
+    }
+    let error = timestamps_from_local(&array.slice(1, 1), tz, Disambiguation::Reject).unwrap_err();
+    assert!(matches!(
+        &error,
+        TimestampColumnError::LocalTime { row: 0, error } if matches!(**error, LocalTimeError::Fold(_))
+    ));
+    assert!(matches!(
+        ArrowError::from(error),
+        ArrowError::ComputeError(message) if message.starts_with("row 0: local time")
+    ));
+}
+
+#[test]
+fn out_of_range() {
+    let array = TimestampNanosecondArray::from(vec![0, i64::MAX - 1]);
+    assert_eq!(
+        timestamps_to_local(&array, Tz::Asia__Tokyo).unwrap_err(),
+        TimestampColumnError::OutOfRange { row: 1 }
+    );
+    let array = TimestampSecondArray::from(vec![i64::MIN / 2]);
+    assert_eq!(
+        timestamps_to_local_dates(&array, Tz::UTC).unwrap_err(),
+        TimestampColumnError::OutOfRange { row: 0 }
+    );
+}
@@ -0,0 +1,105 @@
+//! Tests for converting many timestamps at once.
+
+use chrono::{LocalResult, Offset, TimeZone, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// Timestamps every few weeks from 1800 to 2200, and around each transition.
+fn timestamps(tz: Tz) -> Vec<i64> {
+    let start = Utc.with_ymd_and_hms(1800, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap();
+    let mut timestamps = (start.timestamp()..end.timestamp())
+        .step_by(17 * 86_400 + 3_607)
+        .collect::<Vec<_>>();
+    for transition in tz.transitions(start..end) {
+        let time = transition.utc().timestamp();
+        timestamps.extend((time - 7_200..time + 7_200).step_by(1_800));
+        timestamps.extend([time - 1, time, time + 1]);
+    }
+    timestamps.sort_unstable();
+    timestamps
+}
+
+fn utc_offsets(tz: Tz, timestamps: &[i64]) -> Vec<i32> {
+    let mut offsets = vec![0; timestamps.len()];
+    tz.offsets_for_utc_timestamps(timestamps, &mut offsets);
+    offsets
+}
+
+fn local_offsets(tz: Tz, timestamps: &[i64]) -> Vec<LocalResult<i32>> {
+    let mut offsets = vec![LocalResult::None; timestamps.len()];
+    tz.offsets_for_local_timestamps(timestamps, &mut offsets);
+    offsets
+}
+
+fn expected_utc_offset(tz: Tz, timestamp: i64) -> i32 {
+    let utc = Utc.timestamp_opt(timestamp, 0).unwrap().naive_utc();
+    tz.offset_from_utc_datetime(&utc).fix().local_minus_utc()
+}
+
+fn expected_local_offset(tz: Tz, timestamp: i64) -> LocalResult<i32> {
+    let local = Utc.timestamp_opt(timestamp, 0).unwrap().naive_utc();
+    tz.offset_from_local_datetime(&local)
+        .map(|offset| offset.fix().local_minus_utc())
+}
+
+#[test]
+fn batches_match_single_conversions() {
+    for &tz in TZ_VARIANTS.iter() {
+        let timestamps = timestamps(tz);
+        let utc = utc_offsets(tz, &timestamps);
+        let local = local_offsets(tz, &timestamps);
+        for (i, &timestamp) in timestamps.iter().enumerate() {
+            assert_eq!(
+                utc[i],
+                expected_utc_offset(tz, timestamp),
+                "{} at {}",
+                tz,
+                timestamp
+            );
+            assert_eq!(
+                local[i],
+                expected_local_offset(tz, timestamp),
+                "{} at {}",
+                tz,
+                timestamp
+            );
+        }
+    }
+}
+
+#[test]
+fn unsorted_batches() {
+    for &tz in &[
+        Tz::America__New_York,
+        Tz::Europe__Dublin,
+        Tz::Australia__Lord_Howe,
+    ] {
+        let sorted = timestamps(tz);
+        let expected_utc = utc_offsets(tz, &sorted);
+        let expected_local = local_offsets(tz, &sorted);
+
+        // Reversed, and shuffled with large and small jumps both ways.
+        let reversed = sorted.iter().rev().copied().collect::<Vec<_>>();
+        let utc = utc_offsets(tz, &reversed);
+        let local = local_offsets(tz, &reversed);
+        assert!(utc.iter().rev().eq(&expected_utc), "{}", tz);
+        assert!(local.iter().rev().eq(&expected_local), "{}", tz);
+
+        let order = (0..sorted.len())
+            .map(|i| (i * 7_919) % sorted.len())
+            .collect::<Vec<_>>();
+        let shuffled = order.iter().map(|&i| sorted[i]).collect::<Vec<_>>();
+        let utc = utc_offsets(tz, &shuffled);
+        let local = local_offsets(tz, &shuffled);
+        for (j, &i) in order.iter().enumerate() {
+            assert_eq!(utc[j], expected_utc[i], "{} at {}", tz, sorted[i]);
+            assert_eq!(local[j], expected_local[i], "{} at {}", tz, sorted[i]);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn mismatched_lengths() {
+    Tz::UTC.offsets_for_utc_timestamps(&[0, 1], &mut [0]);
+}
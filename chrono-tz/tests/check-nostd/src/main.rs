@@ -14,6 +14,8 @@ fn catch_panic(_e: &PanicInfo) -> ! {
 #[entry]
 fn main() -> ! {
     assert!(Pacific == Pacific);
+    #[cfg(feature = "libm")]
+    assert!(chrono_tz::Tz::nearest(37.8, -122.4) == Some(chrono_tz::Tz::America__Los_Angeles));
 
     loop {}
 }
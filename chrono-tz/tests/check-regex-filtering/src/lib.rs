@@ -12,7 +12,7 @@
 #[cfg(test)]
 mod tests {
     use chrono::offset::TimeZone;
-    use chrono_tz::{Europe, Europe::London, Tz, TZ_VARIANTS};
+    use chrono_tz::{windows_zone, windows_zones, Europe, Europe::London, Tz, TZ_VARIANTS};
     use std::str::FromStr;
 
     #[test]
@@ -64,4 +64,22 @@ mod tests {
             assert!(!tz.name().starts_with("US"));
         }
     }
+
+    #[test]
+    fn windows_zones_are_filtered() {
+        let gmt = windows_zone("GMT Standard Time").unwrap();
+        assert_eq!(gmt.default_zone(), London);
+        assert_eq!(gmt.territory_zones("IM"), [Europe::Isle_of_Man]);
+        assert_eq!(London.windows_id(), Some("GMT Standard Time"));
+
+        // Windows IDs whose default zone was filtered out are left out.
+        assert!(windows_zone("Pacific Standard Time").is_none());
+        assert_eq!(Tz::from_windows_id("W. Europe Standard Time"), None);
+        for zone in windows_zones() {
+            assert_eq!(zone.territories()[0].0, "001", "{}", zone.id());
+            for (_, zones) in zone.territories() {
+                assert!(!zones.is_empty(), "{}", zone.id());
+            }
+        }
+    }
 }
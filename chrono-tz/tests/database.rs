@@ -0,0 +1,83 @@
+//! Tests for replacing the global time zone database.
+//!
+//! The database is shared by the whole process, so everything is tested in a
+//! single test.
+
+#![cfg(feature = "std")]
+
+use std::collections::HashMap;
+use std::thread;
+
+use chrono::TimeZone;
+use chrono_tz::{
+    current_database, set_database, CompiledTz, DynamicTz, OffsetName, Tz, TzDatabase, TzProvider,
+    IANA_TZDB_VERSION,
+};
+
+/// A database in which `Europe/London` is Tokyo time.
+fn patched(version: &str) -> TzDatabase {
+    let mut zones = HashMap::new();
+    zones.insert("Europe/London".to_owned(), DynamicTz::from(Tz::Asia__Tokyo));
+    TzDatabase::new(version, zones.or(CompiledTz))
+}
+
+#[test]
+fn replace_database() {
+    let compiled = current_database();
+    assert_eq!(compiled.version(), IANA_TZDB_VERSION);
+    let london = compiled.resolve("Europe/London").unwrap();
+    assert_eq!(london.name(), "Europe/London");
+
+    let previous = set_database(patched("patched"));
+    assert_eq!(previous.version(), IANA_TZDB_VERSION);
+    let current = current_database();
+    assert_eq!(current.version(), "patched");
+    assert_eq!(
+        current.resolve("Europe/London").unwrap().name(),
+        "Asia/Tokyo"
+    );
+    assert_eq!(
+        current.resolve("Europe/Paris").unwrap().name(),
+        "Europe/Paris"
+    );
+
+    // Snapshots and zones looked up before are not affected.
+    assert_eq!(
+        compiled.resolve("Europe/London").unwrap().name(),
+        "Europe/London"
+    );
+    let dt = london.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    assert_eq!(dt.offset().abbreviation(), Some("BST"));
+
+    // Readers always see a database whose zones match its version.
+    let readers = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                for _ in 0..1000 {
+                    let database = current_database();
+                    let london = database.resolve("Europe/London").unwrap();
+                    match database.version() {
+                        "patched" => assert_eq!(london.name(), "Asia/Tokyo"),
+                        _ => assert_eq!(london.name(), "Europe/London"),
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for i in 0..100 {
+        match i % 2 {
+            0 => set_database(TzDatabase::compiled()),
+            _ => set_database(patched("patched")),
+        };
+    }
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    set_database(TzDatabase::compiled());
+    assert_eq!(current_database().version(), IANA_TZDB_VERSION);
+    assert_eq!(
+        format!("{:?}", current_database()),
+        format!("TzDatabase {{ version: {:?}, .. }}", IANA_TZDB_VERSION)
+    );
+}
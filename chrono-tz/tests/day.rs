@@ -0,0 +1,135 @@
+//! Tests for the span of time covered by local days.
+
+use chrono::{Duration, NaiveDate, NaiveTime, Offset, TimeZone, Utc};
+
+use chrono_tz::{OffsetName, Tz, TZ_VARIANTS};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn ordinary_day() {
+    let day = Tz::Europe__London.local_day(date(2024, 7, 1)).unwrap();
+    assert_eq!(day.date(), date(2024, 7, 1));
+    assert_eq!(day.length(), Duration::hours(24));
+    assert_eq!(day.first_time(), NaiveTime::MIN);
+    assert_eq!(day.start().to_string(), "2024-07-01 00:00:00 BST");
+    assert_eq!(day.end().to_string(), "2024-07-02 00:00:00 BST");
+    assert_eq!(
+        day.utc_range(),
+        Utc.with_ymd_and_hms(2024, 6, 30, 23, 0, 0).unwrap()
+            ..Utc.with_ymd_and_hms(2024, 7, 1, 23, 0, 0).unwrap()
+    );
+    assert_eq!(day.transitions().count(), 0);
+}
+
+#[test]
+fn daylight_saving_time() {
+    let tz = Tz::Europe__London;
+    let spring = tz.local_day(date(2024, 3, 31)).unwrap();
+    assert_eq!(spring.length(), Duration::hours(23));
+    let transitions: Vec<_> = spring.transitions().collect();
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(transitions[0].after().abbreviation(), Some("BST"));
+
+    let autumn = tz.local_day(date(2024, 10, 27)).unwrap();
+    assert_eq!(autumn.length(), Duration::hours(25));
+    assert_eq!(autumn.transitions().count(), 1);
+
+    let tz = Tz::Australia__Lord_Howe;
+    let autumn = tz.local_day(date(2024, 4, 7)).unwrap();
+    assert_eq!(autumn.length(), Duration::minutes(24 * 60 + 30));
+    let spring = tz.local_day(date(2024, 10, 6)).unwrap();
+    assert_eq!(spring.length(), Duration::minutes(23 * 60 + 30));
+}
+
+#[test]
+fn midnight_skipped() {
+    // Chile puts its clocks forward from midnight to one in the morning.
+    let tz = Tz::America__Santiago;
+    let day = tz.local_day(date(2024, 9, 8)).unwrap();
+    assert_eq!(day.first_time(), NaiveTime::from_hms_opt(1, 0, 0).unwrap());
+    assert_eq!(day.start().to_rfc3339(), "2024-09-08T01:00:00-03:00");
+    assert_eq!(day.length(), Duration::hours(23));
+    assert_eq!(day.transitions().count(), 1);
+
+    // The previous day ends when the clocks change.
+    let day = tz.local_day(date(2024, 9, 7)).unwrap();
+    assert_eq!(day.length(), Duration::hours(24));
+    assert_eq!(day.transitions().count(), 0);
+
+    // And they are put back from midnight to eleven in the evening.
+    let day = tz.local_day(date(2024, 4, 6)).unwrap();
+    assert_eq!(day.length(), Duration::hours(25));
+    assert_eq!(day.end().to_rfc3339(), "2024-04-07T00:00:00-04:00");
+}
+
+#[test]
+fn skipped_day() {
+    let tz = Tz::Pacific__Apia;
+    assert_eq!(tz.local_day(date(2011, 12, 30)), None);
+
+    let before = tz.local_day(date(2011, 12, 29)).unwrap();
+    assert_eq!(before.length(), Duration::hours(24));
+    let after = tz.local_day(date(2011, 12, 31)).unwrap();
+    assert_eq!(after.length(), Duration::hours(24));
+    assert_eq!(before.end(), after.start());
+    // The transition is at the first instant of December 31.
+    assert_eq!(before.transitions().count(), 0);
+    assert_eq!(after.transitions().count(), 1);
+}
+
+#[test]
+fn after_2100() {
+    let day = Tz::America__New_York.local_day(date(2200, 3, 9)).unwrap();
+    assert_eq!(day.length(), Duration::hours(23));
+    assert_eq!(day.transitions().count(), 1);
+}
+
+#[test]
+fn range_limits() {
+    // Midnight on the first date is before the first instant east of UTC, and the
+    // last date has no following day.
+    assert_eq!(Tz::Asia__Tokyo.local_day(NaiveDate::MIN), None);
+    assert_eq!(Tz::America__Los_Angeles.local_day(NaiveDate::MAX), None);
+    assert_eq!(Tz::Asia__Tokyo.local_day(NaiveDate::MAX), None);
+
+    let day = Tz::America__Los_Angeles.local_day(NaiveDate::MIN).unwrap();
+    assert_eq!(day.first_time(), NaiveTime::MIN);
+    assert_eq!(day.length(), Duration::hours(24));
+    let day = Tz::Asia__Tokyo
+        .local_day(NaiveDate::MIN.succ_opt().unwrap())
+        .unwrap();
+    assert_eq!(day.length(), Duration::hours(24));
+    let day = Tz::America__Los_Angeles
+        .local_day(NaiveDate::MAX.pred_opt().unwrap())
+        .unwrap();
+    assert_eq!(day.length(), Duration::hours(24));
+}
+
+/// Local days follow each other without gaps or overlaps.
+#[test]
+fn consecutive_days() {
+    for &tz in TZ_VARIANTS.iter() {
+        let mut previous = tz.local_day(date(2010, 1, 1)).unwrap();
+        let mut current = previous.date();
+        while current < date(2014, 1, 1) {
+            current = current.succ_opt().unwrap();
+            let day = match tz.local_day(current) {
+                Some(day) => day,
+                None => continue,
+            };
+            assert_eq!(day.start(), previous.end(), "{} on {}", tz, current);
+            assert_eq!(day.start().date_naive(), current, "{} on {}", tz, current);
+            assert!(day.end().date_naive() > current, "{} on {}", tz, current);
+            if day.length() != Duration::hours(24) {
+                let offset_changes = day
+                    .transitions()
+                    .any(|t| t.before().fix() != t.after().fix());
+                assert!(offset_changes, "{} on {}", tz, current);
+            }
+            previous = day;
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Tests for resolving skipped and repeated local times.
+
+use chrono::{Duration, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+
+use chrono_tz::{Disambiguation, LocalResolution, LocalTimeError, OffsetName, Tz, TZ_VARIANTS};
+
+fn local(year: i32, month: u32, day: u32, hour: u32, min: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(hour, min, 0)
+        .unwrap()
+}
+
+fn resolve(tz: Tz, local: NaiveDateTime, disambiguation: Disambiguation) -> String {
+    match tz.from_local_datetime_with(&local, disambiguation) {
+        Ok(dt) => dt.to_rfc3339(),
+        Err(LocalTimeError::Gap(_)) => "gap".to_owned(),
+        Err(LocalTimeError::Fold(_)) => "fold".to_owned(),
+        Err(LocalTimeError::OutOfRange(_)) => "out of range".to_owned(),
+    }
+}
+
+#[test]
+fn spring_forward() {
+    let tz = Tz::America__New_York;
+    let gap = match tz.resolve_local(&local(2024, 3, 10, 2, 30)) {
+        LocalResolution::Gap(gap) => gap,
+        result => panic!("expected a gap, got {:?}", result),
+    };
+    assert_eq!(gap.local(), local(2024, 3, 10, 2, 30));
+    assert_eq!(gap.start(), local(2024, 3, 10, 2, 0));
+    assert_eq!(gap.end(), local(2024, 3, 10, 3, 0));
+    assert_eq!(gap.length(), Duration::hours(1));
+    assert_eq!(
+        gap.transition().utc(),
+        Utc.with_ymd_and_hms(2024, 3, 10, 7, 0, 0).unwrap()
+    );
+    assert_eq!(gap.earlier().to_string(), "2024-03-10 01:30:00 EST");
+    assert_eq!(gap.later().to_string(), "2024-03-10 03:30:00 EDT");
+
+    use Disambiguation::*;
+    let time = local(2024, 3, 10, 2, 30);
+    assert_eq!(resolve(tz, time, Compatible), "2024-03-10T03:30:00-04:00");
+    assert_eq!(resolve(tz, time, Earlier), "2024-03-10T01:30:00-05:00");
+    assert_eq!(resolve(tz, time, Later), "2024-03-10T03:30:00-04:00");
+    assert_eq!(resolve(tz, time, Reject), "gap");
+
+    // The edges of the gap.
+    let start = local(2024, 3, 10, 2, 0);
+    assert_eq!(resolve(tz, start, Reject), "gap");
+    let end = local(2024, 3, 10, 3, 0);
+    assert_eq!(resolve(tz, end, Reject), "2024-03-10T03:00:00-04:00");
+}
+
+#[test]
+fn fall_back() {
+    let tz = Tz::America__New_York;
+    let fold = match tz.resolve_local(&local(2024, 11, 3, 1, 30)) {
+        LocalResolution::Fold(fold) => fold,
+        result => panic!("expected a fold, got {:?}", result),
+    };
+    assert_eq!(fold.start(), local(2024, 11, 3, 1, 0));
+    assert_eq!(fold.end(), local(2024, 11, 3, 2, 0));
+    assert_eq!(fold.width(), Duration::hours(1));
+    assert_eq!(fold.earlier().offset().abbreviation(), Some("EDT"));
+    assert_eq!(fold.later().offset().abbreviation(), Some("EST"));
+    assert_eq!(fold.later() - fold.earlier(), Duration::hours(1));
+
+    use Disambiguation::*;
+    let time = local(2024, 11, 3, 1, 30);
+    assert_eq!(resolve(tz, time, Compatible), "2024-11-03T01:30:00-04:00");
+    assert_eq!(resolve(tz, time, Earlier), "2024-11-03T01:30:00-04:00");
+    assert_eq!(resolve(tz, time, Later), "2024-11-03T01:30:00-05:00");
+    assert_eq!(resolve(tz, time, Reject), "fold");
+}
+
+#[test]
+fn unambiguous() {
+    let tz = Tz::Europe__London;
+    let time = local(2024, 7, 1, 12, 0);
+    match tz.resolve_local(&time) {
+        LocalResolution::Single(dt) => assert_eq!(dt.to_string(), "2024-07-01 12:00:00 BST"),
+        result => panic!("expected a single time, got {:?}", result),
+    }
+    for disambiguation in [
+        Disambiguation::Compatible,
+        Disambiguation::Earlier,
+        Disambiguation::Later,
+        Disambiguation::Reject,
+    ] {
+        assert_eq!(
+            resolve(tz, time, disambiguation),
+            "2024-07-01T12:00:00+01:00"
+        );
+    }
+    assert_eq!(Disambiguation::default(), Disambiguation::Compatible);
+}
+
+#[test]
+fn skipped_day() {
+    // Samoa skipped the whole of December 30, 2011.
+    let tz = Tz::Pacific__Apia;
+    let gap = match tz.resolve_local(&local(2011, 12, 30, 12, 0)) {
+        LocalResolution::Gap(gap) => gap,
+        result => panic!("expected a gap, got {:?}", result),
+    };
+    assert_eq!(gap.start(), local(2011, 12, 30, 0, 0));
+    assert_eq!(gap.end(), local(2011, 12, 31, 0, 0));
+    assert_eq!(gap.length(), Duration::days(1));
+    assert_eq!(gap.later().to_rfc3339(), "2011-12-31T12:00:00+14:00");
+    assert_eq!(gap.earlier().to_rfc3339(), "2011-12-29T12:00:00-10:00");
+}
+
+#[test]
+fn half_hour_fold() {
+    let tz = Tz::Australia__Lord_Howe;
+    match tz.resolve_local(&local(2024, 4, 7, 1, 45)) {
+        LocalResolution::Fold(fold) => {
+            assert_eq!(fold.width(), Duration::minutes(30));
+            assert_eq!(fold.start(), local(2024, 4, 7, 1, 30));
+            assert_eq!(fold.end(), local(2024, 4, 7, 2, 0));
+        }
+        result => panic!("expected a fold, got {:?}", result),
+    }
+}
+
+#[test]
+fn after_2100() {
+    let tz = Tz::Europe__Paris;
+    match tz.resolve_local(&local(2150, 3, 29, 2, 15)) {
+        LocalResolution::Gap(gap) => {
+            assert_eq!(gap.start(), local(2150, 3, 29, 2, 0));
+            assert_eq!(gap.later().to_string(), "2150-03-29 03:15:00 CEST");
+        }
+        result => panic!("expected a gap, got {:?}", result),
+    }
+}
+
+#[test]
+fn out_of_range() {
+    // Applying the offset takes these local times past the ends of the range.
+    for (tz, local) in [
+        (Tz::Asia__Tokyo, NaiveDateTime::MIN),
+        (Tz::America__Los_Angeles, NaiveDateTime::MAX),
+    ] {
+        assert_eq!(tz.resolve_local(&local), LocalResolution::OutOfRange(local));
+        for disambiguation in [
+            Disambiguation::Compatible,
+            Disambiguation::Earlier,
+            Disambiguation::Later,
+            Disambiguation::Reject,
+        ] {
+            assert_eq!(
+                tz.from_local_datetime_with(&local, disambiguation),
+                Err(LocalTimeError::OutOfRange(local))
+            );
+        }
+    }
+
+    // The other way around, the instants are in range.
+    let tz = Tz::America__Los_Angeles;
+    let first = tz.resolve_local(&NaiveDateTime::MIN);
+    assert!(matches!(first, LocalResolution::Single(_)), "{:?}", first);
+    let tz = Tz::Asia__Tokyo;
+    let last = tz.resolve_local(&NaiveDateTime::MAX);
+    assert!(matches!(last, LocalResolution::Single(_)), "{:?}", last);
+    assert_eq!(
+        resolve(tz, NaiveDateTime::MAX, Disambiguation::Reject),
+        "+262142-12-31T23:59:59.999999999+09:00"
+    );
+}
+
+#[test]
+fn error_display() {
+    let error = Tz::America__New_York
+        .from_local_datetime_with(&local(2024, 3, 10, 2, 30), Disambiguation::Reject)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "local time 2024-03-10 02:30:00 is skipped by the transition at 2024-03-10 07:00:00 UTC"
+    );
+    let error = Tz::Asia__Tokyo
+        .from_local_datetime_with(&NaiveDateTime::MIN, Disambiguation::Compatible)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "local time -262143-01-01 00:00:00 is out of range"
+    );
+}
+
+/// Every gap and fold reported by `from_local_datetime` is found again, and
+/// contains the local time that was looked up.
+#[test]
+fn all_gaps_and_folds() {
+    let range = Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap()
+        ..Utc.with_ymd_and_hms(2110, 1, 1, 0, 0, 0).unwrap();
+    for &tz in TZ_VARIANTS.iter() {
+        for transition in tz.transitions(range.clone()) {
+            // Local times just inside of whatever the transition skips or repeats.
+            let utc = transition.utc().naive_utc();
+            let before =
+                utc + Duration::seconds(transition.before().fix().local_minus_utc().into());
+            let after = utc + Duration::seconds(transition.after().fix().local_minus_utc().into());
+            for local in [
+                before - Duration::seconds(1),
+                before,
+                after - Duration::seconds(1),
+                after,
+            ] {
+                match (tz.from_local_datetime(&local), tz.resolve_local(&local)) {
+                    (LocalResult::Single(a), LocalResolution::Single(b)) => assert_eq!(a, b),
+                    (LocalResult::None, LocalResolution::Gap(gap)) => {
+                        assert!(
+                            gap.start() <= local && local < gap.end(),
+                            "{} {}",
+                            tz,
+                            local
+                        );
+                        assert!(gap.earlier() < gap.later(), "{} {}", tz, local);
+                    }
+                    (LocalResult::Ambiguous(a, b), LocalResolution::Fold(fold)) => {
+                        assert!(
+                            fold.start() <= local && local < fold.end(),
+                            "{} {}",
+                            tz,
+                            local
+                        );
+                        assert_eq!((a, b), (fold.earlier(), fold.later()), "{} {}", tz, local);
+                    }
+                    (expected, actual) => {
+                        panic!("{} at {}: {:?} vs {:?}", tz, local, expected, actual)
+                    }
+                }
+            }
+        }
+    }
+}
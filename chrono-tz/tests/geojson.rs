@@ -0,0 +1,122 @@
+//! Tests for finding zones from boundary polygons.
+
+#![cfg(feature = "geojson")]
+
+use chrono_tz::{BoundaryError, BoundaryIndex, Tz};
+
+/// Two zones split along longitude 10, one of which has a hole covered by a
+/// third, and a feature for a zone that does not exist.
+const BOUNDARIES: &str = r#"{
+    "type": "FeatureCollection",
+    "features": [
+        {
+            "type": "Feature",
+            "properties": { "tzid": "Europe/Berlin" },
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [
+                    [[0, 40], [10, 40], [10, 50], [0, 50], [0, 40]],
+                    [[4, 44], [6, 44], [6, 46], [4, 46], [4, 44]]
+                ]
+            }
+        },
+        {
+            "type": "Feature",
+            "properties": { "tzid": "Europe/Vienna" },
+            "geometry": {
+                "type": "MultiPolygon",
+                "coordinates": [
+                    [[[10, 40], [20, 40], [20, 50], [10, 50], [10, 40]]],
+                    [[[30.5, 40.5], [30.6, 40.5], [30.6, 40.6, 100], [30.5, 40.5]]]
+                ]
+            }
+        },
+        {
+            "type": "Feature",
+            "properties": { "tzid": "Europe/Zurich" },
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [[[4, 44], [6, 44], [6, 46], [4, 46], [4, 44]]]
+            }
+        },
+        {
+            "type": "Feature",
+            "properties": { "tzid": "Mars/Olympus_Mons" },
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [[[-10, -10], [10, -10], [10, 10], [-10, -10]]]
+            }
+        }
+    ]
+}"#;
+
+#[test]
+fn lookup() {
+    let index = BoundaryIndex::from_slice(BOUNDARIES.as_bytes()).unwrap();
+    assert_eq!(index.lookup(45.0, 2.0), Some(Tz::Europe__Berlin));
+    assert_eq!(index.lookup(45.0, 15.0), Some(Tz::Europe__Vienna));
+    assert_eq!(index.lookup(45.0, 9.99), Some(Tz::Europe__Berlin));
+    assert_eq!(index.lookup(45.0, 10.01), Some(Tz::Europe__Vienna));
+    // Holes are left to the polygons that fill them.
+    assert_eq!(index.lookup(45.0, 5.0), Some(Tz::Europe__Zurich));
+    // Parts of a multipolygon, with positions that have an altitude.
+    assert_eq!(index.lookup(40.52, 30.55), Some(Tz::Europe__Vienna));
+    assert_eq!(index.lookup(40.58, 30.52), None);
+
+    assert_eq!(index.lookup(0.0, 0.0), None);
+    assert_eq!(index.lookup(90.0, 180.0), None);
+    assert_eq!(index.lookup(-90.0, -180.0), None);
+    assert_eq!(index.lookup(f64::NAN, 0.0), None);
+    assert_eq!(index.lookup(45.0, f64::INFINITY), None);
+}
+
+#[test]
+fn member_order() {
+    // The type of a collection or geometry may come after its contents.
+    let boundaries = r#"{"features": [
+        {"geometry": {"coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]], "type": "Polygon"},
+         "id": 7, "properties": {"name": "ignored", "tzid": "Europe/Berlin"}, "type": "Feature"}
+    ], "name": "ignored", "type": "FeatureCollection"}"#;
+    let index = BoundaryIndex::from_slice(boundaries.as_bytes()).unwrap();
+    assert_eq!(index.lookup(0.25, 0.75), Some(Tz::Europe__Berlin));
+}
+
+#[test]
+fn skipped() {
+    let index = BoundaryIndex::from_reader(BOUNDARIES.as_bytes()).unwrap();
+    assert_eq!(index.skipped().len(), 1);
+    assert_eq!(index.skipped()[0].index(), 3);
+    assert_eq!(index.skipped()[0].tzid(), "Mars/Olympus_Mons");
+}
+
+#[test]
+fn errors() {
+    assert!(matches!(
+        BoundaryIndex::from_slice(b"{"),
+        Err(BoundaryError::Json(_))
+    ));
+    assert!(matches!(
+        BoundaryIndex::from_slice(br#"{"type": "Feature"}"#),
+        Err(BoundaryError::NotAFeatureCollection)
+    ));
+
+    let missing_tzid = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": []}}
+    ]}"#;
+    let error = BoundaryIndex::from_slice(missing_tzid.as_bytes())
+        .err()
+        .unwrap();
+    assert!(matches!(error, BoundaryError::InvalidFeature(0)));
+    assert_eq!(error.to_string(), "invalid feature at index 0");
+
+    let point = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"tzid": "UTC"},
+         "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 1], [1]]]}},
+        {"type": "Feature", "properties": {"tzid": "UTC"},
+         "geometry": {"type": "Point", "coordinates": [0, 0]}}
+    ]}"#;
+    assert!(matches!(
+        BoundaryIndex::from_slice(point.as_bytes()),
+        Err(BoundaryError::InvalidFeature(0))
+    ));
+}
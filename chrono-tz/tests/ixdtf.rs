@@ -0,0 +1,258 @@
+//! Tests for formatting and parsing dates and times with their zone as in RFC 9557.
+
+use chrono::{DateTime, Duration, NaiveDate, Offset, TimeZone, Utc};
+use chrono_tz::{
+    format_ixdtf, parse_ixdtf, parse_ixdtf_with, Disambiguation, IxdtfError, LocalTimeError,
+    OffsetConflict, Tz, TZ_VARIANTS,
+};
+
+fn ixdtf(dt: &DateTime<Tz>) -> String {
+    format_ixdtf(dt).to_string()
+}
+
+#[test]
+fn round_trips() {
+    let start = Utc.with_ymd_and_hms(1850, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+    for tz in TZ_VARIANTS {
+        let mut instants = (start.timestamp()..end.timestamp())
+            .step_by(97 * 86_400 + 12_345)
+            .collect::<Vec<_>>();
+        for transition in tz.transitions(start..end) {
+            let time = transition.utc().timestamp();
+            instants.extend([time - 1, time, time + 1, time + 1_800]);
+        }
+        for timestamp in instants {
+            let dt = Utc.timestamp_opt(timestamp, 0).unwrap().with_timezone(&tz);
+            let s = ixdtf(&dt);
+            let parsed = parse_ixdtf(&s).unwrap();
+            if dt.offset().fix().local_minus_utc() % 60 == 0 {
+                assert_eq!(parsed, dt, "{}", s);
+            } else {
+                // Offsets with seconds are rounded, so only the local time is
+                // certain to be kept.
+                assert_eq!(parsed.naive_local(), dt.naive_local(), "{}", s);
+            }
+        }
+    }
+}
+
+#[test]
+fn formatting() {
+    let tz = Tz::Europe__London;
+    let dt = tz.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    assert_eq!(ixdtf(&dt), "2024-07-01T12:00:00+01:00[Europe/London]");
+    let dt = tz.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    assert_eq!(ixdtf(&dt), "2024-01-01T12:00:00+00:00[Europe/London]");
+
+    let dt = dt + Duration::milliseconds(250);
+    assert_eq!(ixdtf(&dt), "2024-01-01T12:00:00.25+00:00[Europe/London]");
+    let dt = dt + Duration::nanoseconds(1);
+    assert_eq!(
+        ixdtf(&dt),
+        "2024-01-01T12:00:00.250000001+00:00[Europe/London]"
+    );
+
+    // Local mean time, 4:56:02 behind UTC, is rounded to the minute.
+    let dt = Tz::America__New_York
+        .with_ymd_and_hms(1880, 1, 1, 0, 0, 0)
+        .unwrap();
+    assert_eq!(ixdtf(&dt), "1880-01-01T00:00:00-04:56[America/New_York]");
+
+    let dt = Tz::UTC.with_ymd_and_hms(-1, 12, 31, 0, 0, 0).unwrap();
+    assert_eq!(ixdtf(&dt), "-000001-12-31T00:00:00+00:00[UTC]");
+    let dt = Tz::UTC.with_ymd_and_hms(10000, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(ixdtf(&dt), "+010000-01-01T00:00:00+00:00[UTC]");
+    assert_eq!(parse_ixdtf(&ixdtf(&dt)), Ok(dt));
+}
+
+#[test]
+fn rfc_9557_examples() {
+    let tz = Tz::America__Los_Angeles;
+    let expected = tz.with_ymd_and_hms(2022, 7, 8, 0, 14, 7).unwrap();
+    assert_eq!(
+        parse_ixdtf("2022-07-08T00:14:07-07:00[America/Los_Angeles]"),
+        Ok(expected)
+    );
+    assert_eq!(
+        parse_ixdtf("2022-07-08T00:14:07-07:00[!America/Los_Angeles][u-ca=iso8601]"),
+        Ok(expected)
+    );
+    // An offset of `Z` gives only the instant, which the zone turns into a local time.
+    assert_eq!(
+        parse_ixdtf("2022-07-08T07:14:07Z[America/Los_Angeles]"),
+        Ok(expected)
+    );
+    assert_eq!(
+        parse_ixdtf("2022-07-08T07:14:07-00:00[America/Los_Angeles]"),
+        Ok(expected)
+    );
+    assert_eq!(
+        parse_ixdtf("2022-07-08T00:14:07.5-07:00[America/Los_Angeles]"),
+        Ok(expected + Duration::milliseconds(500))
+    );
+    assert_eq!(
+        parse_ixdtf("2022-07-08t00:14:07-07:00[America/Los_Angeles][foo=bar-baz][_x=y]"),
+        Ok(expected)
+    );
+}
+
+#[test]
+fn repeated_times() {
+    let tz = Tz::America__New_York;
+    let earlier = parse_ixdtf("2024-11-03T01:30:00-04:00[America/New_York]").unwrap();
+    let later = parse_ixdtf("2024-11-03T01:30:00-05:00[America/New_York]").unwrap();
+    assert_eq!(later - earlier, Duration::hours(1));
+    assert_eq!(
+        ixdtf(&earlier),
+        "2024-11-03T01:30:00-04:00[America/New_York]"
+    );
+    assert_eq!(ixdtf(&later), "2024-11-03T01:30:00-05:00[America/New_York]");
+
+    // The offset is ignored, and the repeated time resolved as asked.
+    let s = "2024-11-03T01:30:00-05:00[America/New_York]";
+    assert_eq!(
+        parse_ixdtf_with(s, OffsetConflict::Ignore, Disambiguation::Earlier),
+        Ok(earlier)
+    );
+    assert!(matches!(
+        parse_ixdtf_with(s, OffsetConflict::Ignore, Disambiguation::Reject),
+        Err(IxdtfError::LocalTime(LocalTimeError::Fold(_)))
+    ));
+    assert_eq!(
+        tz.from_local_datetime_with(&later.naive_local(), Disambiguation::Later),
+        Ok(later)
+    );
+}
+
+#[test]
+fn offset_conflicts() {
+    // 02:30 is skipped in New York on 2024-03-10, and -04:00 is wrong in January.
+    let gap = "2024-03-10T02:30:00-05:00[America/New_York]";
+    let winter = "2024-01-10T09:00:00-04:00[America/New_York]";
+    let tz = Tz::America__New_York;
+    for s in [gap, winter] {
+        assert_eq!(parse_ixdtf(s), Err(IxdtfError::OffsetMismatch));
+        assert_eq!(
+            parse_ixdtf_with(s, OffsetConflict::Reject, Disambiguation::Compatible),
+            Err(IxdtfError::OffsetMismatch)
+        );
+    }
+
+    let at = |h, m| {
+        let utc = NaiveDate::from_ymd_opt(2024, 1, 10)
+            .unwrap()
+            .and_hms_opt(h, m, 0)
+            .unwrap();
+        tz.from_utc_datetime(&utc)
+    };
+    let parse = |s, conflict| parse_ixdtf_with(s, conflict, Disambiguation::Compatible);
+    assert_eq!(parse(winter, OffsetConflict::Use), Ok(at(13, 0)));
+    assert_eq!(parse(winter, OffsetConflict::Prefer), Ok(at(14, 0)));
+    assert_eq!(parse(winter, OffsetConflict::Ignore), Ok(at(14, 0)));
+    assert_eq!(
+        ixdtf(&parse(gap, OffsetConflict::Use).unwrap()),
+        "2024-03-10T03:30:00-04:00[America/New_York]"
+    );
+    assert_eq!(
+        ixdtf(&parse(gap, OffsetConflict::Prefer).unwrap()),
+        "2024-03-10T03:30:00-04:00[America/New_York]"
+    );
+    assert!(matches!(
+        parse_ixdtf_with(gap, OffsetConflict::Prefer, Disambiguation::Reject),
+        Err(IxdtfError::LocalTime(LocalTimeError::Gap(_)))
+    ));
+
+    // A critical zone must agree with the offset whatever the policy.
+    let critical = "2024-01-10T09:00:00-04:00[!America/New_York]";
+    for conflict in [
+        OffsetConflict::Use,
+        OffsetConflict::Prefer,
+        OffsetConflict::Ignore,
+        OffsetConflict::Reject,
+    ] {
+        assert_eq!(parse(critical, conflict), Err(IxdtfError::OffsetMismatch));
+    }
+}
+
+#[test]
+fn errors() {
+    let cases = [
+        ("2024-01-10T09:00:00-05:00", IxdtfError::MissingZone),
+        (
+            "2024-01-10T09:00:00-05:00[u-ca=iso8601]",
+            IxdtfError::MissingZone,
+        ),
+        (
+            "2024-01-10T09:00:00[America/New_York]",
+            IxdtfError::InvalidDateTime,
+        ),
+        (
+            "2024-01-10 09:00:00-05:00[America/New_York]",
+            IxdtfError::InvalidDateTime,
+        ),
+        (
+            "2024-02-30T09:00:00-05:00[America/New_York]",
+            IxdtfError::InvalidDateTime,
+        ),
+        (
+            "2024-01-10T24:00:00-05:00[America/New_York]",
+            IxdtfError::InvalidDateTime,
+        ),
+        (
+            "2024-01-10T09:00:00.-05:00[America/New_York]",
+            IxdtfError::InvalidDateTime,
+        ),
+        (
+            "2024-01-10T09:00:00.1234567891-05:00[America/New_York]",
+            IxdtfError::InvalidDateTime,
+        ),
+        (
+            "2024-01-10T09:00:00-24:00[America/New_York]",
+            IxdtfError::InvalidDateTime,
+        ),
+        ("-000000-01-10T09:00:00Z[UTC]", IxdtfError::InvalidDateTime),
+        (
+            "2024-01-10T09:00:00-05:00[America/Nowhere]",
+            IxdtfError::UnknownZone,
+        ),
+        ("2024-01-10T09:00:00-05:00[-05:00]", IxdtfError::UnknownZone),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York",
+            IxdtfError::InvalidAnnotation,
+        ),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York]x",
+            IxdtfError::InvalidAnnotation,
+        ),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York][UTC]",
+            IxdtfError::InvalidAnnotation,
+        ),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York][Foo=bar]",
+            IxdtfError::InvalidAnnotation,
+        ),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York][foo=]",
+            IxdtfError::InvalidAnnotation,
+        ),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York][foo=barbazqux]",
+            IxdtfError::InvalidAnnotation,
+        ),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York][!foo=bar]",
+            IxdtfError::UnsupportedAnnotation,
+        ),
+        (
+            "2024-01-10T09:00:00-05:00[America/New_York][!u-ca=hebrew]",
+            IxdtfError::UnsupportedAnnotation,
+        ),
+    ];
+    for (s, error) in cases {
+        assert_eq!(parse_ixdtf(s), Err(error), "{}", s);
+    }
+    // An elective calendar that is not understood is skipped.
+    assert!(parse_ixdtf("2024-01-10T09:00:00-05:00[America/New_York][u-ca=hebrew]").is_ok());
+}
@@ -0,0 +1,166 @@
+//! Tests for detecting the local time zone, against fake root directories.
+
+#![cfg(feature = "std")]
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono_tz::{local_tz_from, LocalTzError, Tz};
+
+/// A temporary directory standing in for the root of a file system.
+struct FakeRoot(PathBuf);
+
+impl FakeRoot {
+    fn new(name: &str) -> FakeRoot {
+        let path =
+            std::env::temp_dir().join(format!("chrono-tz-local-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(path.join("etc")).unwrap();
+        FakeRoot(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+
+    fn write(&self, path: &str, contents: &str) {
+        let path = self.0.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[cfg(unix)]
+    fn symlink(&self, path: &str, target: &str) {
+        let path = self.0.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(target, path).unwrap();
+    }
+
+    fn local_tz(&self, tz_var: Option<&str>) -> Result<Tz, LocalTzError> {
+        local_tz_from(self.path(), tz_var.map(OsStr::new))
+    }
+}
+
+impl Drop for FakeRoot {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn tz_variable() {
+    let root = FakeRoot::new("tz-variable");
+    assert_eq!(root.local_tz(Some("Europe/London")), Ok(Tz::Europe__London));
+    assert_eq!(
+        root.local_tz(Some(":America/New_York")),
+        Ok(Tz::America__New_York)
+    );
+    assert_eq!(root.local_tz(Some("EST5EDT")), Ok(Tz::EST5EDT));
+    assert_eq!(root.local_tz(Some("")), Ok(Tz::UTC));
+    assert_eq!(root.local_tz(Some(":")), Ok(Tz::UTC));
+    assert_eq!(
+        root.local_tz(Some("/usr/share/zoneinfo/Asia/Tokyo")),
+        Ok(Tz::Asia__Tokyo)
+    );
+    assert_eq!(
+        root.local_tz(Some(":/usr/share/zoneinfo/posix/Asia/Tokyo")),
+        Ok(Tz::Asia__Tokyo)
+    );
+    assert_eq!(
+        root.local_tz(Some("CET-1CEST,M3.5.0,M10.5.0/3")),
+        Err(LocalTzError::UnknownZone(
+            "CET-1CEST,M3.5.0,M10.5.0/3".to_owned()
+        ))
+    );
+    assert_eq!(
+        root.local_tz(Some("/etc/nowhere")),
+        Err(LocalTzError::UnknownZone("/etc/nowhere".to_owned()))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn tz_variable_overrides_localtime() {
+    let root = FakeRoot::new("tz-overrides");
+    root.symlink("etc/localtime", "/usr/share/zoneinfo/Europe/Berlin");
+    assert_eq!(root.local_tz(Some("Asia/Kolkata")), Ok(Tz::Asia__Kolkata));
+    assert_eq!(root.local_tz(None), Ok(Tz::Europe__Berlin));
+}
+
+#[cfg(unix)]
+#[test]
+fn tz_variable_with_link() {
+    let root = FakeRoot::new("tz-link");
+    root.symlink("etc/mytz", "/usr/share/zoneinfo/Europe/Paris");
+    assert_eq!(root.local_tz(Some("/etc/mytz")), Ok(Tz::Europe__Paris));
+}
+
+#[cfg(unix)]
+#[test]
+fn localtime_links() {
+    let root = FakeRoot::new("localtime-absolute");
+    root.symlink("etc/localtime", "/usr/share/zoneinfo/America/Sao_Paulo");
+    assert_eq!(root.local_tz(None), Ok(Tz::America__Sao_Paulo));
+
+    let root = FakeRoot::new("localtime-relative");
+    root.symlink("etc/localtime", "../usr/share/zoneinfo/Europe/Berlin");
+    assert_eq!(root.local_tz(None), Ok(Tz::Europe__Berlin));
+
+    // macOS keeps its database elsewhere.
+    let root = FakeRoot::new("localtime-macos");
+    root.symlink(
+        "etc/localtime",
+        "/var/db/timezone/zoneinfo/America/Argentina/Buenos_Aires",
+    );
+    assert_eq!(
+        root.local_tz(None),
+        Ok(Tz::America__Argentina__Buenos_Aires)
+    );
+
+    let root = FakeRoot::new("localtime-chain");
+    root.symlink("etc/localtime", "/etc/alternatives/localtime");
+    root.symlink(
+        "etc/alternatives/localtime",
+        "../../usr/share/zoneinfo/Asia/Seoul",
+    );
+    assert_eq!(root.local_tz(None), Ok(Tz::Asia__Seoul));
+}
+
+#[cfg(unix)]
+#[test]
+fn localtime_link_to_unknown_zone() {
+    let root = FakeRoot::new("localtime-unknown");
+    root.symlink("etc/localtime", "/usr/share/zoneinfo/Mars/Olympus_Mons");
+    assert_eq!(
+        root.local_tz(None),
+        Err(LocalTzError::UnknownZone("Mars/Olympus_Mons".to_owned()))
+    );
+}
+
+#[test]
+fn timezone_file() {
+    let root = FakeRoot::new("timezone-file");
+    // A copied zoneinfo file does not say which zone it is.
+    root.write("etc/localtime", "TZif");
+    root.write("etc/timezone", "Europe/Madrid\n");
+    assert_eq!(root.local_tz(None), Ok(Tz::Europe__Madrid));
+
+    root.write("etc/timezone", "  Africa/Lagos  \n# comment\n");
+    assert_eq!(root.local_tz(None), Ok(Tz::Africa__Lagos));
+
+    root.write("etc/timezone", "Mars/Olympus_Mons\n");
+    assert_eq!(
+        root.local_tz(None),
+        Err(LocalTzError::UnknownZone("Mars/Olympus_Mons".to_owned()))
+    );
+
+    root.write("etc/timezone", "\n");
+    assert_eq!(root.local_tz(None), Err(LocalTzError::NotConfigured));
+}
+
+#[test]
+fn not_configured() {
+    let root = FakeRoot::new("not-configured");
+    assert_eq!(root.local_tz(None), Err(LocalTzError::NotConfigured));
+}
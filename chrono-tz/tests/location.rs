@@ -0,0 +1,87 @@
+//! Tests for the country and location metadata of zones.
+
+use chrono_tz::{country_name, zones_for_country, Tz, TZ_VARIANTS};
+
+#[test]
+fn zone_locations() {
+    let zurich = Tz::Europe__Zurich.location().unwrap();
+    assert_eq!(zurich.countries(), ["CH", "DE", "LI"]);
+    assert!((zurich.latitude() - (47.0 + 23.0 / 60.0)).abs() < 1e-9);
+    assert!((zurich.longitude() - (8.0 + 32.0 / 60.0)).abs() < 1e-9);
+    assert_eq!(zurich.comment(), Some("Büsingen"));
+
+    // Coordinates can be given to the second, and be south and west.
+    let lima = Tz::America__Lima.location().unwrap();
+    assert!((lima.latitude() + (12.0 + 3.0 / 60.0)).abs() < 1e-9);
+    let st_johns = Tz::America__St_Johns.location().unwrap();
+    assert!((st_johns.longitude() + (52.0 + 43.0 / 60.0)).abs() < 1e-9);
+    let boise = Tz::America__Boise.location().unwrap();
+    assert!((boise.latitude() - (43.0 + 36.0 / 60.0 + 49.0 / 3600.0)).abs() < 1e-9);
+    assert_eq!(boise.comment(), Some("Mountain - ID (south), OR (east)"));
+}
+
+#[test]
+fn links() {
+    // Links listed in `zone.tab` have their own location.
+    let vaduz = Tz::Europe__Vaduz.location().unwrap();
+    assert_eq!(vaduz.countries(), ["LI"]);
+    assert_ne!(vaduz, Tz::Europe__Zurich.location().unwrap());
+
+    // Others take that of their zone.
+    assert_eq!(Tz::US__Mountain.location(), Tz::America__Denver.location());
+    assert_eq!(Tz::Etc__UTC.location(), None);
+    assert_eq!(Tz::UTC.location(), None);
+}
+
+#[test]
+fn countries() {
+    let us = zones_for_country("US");
+    assert_eq!(us[0], Tz::America__New_York);
+    assert!(us.contains(&Tz::Pacific__Honolulu));
+    assert!(!us.contains(&Tz::US__Pacific));
+    assert_eq!(zones_for_country("us"), us);
+    assert_eq!(zones_for_country("LI"), [Tz::Europe__Vaduz]);
+    assert!(zones_for_country("").is_empty());
+    assert!(zones_for_country("USA").is_empty());
+
+    assert_eq!(country_name("GB"), Some("Britain (UK)"));
+    assert_eq!(country_name("gb"), Some("Britain (UK)"));
+    assert_eq!(country_name("XX"), None);
+}
+
+#[test]
+fn consistent_tables() {
+    for tz in TZ_VARIANTS {
+        let location = match tz.location() {
+            Some(location) => location,
+            None => continue,
+        };
+        assert!(location.latitude().abs() <= 90.0, "{}", tz);
+        assert!(location.longitude().abs() <= 180.0, "{}", tz);
+        for &code in location.countries() {
+            assert!(country_name(code).is_some(), "{} in {}", tz, code);
+        }
+    }
+    for tz in TZ_VARIANTS {
+        let location = match tz.location() {
+            Some(location) => location,
+            None => continue,
+        };
+        if !tz.is_link() {
+            let listed = location
+                .countries()
+                .iter()
+                .any(|country| zones_for_country(country).contains(&tz));
+            assert!(listed, "{}", tz);
+        }
+        let country = location.countries()[0];
+        for zone in zones_for_country(country) {
+            assert!(
+                zone.location().unwrap().countries().contains(&country),
+                "{} in {}",
+                zone,
+                country
+            );
+        }
+    }
+}
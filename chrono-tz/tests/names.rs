@@ -0,0 +1,191 @@
+//! Tests for the localized names of offsets.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::{name_locales, Tz};
+
+fn noon(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+}
+
+#[test]
+fn long_names() {
+    let new_york = Tz::America__New_York;
+    let winter = noon(2024, 1, 15);
+    let summer = noon(2024, 7, 15);
+    assert_eq!(
+        new_york.long_name(&winter, "en"),
+        Some("Eastern Standard Time")
+    );
+    assert_eq!(
+        new_york.long_name(&summer, "en"),
+        Some("Eastern Daylight Time")
+    );
+    assert_eq!(new_york.generic_name(&summer, "en"), Some("Eastern Time"));
+    assert_eq!(
+        new_york.long_name(&summer, "de"),
+        Some("Nordamerikanische Ostküsten-Sommerzeit")
+    );
+
+    let paris = Tz::Europe__Paris;
+    assert_eq!(
+        paris.long_name(&summer, "fr"),
+        Some("heure d’été d’Europe centrale")
+    );
+    assert_eq!(
+        paris.generic_name(&summer, "fr"),
+        Some("heure d’Europe centrale")
+    );
+
+    // Zones that are links, or that CLDR lists under an older name.
+    let kolkata = Tz::Asia__Kolkata;
+    assert_eq!(
+        kolkata.long_name(&summer, "en"),
+        Some("India Standard Time")
+    );
+    assert_eq!(kolkata.metazone(&summer), Some("India"));
+    assert_eq!(
+        Tz::US__Pacific.long_name(&winter, "en"),
+        Some("Pacific Standard Time")
+    );
+
+    // Any time zone can give the instant.
+    let local = new_york.from_utc_datetime(&summer.naive_utc());
+    assert_eq!(
+        new_york.long_name(&local, "en"),
+        Some("Eastern Daylight Time")
+    );
+}
+
+/// Zones outside of the sample that was vendored before the complete CLDR
+/// files.
+#[test]
+#[ignore = "needs the complete CLDR files, which bin/update-cldr.sh vendors"]
+fn complete_data() {
+    for (tz, name) in [
+        (Tz::America__Bogota, "Colombia Standard Time"),
+        (Tz::America__Lima, "Peru Standard Time"),
+        (Tz::Asia__Dhaka, "Bangladesh Standard Time"),
+        (Tz::Asia__Manila, "Philippine Standard Time"),
+        (Tz::Asia__Kathmandu, "Nepal Time"),
+        (Tz::Asia__Yekaterinburg, "Yekaterinburg Standard Time"),
+    ] {
+        assert_eq!(tz.long_name(&noon(2024, 1, 15), "en"), Some(name), "{}", tz);
+    }
+}
+
+#[test]
+fn zone_names() {
+    let winter = noon(2024, 1, 15);
+    let summer = noon(2024, 7, 15);
+    let london = Tz::Europe__London;
+    assert_eq!(london.long_name(&winter, "en"), Some("Greenwich Mean Time"));
+    assert_eq!(london.long_name(&summer, "en"), Some("British Summer Time"));
+
+    // Ireland is on negative daylight saving time in winter in the tz database.
+    let dublin = Tz::Europe__Dublin;
+    assert_eq!(dublin.long_name(&winter, "en"), Some("Greenwich Mean Time"));
+    assert_eq!(dublin.long_name(&summer, "en"), Some("Irish Standard Time"));
+
+    let utc = Tz::UTC;
+    assert_eq!(
+        utc.long_name(&winter, "en"),
+        Some("Coordinated Universal Time")
+    );
+    assert_eq!(
+        utc.long_name(&winter, "fr"),
+        Some("temps universel coordonné")
+    );
+    assert_eq!(
+        utc.generic_name(&winter, "en"),
+        Some("Coordinated Universal Time")
+    );
+    assert_eq!(utc.metazone(&winter), None);
+}
+
+#[test]
+fn metazone_history() {
+    // Lisbon was on Central European Time from 1992 to 1996.
+    let lisbon = Tz::Europe__Lisbon;
+    assert_eq!(lisbon.metazone(&noon(1994, 1, 15)), Some("Europe_Central"));
+    assert_eq!(
+        lisbon.long_name(&noon(1994, 1, 15), "en"),
+        Some("Central European Standard Time")
+    );
+    assert_eq!(lisbon.metazone(&noon(2024, 7, 15)), Some("Europe_Western"));
+    assert_eq!(
+        lisbon.long_name(&noon(2024, 7, 15), "en"),
+        Some("Western European Summer Time")
+    );
+
+    // Knox, Indiana was on Eastern Standard Time all year from 1991 to 2006.
+    let knox = Tz::America__Indiana__Knox;
+    assert_eq!(
+        knox.long_name(&noon(2000, 1, 15), "en"),
+        Some("Eastern Standard Time")
+    );
+    assert_eq!(
+        knox.long_name(&noon(2010, 1, 15), "en"),
+        Some("Central Standard Time")
+    );
+
+    // Turkey has had no metazone since 2016.
+    let istanbul = Tz::Europe__Istanbul;
+    assert_eq!(
+        istanbul.long_name(&noon(2015, 1, 15), "en"),
+        Some("Eastern European Standard Time")
+    );
+    assert_eq!(istanbul.metazone(&noon(2017, 1, 15)), None);
+    assert_eq!(istanbul.long_name(&noon(2017, 1, 15), "en"), None);
+
+    // Offsets of transitions are named for either side of them.
+    let transition = knox.next_transition(&noon(2006, 1, 1)).unwrap();
+    assert_eq!(
+        knox.long_name(&(transition.utc() - Duration::seconds(1)), "en"),
+        Some("Eastern Standard Time")
+    );
+    assert_eq!(
+        knox.long_name(&transition.utc(), "en"),
+        Some("Central Daylight Time")
+    );
+}
+
+#[test]
+fn locale_fallback() {
+    let los_angeles = Tz::America__Los_Angeles;
+    let summer = noon(2024, 7, 15);
+    assert_eq!(
+        los_angeles.long_name(&summer, "fr_CA"),
+        Some("heure avancée du Pacifique")
+    );
+    assert_eq!(
+        los_angeles.long_name(&summer, "FR-ca"),
+        Some("heure avancée du Pacifique")
+    );
+    assert_eq!(
+        los_angeles.long_name(&summer, "fr-FR"),
+        Some("heure d’été du Pacifique nord-américain")
+    );
+    assert_eq!(
+        los_angeles.long_name(&summer, "fr"),
+        Some("heure d’été du Pacifique nord-américain")
+    );
+
+    // `fr_CA` only has its own daylight saving time name for the Rockies.
+    let denver = Tz::America__Denver;
+    assert_eq!(
+        denver.long_name(&noon(2024, 1, 15), "fr-CA"),
+        Some("heure normale des Rocheuses")
+    );
+    assert_eq!(
+        denver.long_name(&summer, "fr-CA"),
+        Some("heure avancée des Rocheuses")
+    );
+
+    assert_eq!(los_angeles.long_name(&summer, "xx"), None);
+    assert_eq!(los_angeles.long_name(&summer, ""), None);
+
+    let locales = name_locales().collect::<Vec<_>>();
+    for locale in ["de", "en", "fr", "fr_CA"] {
+        assert!(locales.contains(&locale), "{}", locale);
+    }
+}
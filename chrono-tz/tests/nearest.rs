@@ -0,0 +1,95 @@
+//! Tests for finding the zones closest to a point.
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// The great-circle distance in kilometres, computed with the standard library.
+fn haversine(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (a.0.to_radians(), a.1.to_radians());
+    let (lat2, lon2) = (b.0.to_radians(), b.1.to_radians());
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * 6371.0088 * h.sqrt().asin()
+}
+
+#[test]
+fn cities() {
+    assert_eq!(Tz::nearest(48.86, 2.35), Some(Tz::Europe__Paris));
+    assert_eq!(Tz::nearest(-33.87, 151.21), Some(Tz::Australia__Sydney));
+    assert_eq!(Tz::nearest(35.68, 139.69), Some(Tz::Asia__Tokyo));
+    assert_eq!(
+        Tz::nearest(-34.6, -58.38),
+        Some(Tz::America__Argentina__Buenos_Aires)
+    );
+    // Across the antimeridian from Fiji.
+    assert_eq!(Tz::nearest(-18.0, -179.9), Some(Tz::Pacific__Fiji));
+    // Links with a location of their own are candidates.
+    assert_eq!(Tz::nearest(47.14, 9.52), Some(Tz::Europe__Vaduz));
+}
+
+#[test]
+fn out_of_range() {
+    assert_eq!(Tz::nearest(f64::NAN, 0.0), None);
+    assert_eq!(Tz::nearest(0.0, f64::INFINITY), None);
+    assert_eq!(Tz::nearest(90.5, 0.0), None);
+    assert_eq!(Tz::nearest(-91.0, 0.0), None);
+    assert_eq!(Tz::nearest(0.0, 180.5), None);
+    assert_eq!(Tz::nearest(0.0, -360.0), None);
+    let mut nearest = [(Tz::UTC, 0.0); 3];
+    assert!(Tz::nearest_k(100.0, 0.0, &mut nearest).is_empty());
+
+    // The limits themselves are allowed.
+    assert!(Tz::nearest(90.0, 180.0).is_some());
+    assert!(Tz::nearest(-90.0, -180.0).is_some());
+    assert_eq!(Tz::nearest(-18.0, 180.0), Tz::nearest(-18.0, -180.0));
+}
+
+#[test]
+fn sorted_and_distinct() {
+    let mut nearest = [(Tz::UTC, 0.0); 20];
+    let nearest = Tz::nearest_k(52.0, 5.0, &mut nearest);
+    assert_eq!(nearest.len(), 20);
+    assert_eq!(nearest[0].0, Tz::Europe__Amsterdam);
+    for pair in nearest.windows(2) {
+        assert!(pair[0].1 <= pair[1].1);
+        assert_ne!(pair[0].0, pair[1].0);
+    }
+    // Links without a location of their own are left out.
+    for &(tz, _) in nearest.iter() {
+        assert!(!tz.is_link() || tz.canonical().location() != tz.location());
+    }
+
+    assert!(Tz::nearest_k(52.0, 5.0, &mut []).is_empty());
+}
+
+#[test]
+fn distances() {
+    let points = [
+        (0.0, 0.0),
+        (89.9, 10.0),
+        (-89.9, -170.0),
+        (51.5, -0.12),
+        (-45.0, 179.99),
+        (10.0, -179.99),
+        (37.0, 127.0),
+    ];
+    let mut all = [(Tz::UTC, 0.0); 600];
+    for &point in &points {
+        let nearest = Tz::nearest_k(point.0, point.1, &mut all);
+        assert!(nearest.len() > 300);
+        for &(tz, distance) in nearest.iter() {
+            let location = tz.location().unwrap();
+            let expected = haversine(point, (location.latitude(), location.longitude()));
+            assert!(
+                (distance - expected).abs() < 1e-6,
+                "{} from {:?}: {} vs {}",
+                tz,
+                point,
+                distance,
+                expected
+            );
+        }
+    }
+    assert!(TZ_VARIANTS.len() < all.len());
+}
@@ -0,0 +1,74 @@
+//! Tests for finding zones from observed offsets.
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+use chrono_tz::{zones_with_offsets, Tz, TZ_VARIANTS};
+
+fn sample(utc: (i32, u32, u32), offset_hours: f64) -> DateTime<FixedOffset> {
+    let offset = FixedOffset::east_opt((offset_hours * 3600.0) as i32).unwrap();
+    let (year, month, day) = utc;
+    offset.from_utc_datetime(
+        &chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap(),
+    )
+}
+
+#[test]
+fn january_and_july() {
+    // Eastern Time, as opposed to zones on Eastern Standard Time all year.
+    let samples = [sample((2024, 1, 15), -5.0), sample((2024, 7, 15), -4.0)];
+    let zones = zones_with_offsets(&samples).collect::<Vec<_>>();
+    assert!(zones.contains(&Tz::America__New_York));
+    assert!(zones.contains(&Tz::America__Toronto));
+    assert!(zones.contains(&Tz::US__Eastern));
+    assert!(!zones.contains(&Tz::America__Panama));
+    assert!(!zones.contains(&Tz::America__Chicago));
+
+    let samples = [sample((2024, 1, 15), -5.0), sample((2024, 7, 15), -5.0)];
+    let zones = zones_with_offsets(&samples).collect::<Vec<_>>();
+    assert!(zones.contains(&Tz::America__Panama));
+    assert!(zones.contains(&Tz::Etc__GMTPlus5));
+    assert!(!zones.contains(&Tz::America__New_York));
+
+    // Half-hour offsets and southern summers are told apart as well.
+    let samples = [sample((2024, 1, 15), 10.5), sample((2024, 7, 15), 9.5)];
+    assert_eq!(
+        zones_with_offsets(&samples).next(),
+        Some(Tz::Australia__Adelaide)
+    );
+}
+
+#[test]
+fn past_samples() {
+    // Indiana zones have agreed with New York since 2006, but not before.
+    let recent = [sample((2024, 1, 15), -5.0), sample((2024, 7, 15), -4.0)];
+    assert!(zones_with_offsets(&recent).any(|tz| tz == Tz::America__Indiana__Indianapolis));
+    let samples = [
+        sample((2024, 1, 15), -5.0),
+        sample((2024, 7, 15), -4.0),
+        sample((2005, 7, 15), -5.0),
+    ];
+    let zones = zones_with_offsets(&samples).collect::<Vec<_>>();
+    assert!(zones.contains(&Tz::America__Indiana__Indianapolis));
+    assert!(!zones.contains(&Tz::America__New_York));
+}
+
+#[test]
+fn ranking() {
+    let samples = [sample((2024, 1, 15), 1.0), sample((2024, 7, 15), 2.0)];
+    let zones = zones_with_offsets(&samples).collect::<Vec<_>>();
+    let ranks = zones
+        .iter()
+        .map(|tz| (tz.is_link(), tz.location().is_none()))
+        .collect::<Vec<_>>();
+    let mut sorted = ranks.clone();
+    sorted.sort();
+    assert_eq!(ranks, sorted);
+    assert!(zones[0].location().is_some());
+    assert!(zones.last().unwrap().is_link());
+
+    assert_eq!(zones_with_offsets(&[]).count(), TZ_VARIANTS.len());
+    let nowhere = [sample((2024, 1, 15), 1.0 / 60.0)];
+    assert_eq!(zones_with_offsets(&nowhere).next(), None);
+}
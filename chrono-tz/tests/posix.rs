@@ -0,0 +1,239 @@
+//! Tests for time zones described by POSIX TZ strings.
+//!
+//! Where a zone's current rules can be written as a TZ string, the result is
+//! compared with the compiled zone over the years those rules apply.
+
+use chrono::{Duration, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+
+use chrono_tz::{OffsetComponents, OffsetName, PosixTz, PosixTzError, Tz};
+
+fn assert_same_utc_offsets(posix: &str, compiled: Tz, from: i32, to: i32) {
+    let tz: PosixTz = posix.parse().unwrap();
+    let mut dt = Utc.with_ymd_and_hms(from, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(to, 1, 1, 0, 0, 0).unwrap();
+    while dt < end {
+        let expected = dt.with_timezone(&compiled);
+        let actual = dt.with_timezone(&tz);
+        assert_eq!(actual.offset().fix(), expected.offset().fix(), "at {}", dt);
+        assert_eq!(
+            actual.offset().to_string(),
+            expected.offset().to_string(),
+            "at {}",
+            dt
+        );
+        assert_eq!(
+            actual.offset().base_utc_offset(),
+            expected.offset().base_utc_offset(),
+            "at {}",
+            dt
+        );
+        assert_eq!(
+            actual.offset().dst_offset(),
+            expected.offset().dst_offset(),
+            "at {}",
+            dt
+        );
+        dt += Duration::minutes(30);
+    }
+}
+
+fn assert_same_local_offsets(posix: &str, compiled: Tz, year: i32) {
+    let tz: PosixTz = posix.parse().unwrap();
+    let mut local = NaiveDate::from_ymd_opt(year, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let end = NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    while local < end {
+        let expected = compiled
+            .from_local_datetime(&local)
+            .map(|dt| dt.fixed_offset());
+        let actual = tz.from_local_datetime(&local).map(|dt| dt.fixed_offset());
+        assert_eq!(actual, expected, "at {}", local);
+        local += Duration::minutes(15);
+    }
+}
+
+#[test]
+fn new_york() {
+    let posix = "EST5EDT,M3.2.0,M11.1.0";
+    assert_same_utc_offsets(posix, Tz::America__New_York, 2007, 2037);
+    assert_same_local_offsets(posix, Tz::America__New_York, 2024);
+}
+
+#[test]
+fn london() {
+    let posix = "GMT0BST,M3.5.0/1,M10.5.0";
+    assert_same_utc_offsets(posix, Tz::Europe__London, 1996, 2037);
+    assert_same_local_offsets(posix, Tz::Europe__London, 2024);
+}
+
+#[test]
+fn southern_hemisphere() {
+    let posix = "AEST-10AEDT,M10.1.0,M4.1.0/3";
+    assert_same_utc_offsets(posix, Tz::Australia__Sydney, 2008, 2037);
+    assert_same_local_offsets(posix, Tz::Australia__Sydney, 2024);
+}
+
+#[test]
+fn half_hour_dst() {
+    let posix = "<+1030>-10:30<+11>-11,M10.1.0,M4.1.0";
+    assert_same_utc_offsets(posix, Tz::Australia__Lord_Howe, 2008, 2037);
+    assert_same_local_offsets(posix, Tz::Australia__Lord_Howe, 2024);
+}
+
+#[test]
+fn negative_transition_time() {
+    // Nuuk switches at 01:00 UTC, which is -1:00 in local standard time.
+    let posix = "<-02>2<-01>,M3.5.0/-1,M10.5.0/0";
+    assert_same_utc_offsets(posix, Tz::America__Nuuk, 2024, 2037);
+    assert_same_local_offsets(posix, Tz::America__Nuuk, 2025);
+}
+
+#[test]
+fn fixed_offset() {
+    let tz: PosixTz = "<+0530>-5:30".parse().unwrap();
+    let dt = tz.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+    assert_eq!(dt.to_rfc3339(), "2024-06-01T12:00:00+05:30");
+    assert_eq!(dt.offset().abbreviation(), Some("+0530"));
+    assert_eq!(dt.offset().tz_id(), "");
+    assert_eq!(dt.offset().dst_offset(), Duration::zero());
+    assert_eq!(dt.to_string(), "2024-06-01 12:00:00 +0530");
+}
+
+#[test]
+fn julian_and_ordinal_days() {
+    // J60 is always March 1, while 59 is March 1 only outside of leap years.
+    let julian: PosixTz = "STD0DST,J60/0,J300/0".parse().unwrap();
+    let ordinal: PosixTz = "STD0DST,59/0,299/0".parse().unwrap();
+    let start = |tz: &PosixTz, year| {
+        let before = tz.with_ymd_and_hms(year, 2, 29, 12, 0, 0).single();
+        let after = tz.with_ymd_and_hms(year, 3, 1, 12, 0, 0).unwrap();
+        (
+            before.map(|dt| dt.offset().dst_offset()),
+            after.offset().dst_offset(),
+        )
+    };
+    assert_eq!(start(&julian, 2023), (None, Duration::hours(1)));
+    assert_eq!(start(&ordinal, 2023), (None, Duration::hours(1)));
+    assert_eq!(
+        start(&julian, 2024),
+        (Some(Duration::zero()), Duration::hours(1))
+    );
+    assert_eq!(
+        start(&ordinal, 2024),
+        (Some(Duration::hours(1)), Duration::hours(1))
+    );
+}
+
+#[test]
+fn dst_all_year() {
+    let tz: PosixTz = "EST5EDT,0/0,J365/25".parse().unwrap();
+    let mut dt = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    while dt < end {
+        let local = dt.with_timezone(&tz);
+        assert_eq!(local.offset().abbreviation(), Some("EDT"), "at {}", dt);
+        dt += Duration::hours(1);
+    }
+}
+
+#[test]
+fn gaps_and_folds() {
+    let tz: PosixTz = "EST5EDT".parse().unwrap();
+    let gap = NaiveDate::from_ymd_opt(2024, 3, 10)
+        .unwrap()
+        .and_hms_opt(2, 30, 0)
+        .unwrap();
+    assert_eq!(tz.from_local_datetime(&gap), LocalResult::None);
+
+    let fold = NaiveDate::from_ymd_opt(2024, 11, 3)
+        .unwrap()
+        .and_hms_opt(1, 30, 0)
+        .unwrap();
+    match tz.from_local_datetime(&fold) {
+        LocalResult::Ambiguous(earlier, later) => {
+            assert_eq!(earlier.offset().abbreviation(), Some("EDT"));
+            assert_eq!(later.offset().abbreviation(), Some("EST"));
+            assert!(earlier < later);
+        }
+        result => panic!("expected an ambiguous result, got {:?}", result),
+    }
+}
+
+#[test]
+fn far_future() {
+    let tz: PosixTz = "CET-1CEST,M3.5.0,M10.5.0/3".parse().unwrap();
+    let summer = tz.with_ymd_and_hms(200_000, 7, 1, 0, 0, 0).unwrap();
+    assert_eq!(summer.offset().abbreviation(), Some("CEST"));
+    let winter = tz.with_ymd_and_hms(-200_000, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(winter.offset().abbreviation(), Some("CET"));
+
+    // Rules are only evaluated for years that `NaiveDate` can represent.
+    let first = Utc
+        .from_utc_datetime(&NaiveDateTime::MIN)
+        .with_timezone(&tz);
+    assert_eq!(first.offset().abbreviation(), Some("CET"));
+    let last = Utc
+        .from_utc_datetime(&NaiveDateTime::MAX)
+        .with_timezone(&tz);
+    assert_eq!(last.offset().abbreviation(), Some("CET"));
+    assert_eq!(
+        tz.offset_from_local_datetime(&NaiveDateTime::MAX)
+            .map(|offset| offset.fix()),
+        LocalResult::Single(tz.offset_from_utc_datetime(&NaiveDateTime::MAX).fix())
+    );
+}
+
+#[test]
+fn display() {
+    for (input, output) in [
+        ("UTC0", "UTC0"),
+        ("EST+5", "EST5"),
+        ("EST5EDT", "EST5EDT,M3.2.0,M11.1.0"),
+        (
+            "EST5EDT4,M3.2.0/02:00:00,M11.1.0/2",
+            "EST5EDT,M3.2.0,M11.1.0",
+        ),
+        ("IST-1GMT0,M10.5.0,M3.5.0/1", "IST-1GMT0,M10.5.0,M3.5.0/1"),
+        (
+            "<-03>3<-02>,M3.5.0/-2,M10.5.0/-1",
+            "<-03>3<-02>,M3.5.0/-2,M10.5.0/-1",
+        ),
+        ("XXX3:25:45", "XXX3:25:45"),
+        ("<+0545>-5:45", "<+0545>-5:45"),
+        ("ABC0DEF,J1/167,300/-167", "ABC0DEF,J1/167,300/-167"),
+    ] {
+        let tz: PosixTz = input.parse().unwrap();
+        assert_eq!(tz.to_string(), output);
+        assert_eq!(format!("{:?}", tz), output);
+    }
+}
+
+#[test]
+fn invalid() {
+    for (input, error) in [
+        ("", PosixTzError::InvalidAbbreviation),
+        ("ES5", PosixTzError::InvalidAbbreviation),
+        ("<+05", PosixTzError::InvalidAbbreviation),
+        ("<+0!>-5", PosixTzError::InvalidAbbreviation),
+        ("ABCDEFGHIJKLMNOPQ5", PosixTzError::InvalidAbbreviation),
+        ("EST", PosixTzError::InvalidOffset),
+        ("EST25", PosixTzError::InvalidOffset),
+        ("EST5:60", PosixTzError::InvalidOffset),
+        ("EST24EDT", PosixTzError::InvalidOffset),
+        ("EST5EDT,M3.2.0", PosixTzError::InvalidRule),
+        ("EST5EDT,M13.2.0,M11.1.0", PosixTzError::InvalidRule),
+        ("EST5EDT,M3.6.0,M11.1.0", PosixTzError::InvalidRule),
+        ("EST5EDT,J0,J365", PosixTzError::InvalidRule),
+        ("EST5EDT,0,366", PosixTzError::InvalidRule),
+        ("EST5EDT,M3.2.0/168,M11.1.0", PosixTzError::InvalidRule),
+        ("EST5EDT,M3.2.0,M11.1.0x", PosixTzError::TrailingCharacters),
+        (":America/New_York", PosixTzError::InvalidAbbreviation),
+    ] {
+        assert_eq!(input.parse::<PosixTz>(), Err(error), "{:?}", input);
+    }
+}
@@ -0,0 +1,122 @@
+//! Tests for resolving zones through providers.
+
+#![cfg(feature = "std")]
+
+use std::collections::HashMap;
+use std::fs;
+
+use chrono::{Duration, Offset, TimeZone, Utc};
+use chrono_tz::{
+    CompiledTz, DynamicTz, OffsetComponents, OffsetName, Tz, TzProvider, ZoneinfoDir, TZ_VARIANTS,
+};
+
+const ZONEINFO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/zoneinfo");
+
+#[test]
+fn compiled_zones() {
+    for &compiled in TZ_VARIANTS.iter() {
+        let dynamic = CompiledTz.resolve(compiled.name()).unwrap();
+        assert_eq!(dynamic.name(), compiled.name());
+        let mut dt = Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+        while dt < end {
+            let expected = dt.with_timezone(&compiled);
+            let actual = dt.with_timezone(&dynamic);
+            assert_eq!(actual.offset().fix(), expected.offset().fix(), "at {}", dt);
+            assert_eq!(
+                actual.offset().abbreviation(),
+                expected.offset().abbreviation(),
+                "at {}",
+                dt
+            );
+            assert_eq!(
+                actual.offset().dst_offset(),
+                expected.offset().dst_offset(),
+                "at {}",
+                dt
+            );
+            dt += Duration::days(30);
+        }
+    }
+    assert_eq!(CompiledTz.resolve("Mars/Olympus_Mons"), None);
+}
+
+#[test]
+fn zoneinfo_directory() {
+    let dir = ZoneinfoDir::new(ZONEINFO);
+    let london = dir.resolve("Europe/London").unwrap();
+    assert_eq!(london.name(), "Europe/London");
+    let dt = london.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+    assert_eq!(dt.offset().abbreviation(), Some("BST"));
+    // Files with leap seconds are read too.
+    let right = dir.resolve("right/Europe/London").unwrap();
+    assert_eq!(right.name(), "right/Europe/London");
+
+    // Missing files, and names reaching out of the directory, are not found.
+    assert_eq!(dir.resolve("Asia/Tokyo"), None);
+    assert_eq!(dir.resolve("../zoneinfo/Europe/London"), None);
+    assert_eq!(dir.resolve("./Europe/London"), None);
+    assert_eq!(dir.resolve(&format!("{}/Europe/London", ZONEINFO)), None);
+    assert_eq!(dir.resolve(""), None);
+    assert_eq!(dir.resolve("Europe"), None);
+}
+
+#[test]
+fn zoneinfo_cache() {
+    let root = std::env::temp_dir().join(format!("chrono-tz-provider-{}", std::process::id()));
+    fs::create_dir_all(root.join("Test")).unwrap();
+    fs::copy(
+        format!("{}/Europe/London", ZONEINFO),
+        root.join("Test/Zone"),
+    )
+    .unwrap();
+
+    fs::write(root.join("Test/Text"), "not a TZif file").unwrap();
+
+    let dir = ZoneinfoDir::new(&root);
+    assert_eq!(dir.root(), root);
+    // Unreadable files are not found.
+    assert_eq!(dir.resolve("Test/Text"), None);
+    assert!(dir.resolve("Test/Zone").is_some());
+    fs::remove_file(root.join("Test/Zone")).unwrap();
+    assert!(dir.resolve("Test/Zone").is_some());
+    dir.clear_cache();
+    assert_eq!(dir.resolve("Test/Zone"), None);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn fallback() {
+    let mut overrides = HashMap::new();
+    overrides.insert("Europe/London".to_owned(), DynamicTz::from(Tz::Asia__Tokyo));
+    let provider = overrides.or(CompiledTz);
+    assert_eq!(
+        provider.resolve("Europe/London").unwrap().name(),
+        "Asia/Tokyo"
+    );
+    assert_eq!(
+        provider.resolve("Europe/Paris").unwrap().name(),
+        "Europe/Paris"
+    );
+    assert_eq!(provider.resolve("Mars/Olympus_Mons"), None);
+
+    let providers: Vec<Box<dyn TzProvider>> =
+        vec![Box::new(ZoneinfoDir::new(ZONEINFO)), Box::new(CompiledTz)];
+    assert_eq!(
+        providers.resolve("Asia/Tokyo").unwrap().name(),
+        "Asia/Tokyo"
+    );
+    assert_eq!(
+        providers.resolve("Europe/London"),
+        providers[0].resolve("Europe/London")
+    );
+    let dir_first = ZoneinfoDir::new(ZONEINFO).or(&CompiledTz);
+    assert_eq!(
+        dir_first.resolve("Europe/London"),
+        ZoneinfoDir::new(ZONEINFO).resolve("Europe/London")
+    );
+    assert_ne!(
+        dir_first.resolve("Europe/London"),
+        CompiledTz.resolve("Europe/London")
+    );
+}
@@ -0,0 +1,283 @@
+//! Tests for iterating over the transitions of a time zone.
+
+use chrono::{DateTime, Duration, Offset, TimeZone, Utc};
+
+use chrono_tz::{OffsetComponents, OffsetName, Transition, Tz, TzTransitions, TZ_VARIANTS};
+
+fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+        .unwrap()
+}
+
+fn summary(transition: &Transition) -> (String, Option<&str>, Option<&str>) {
+    (
+        transition.utc().to_string(),
+        transition.before().abbreviation(),
+        transition.after().abbreviation(),
+    )
+}
+
+#[test]
+fn new_york() {
+    let tz = Tz::America__New_York;
+    let transitions: Vec<_> = tz
+        .transitions(utc(2024, 1, 1, 0, 0, 0)..utc(2025, 1, 1, 0, 0, 0))
+        .collect();
+    let summaries: Vec<_> = transitions.iter().map(summary).collect();
+    assert_eq!(
+        summaries,
+        [
+            (
+                "2024-03-10 07:00:00 UTC".to_owned(),
+                Some("EST"),
+                Some("EDT")
+            ),
+            (
+                "2024-11-03 06:00:00 UTC".to_owned(),
+                Some("EDT"),
+                Some("EST")
+            ),
+        ]
+    );
+    assert_eq!(
+        transitions[0].before().fix().local_minus_utc(),
+        -5 * 60 * 60
+    );
+    assert_eq!(transitions[0].after().fix().local_minus_utc(), -4 * 60 * 60);
+    assert_eq!(transitions[0].after().dst_offset(), Duration::hours(1));
+    assert_eq!(transitions[0].after().tz_id(), "America/New_York");
+}
+
+#[test]
+fn reversed() {
+    let tz = Tz::Europe__Berlin;
+    let range = utc(1990, 1, 1, 0, 0, 0)..utc(2030, 1, 1, 0, 0, 0);
+    let forwards: Vec<_> = tz.transitions(range.clone()).collect();
+    let mut backwards: Vec<_> = tz.transitions(range.clone()).rev().collect();
+    backwards.reverse();
+    assert_eq!(forwards.len(), 80);
+    assert_eq!(forwards, backwards);
+
+    // Both ends can be taken from at once.
+    let mut transitions = tz.transitions(range);
+    let first = transitions.next().unwrap();
+    let last = transitions.next_back().unwrap();
+    assert_eq!(first, forwards[0]);
+    assert_eq!(last, forwards[79]);
+    assert_eq!(transitions.count(), 78);
+}
+
+#[test]
+fn bounds() {
+    let tz = Tz::Europe__London;
+    let start = utc(2024, 3, 31, 1, 0, 0);
+    let end = utc(2024, 10, 27, 1, 0, 0);
+    let count = |transitions: chrono_tz::Transitions| transitions.count();
+
+    assert_eq!(count(tz.transitions(start..end)), 1);
+    assert_eq!(count(tz.transitions(start..=end)), 2);
+    assert_eq!(count(tz.transitions(start + Duration::seconds(1)..=end)), 1);
+    assert_eq!(
+        count(tz.transitions(start + Duration::milliseconds(1)..=end)),
+        1
+    );
+    assert_eq!(
+        count(tz.transitions(start..end + Duration::milliseconds(1))),
+        2
+    );
+    assert_eq!(count(tz.transitions(start..start)), 0);
+    assert_eq!(count(tz.transitions(start..=start)), 1);
+    assert!(count(tz.transitions(..start)) > 100);
+
+    // Bounds in other time zones describe the same instants.
+    let local = start.with_timezone(&tz);
+    assert_eq!(count(tz.transitions(local..=local)), 1);
+}
+
+#[test]
+fn after_2100() {
+    let tz = Tz::Australia__Adelaide;
+    let transitions: Vec<_> = tz
+        .transitions(utc(2200, 1, 1, 0, 0, 0)..utc(2201, 1, 1, 0, 0, 0))
+        .collect();
+    let summaries: Vec<_> = transitions.iter().map(summary).collect();
+    assert_eq!(
+        summaries,
+        [
+            (
+                "2200-04-05 16:30:00 UTC".to_owned(),
+                Some("ACDT"),
+                Some("ACST")
+            ),
+            (
+                "2200-10-04 16:30:00 UTC".to_owned(),
+                Some("ACST"),
+                Some("ACDT")
+            ),
+        ]
+    );
+
+    // An unbounded range has no end while daylight saving time is observed.
+    assert_eq!(
+        tz.transitions(utc(2024, 1, 1, 0, 0, 0)..)
+            .take(1000)
+            .count(),
+        1000
+    );
+}
+
+#[test]
+fn fixed_zones() {
+    assert_eq!(Tz::UTC.transitions::<Utc, _>(..).count(), 0);
+    assert_eq!(Tz::Etc__GMTPlus5.transitions::<Utc, _>(..).count(), 0);
+
+    let last = Tz::Asia__Kolkata
+        .transitions::<Utc, _>(..)
+        .next_back()
+        .unwrap();
+    assert_eq!(last.utc(), utc(1945, 10, 14, 17, 30, 0));
+    assert_eq!(last.after().abbreviation(), Some("IST"));
+}
+
+#[test]
+fn offsets_change_at_each_transition() {
+    let range = utc(1900, 1, 1, 0, 0, 0)..utc(2120, 1, 1, 0, 0, 0);
+    for &tz in TZ_VARIANTS.iter() {
+        let mut previous: Option<Transition> = None;
+        for transition in tz.transitions(range.clone()) {
+            let time = transition.utc();
+            assert_ne!(
+                transition.before(),
+                transition.after(),
+                "{} at {}",
+                tz,
+                time
+            );
+            assert_eq!(
+                (time - Duration::seconds(1)).with_timezone(&tz).offset(),
+                transition.before(),
+                "{} at {}",
+                tz,
+                time
+            );
+            assert_eq!(
+                time.with_timezone(&tz).offset(),
+                transition.after(),
+                "{} at {}",
+                tz,
+                time
+            );
+            if let Some(previous) = previous {
+                assert!(previous.utc() < time, "{} at {}", tz, time);
+                assert_eq!(previous.after(), transition.before(), "{} at {}", tz, time);
+            }
+            previous = Some(transition);
+        }
+    }
+}
+
+#[test]
+fn no_offset_change_is_missed() {
+    for tz in [
+        Tz::America__New_York,
+        Tz::Europe__Dublin,
+        Tz::Australia__Lord_Howe,
+        Tz::Africa__Casablanca,
+        Tz::America__PortauPrince,
+    ] {
+        let start = utc(2000, 1, 1, 0, 0, 0);
+        let end = utc(2110, 1, 1, 0, 0, 0);
+        let mut transitions = tz.transitions(start..end).peekable();
+        let mut dt = start;
+        let mut offset = dt.with_timezone(&tz).offset().fix();
+        while dt < end {
+            dt += Duration::minutes(30);
+            let current = dt.with_timezone(&tz).offset().fix();
+            if current != offset {
+                let transition = transitions.next().expect("a transition");
+                assert!(transition.utc() <= dt, "{} at {}", tz, dt);
+                assert!(transition.utc() > dt - Duration::minutes(30));
+                offset = current;
+            }
+            // Transitions that only change the abbreviation are skipped over.
+            while transitions.peek().map_or(false, |t| t.utc() <= dt) {
+                let transition = transitions.next().unwrap();
+                assert_eq!(transition.before().fix(), transition.after().fix());
+            }
+        }
+    }
+}
+
+#[test]
+fn next_and_previous() {
+    let tz = Tz::America__New_York;
+    let transition = utc(2024, 3, 10, 7, 0, 0);
+
+    let next = |dt: DateTime<Utc>| tz.next_transition(&dt).map(|t| t.utc());
+    let previous = |dt: DateTime<Utc>| tz.previous_transition(&dt).map(|t| t.utc());
+    assert_eq!(next(transition - Duration::seconds(1)), Some(transition));
+    assert_eq!(
+        next(transition - Duration::nanoseconds(1)),
+        Some(transition)
+    );
+    assert_eq!(next(transition), Some(utc(2024, 11, 3, 6, 0, 0)));
+    assert_eq!(
+        previous(transition + Duration::seconds(1)),
+        Some(transition)
+    );
+    assert_eq!(
+        previous(transition + Duration::nanoseconds(1)),
+        Some(transition)
+    );
+    assert_eq!(previous(transition), Some(utc(2023, 11, 5, 6, 0, 0)));
+
+    // The first transition is the end of local mean time.
+    let first = tz.transitions::<Utc, _>(..).next().unwrap();
+    assert_eq!(first.utc().date_naive().to_string(), "1883-11-18");
+    assert_eq!(previous(first.utc()), None);
+    assert_eq!(first.before().abbreviation(), Some("LMT"));
+}
+
+#[test]
+fn next_and_previous_from_datetime() {
+    let dt = Tz::Europe__Berlin
+        .with_ymd_and_hms(2024, 10, 27, 2, 30, 0)
+        .earliest()
+        .unwrap();
+    let next = dt.next_transition().unwrap();
+    assert_eq!(next.utc(), utc(2024, 10, 27, 1, 0, 0));
+    assert_eq!(next.before(), dt.offset());
+    assert_eq!(next.after().abbreviation(), Some("CET"));
+
+    let later = next.utc().with_timezone(&Tz::Europe__Berlin);
+    assert_eq!(later.previous_transition(), dt.previous_transition());
+    assert_eq!(
+        later.next_transition(),
+        Tz::Europe__Berlin.next_transition(&later)
+    );
+    assert_eq!(later.offset(), next.after());
+}
+
+#[test]
+fn no_more_transitions() {
+    let dt = Tz::Asia__Kolkata
+        .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        .unwrap();
+    assert_eq!(dt.next_transition(), None);
+    assert!(dt.previous_transition().is_some());
+
+    let dt = Tz::Etc__UTC.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(dt.next_transition(), None);
+    assert_eq!(dt.previous_transition(), None);
+
+    // Zones following a daylight saving time rule never run out.
+    let dt = DateTime::<Utc>::MAX_UTC - Duration::days(400);
+    assert!(Tz::Europe__Paris.next_transition(&dt).is_some());
+    assert_eq!(
+        Tz::Europe__Paris.next_transition(&DateTime::<Utc>::MAX_UTC),
+        None
+    );
+    assert!(Tz::Europe__Paris
+        .previous_transition(&DateTime::<Utc>::MIN_UTC)
+        .is_none());
+}
@@ -0,0 +1,104 @@
+//! Tests for compiling zones from tz database sources at runtime.
+
+#![cfg(feature = "runtime-tzdata")]
+
+use chrono::{Duration, Offset, TimeZone, Utc};
+use chrono_tz::{DynamicTzSet, OffsetComponents, OffsetName, TzdataError, TZ_VARIANTS};
+
+#[test]
+fn matches_compiled_zones() {
+    let zones = DynamicTzSet::from_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tz")).unwrap();
+    assert_eq!(zones.len(), TZ_VARIANTS.len());
+    assert!(zones.names().eq(TZ_VARIANTS.iter().map(|tz| tz.name())));
+
+    for &compiled in TZ_VARIANTS.iter() {
+        let dynamic = zones.get(compiled.name()).unwrap();
+        assert_eq!(dynamic.name(), compiled.name());
+        // Monthly samples, which reach past the tables into the trailing rules,
+        // and both sides of every transition.
+        let start = Utc.with_ymd_and_hms(1850, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+        let samples = (0..)
+            .map(|month| start + Duration::days(30 * month))
+            .take_while(|&dt| dt < end);
+        let transitions = compiled
+            .transitions(start..end)
+            .flat_map(|t| [t.utc() - Duration::seconds(1), t.utc()]);
+        for dt in samples.chain(transitions) {
+            let expected = dt.with_timezone(&compiled);
+            let actual = dt.with_timezone(dynamic);
+            assert_eq!(
+                actual.offset().fix(),
+                expected.offset().fix(),
+                "{} at {}",
+                compiled,
+                dt
+            );
+            assert_eq!(
+                actual.offset().abbreviation(),
+                expected.offset().abbreviation(),
+                "{} at {}",
+                compiled,
+                dt
+            );
+            assert_eq!(
+                actual.offset().dst_offset(),
+                expected.offset().dst_offset(),
+                "{} at {}",
+                compiled,
+                dt
+            );
+            assert_eq!(actual.offset().tz_id(), compiled.name());
+        }
+    }
+}
+
+#[test]
+fn links() {
+    let source = "
+Zone Etc/UTC 0 - UTC
+Link Etc/UTC Etc/Universal
+Link Etc/Universal Etc/Zulu
+";
+    let zones = DynamicTzSet::from_sources([source]).unwrap();
+    assert!(zones.names().eq(["Etc/UTC", "Etc/Universal", "Etc/Zulu"]));
+    let zulu = zones.get("Etc/Zulu").unwrap();
+    assert_eq!(zulu.name(), "Etc/Zulu");
+    let dt = zulu.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(dt.offset().abbreviation(), Some("UTC"));
+
+    let broken = DynamicTzSet::from_sources(["Link Etc/UTC Etc/Zulu"]);
+    assert!(matches!(broken, Err(TzdataError::BrokenLink(name)) if name == "Etc/Zulu"));
+}
+
+#[test]
+fn invalid_sources() {
+    let unknown_rules = DynamicTzSet::from_sources(["Zone Europe/Paris 1:00 EU CE%sT # comment"]);
+    match unknown_rules {
+        Err(TzdataError::InvalidLine { line, .. }) => {
+            assert_eq!(line, "Zone Europe/Paris 1:00 EU CE%sT ")
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+    let garbage = DynamicTzSet::from_sources(["Zone"]);
+    assert!(matches!(garbage, Err(TzdataError::InvalidLine { .. })));
+
+    // Continuation lines may use rules defined later, but not undefined ones.
+    let unknown_rules =
+        DynamicTzSet::from_sources(["Zone Test/Zone 1:00 - LMT 1900\n\t\t\t2:00 Nope X%sT"]);
+    assert!(matches!(unknown_rules, Err(TzdataError::InvalidZone(name)) if name == "Test/Zone"));
+    let later_rules = DynamicTzSet::from_sources([
+        "Zone Test/Zone 1:00 - LMT 1900\n\t\t\t2:00 Later X%sT",
+        "Rule Later 1990 max - Mar lastSun 1:00u 1:00 D\nRule Later 1990 max - Oct lastSun 1:00u 0 S",
+    ]);
+    assert!(later_rules.unwrap().get("Test/Zone").is_some());
+
+    // Only the last line of a zone may last forever.
+    let unbounded = DynamicTzSet::from_sources(["Zone Etc/Test 1:00 - A\n 2:00 - B"]);
+    assert!(matches!(unbounded, Err(TzdataError::InvalidZone(name)) if name == "Etc/Test"));
+
+    let missing = DynamicTzSet::from_dir("does/not/exist");
+    assert!(matches!(missing, Err(TzdataError::Io(_))));
+
+    assert!(DynamicTzSet::from_sources([""]).unwrap().is_empty());
+}
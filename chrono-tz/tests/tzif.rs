@@ -0,0 +1,324 @@
+//! Tests for reading and writing TZif files.
+//!
+//! The files under `data/zoneinfo` were compiled with `zic` from the same
+//! tz database release as the crate, except for `right/Europe/London`, which
+//! is only used for its leap second records. The files under `data/tzif` were
+//! compiled with `zic -b slim` from that release, and are what the writer is
+//! expected to produce.
+
+#![cfg(feature = "std")]
+
+use chrono::{Duration, NaiveDate, Offset, TimeZone, Utc};
+
+use chrono_tz::{DynamicTz, OffsetComponents, OffsetName, Tz, TzifError};
+
+const LONDON: &[u8] = include_bytes!("data/zoneinfo/Europe/London");
+const NEW_YORK: &[u8] = include_bytes!("data/zoneinfo/America/New_York");
+const LORD_HOWE: &[u8] = include_bytes!("data/zoneinfo/Australia/Lord_Howe");
+const RIGHT_LONDON: &[u8] = include_bytes!("data/zoneinfo/right/Europe/London");
+
+fn assert_same_utc_offsets(dynamic: &DynamicTz, compiled: Tz, from: i32, to: i32) {
+    let mut dt = Utc.with_ymd_and_hms(from, 1, 1, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(to, 1, 1, 0, 0, 0).unwrap();
+    while dt < end {
+        let expected = dt.with_timezone(&compiled);
+        let actual = dt.with_timezone(dynamic);
+        assert_eq!(actual.offset().fix(), expected.offset().fix(), "at {}", dt);
+        assert_eq!(
+            actual.offset().abbreviation(),
+            expected.offset().abbreviation(),
+            "at {}",
+            dt
+        );
+        assert_eq!(
+            actual.offset().dst_offset(),
+            expected.offset().dst_offset(),
+            "at {}",
+            dt
+        );
+        dt += Duration::hours(6);
+    }
+}
+
+fn assert_same_local_offsets(dynamic: &DynamicTz, compiled: Tz, year: i32) {
+    let mut local = NaiveDate::from_ymd_opt(year, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let end = NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    while local < end {
+        let expected = compiled
+            .from_local_datetime(&local)
+            .map(|dt| dt.fixed_offset());
+        let actual = dynamic
+            .from_local_datetime(&local)
+            .map(|dt| dt.fixed_offset());
+        assert_eq!(actual, expected, "at {}", local);
+        local += Duration::minutes(15);
+    }
+}
+
+#[test]
+fn london_matches_compiled_zone() {
+    let london = DynamicTz::from_tzif("Europe/London", LONDON).unwrap();
+    assert_same_utc_offsets(&london, Tz::Europe__London, 1840, 2037);
+    assert_same_local_offsets(&london, Tz::Europe__London, 1996);
+}
+
+#[test]
+fn new_york_matches_compiled_zone() {
+    let new_york = DynamicTz::from_tzif("America/New_York", NEW_YORK).unwrap();
+    // The file is "slim": rules that are still in use are only recorded in its footer.
+    // The comparison starts after the switch from local mean time, which the compiled
+    // tables place a few hours late.
+    assert_same_utc_offsets(&new_york, Tz::America__New_York, 1884, 2120);
+    assert_same_local_offsets(&new_york, Tz::America__New_York, 2006);
+    assert_same_local_offsets(&new_york, Tz::America__New_York, 2024);
+    assert_same_local_offsets(&new_york, Tz::America__New_York, 2101);
+}
+
+#[test]
+fn lord_howe_half_hour_dst() {
+    let lord_howe = DynamicTz::from_tzif("Australia/Lord_Howe", LORD_HOWE).unwrap();
+    let summer = Utc
+        .with_ymd_and_hms(2006, 1, 1, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&lord_howe);
+    assert_eq!(summer.offset().base_utc_offset(), Duration::minutes(630));
+    assert_eq!(summer.offset().dst_offset(), Duration::minutes(30));
+    assert_eq!(summer.offset().tz_id(), "Australia/Lord_Howe");
+    assert_eq!(summer.offset().abbreviation(), Some("+11"));
+    assert_eq!(summer.to_string(), "2006-01-01 11:00:00 +11");
+}
+
+#[test]
+fn version_1_file() {
+    // The "fat" London file starts with a complete version 1 data block, which
+    // turns into a valid version 1 file once the version byte is cleared.
+    let mut data = LONDON.to_vec();
+    data[4] = 0;
+    let london = DynamicTz::from_tzif("Europe/London", &data).unwrap();
+    assert_same_utc_offsets(&london, Tz::Europe__London, 1902, 2037);
+}
+
+#[test]
+fn leap_seconds_are_taken_out() {
+    // The transition times of the file count the leap seconds since 1972. It has no footer, as
+    // its leap second table expires in 2026, so its last offset lasts forever from then on.
+    let right = DynamicTz::from_tzif("right/Europe/London", RIGHT_LONDON).unwrap();
+    assert_same_utc_offsets(&right, Tz::Europe__London, 1902, 2025);
+    let start = Utc.with_ymd_and_hms(2024, 3, 31, 1, 0, 0).unwrap();
+    let before = (start - Duration::seconds(1)).with_timezone(&right);
+    assert_eq!(before.offset().fix().local_minus_utc(), 0);
+    assert_eq!(
+        start.with_timezone(&right).offset().fix().local_minus_utc(),
+        3600
+    );
+}
+
+#[test]
+fn truncated_data_is_rejected() {
+    for len in 0..LONDON.len() {
+        assert!(DynamicTz::from_tzif("Europe/London", &LONDON[..len]).is_err());
+    }
+}
+
+#[test]
+fn invalid_header() {
+    let mut data = NEW_YORK.to_vec();
+    data[0] = b'X';
+    assert_eq!(
+        DynamicTz::from_tzif("", &data),
+        Err(TzifError::InvalidMagic)
+    );
+
+    let mut data = NEW_YORK.to_vec();
+    data[4] = b'5';
+    assert_eq!(
+        DynamicTz::from_tzif("", &data),
+        Err(TzifError::UnsupportedVersion(b'5'))
+    );
+
+    // A transition count of 2^32 - 1 must not be trusted.
+    let mut data = NEW_YORK.to_vec();
+    data[32..36].copy_from_slice(&[0xff; 4]);
+    assert_eq!(DynamicTz::from_tzif("", &data), Err(TzifError::TooLarge));
+
+    // No local time types.
+    let mut data = NEW_YORK.to_vec();
+    data[36..40].copy_from_slice(&[0; 4]);
+    assert_eq!(
+        DynamicTz::from_tzif("", &data),
+        Err(TzifError::InvalidHeader)
+    );
+}
+
+/// Builds a version 2 file with an empty version 1 data block.
+fn tzif(transitions: &[(i64, u8)], types: &[(i32, u8, u8)], chars: &[u8]) -> Vec<u8> {
+    fn header(data: &mut Vec<u8>, timecnt: usize, typecnt: usize, charcnt: usize) {
+        data.extend_from_slice(b"TZif2");
+        data.extend_from_slice(&[0; 15]);
+        for count in [0, 0, 0, timecnt, typecnt, charcnt] {
+            data.extend_from_slice(&(count as u32).to_be_bytes());
+        }
+    }
+
+    let mut data = Vec::new();
+    header(&mut data, 0, 1, 1);
+    data.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0]);
+    header(&mut data, transitions.len(), types.len(), chars.len());
+    for (time, _) in transitions {
+        data.extend_from_slice(&time.to_be_bytes());
+    }
+    for (_, index) in transitions {
+        data.push(*index);
+    }
+    for (utoff, is_dst, index) in types {
+        data.extend_from_slice(&utoff.to_be_bytes());
+        data.push(*is_dst);
+        data.push(*index);
+    }
+    data.extend_from_slice(chars);
+    data.extend_from_slice(b"\n\n");
+    data
+}
+
+#[test]
+fn synthetic_file() {
+    let data = tzif(
+        &[(0, 1), (1000, 0)],
+        &[(3600, 0, 0), (7200, 1, 4)],
+        b"STD\0DST\0",
+    );
+    let tz = DynamicTz::from_tzif("Synthetic", &data).unwrap();
+    let dt = Utc.timestamp_opt(500, 0).unwrap().with_timezone(&tz);
+    assert_eq!(dt.offset().abbreviation(), Some("DST"));
+    assert_eq!(dt.offset().base_utc_offset(), Duration::hours(1));
+    assert_eq!(dt.offset().dst_offset(), Duration::hours(1));
+    assert_eq!(format!("{:?}", dt.offset()), "DST");
+    let dt = Utc.timestamp_opt(1000, 0).unwrap().with_timezone(&tz);
+    assert_eq!(dt.offset().abbreviation(), Some("STD"));
+    assert_eq!(format!("{}", tz), "Synthetic");
+}
+
+#[test]
+fn footer_rule() {
+    // Without transitions, the footer applies at all times.
+    let mut data = tzif(&[], &[(-18000, 0, 0)], b"EST\0");
+    data.truncate(data.len() - 1);
+    data.extend_from_slice(b"EST5EDT,M3.2.0,M11.1.0\n");
+    let tz = DynamicTz::from_tzif("Footer", &data).unwrap();
+    let summer = Utc.with_ymd_and_hms(1950, 7, 1, 0, 0, 0).unwrap();
+    let summer = summer.with_timezone(&tz);
+    assert_eq!(summer.offset().abbreviation(), Some("EDT"));
+    assert_eq!(summer.offset().base_utc_offset(), Duration::hours(-5));
+    assert_eq!(summer.offset().dst_offset(), Duration::hours(1));
+    let winter = Utc.with_ymd_and_hms(2500, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(
+        winter.with_timezone(&tz).to_string(),
+        "2499-12-31 19:00:00 EST"
+    );
+}
+
+#[test]
+fn invalid_records() {
+    assert_eq!(
+        DynamicTz::from_tzif("", &tzif(&[(10, 0), (10, 0)], &[(0, 0, 0)], b"UTC\0")),
+        Err(TzifError::UnsortedTransitions)
+    );
+    assert_eq!(
+        DynamicTz::from_tzif("", &tzif(&[(10, 1)], &[(0, 0, 0)], b"UTC\0")),
+        Err(TzifError::InvalidTypeIndex)
+    );
+    assert_eq!(
+        DynamicTz::from_tzif("", &tzif(&[], &[(86_400, 0, 0)], b"UTC\0")),
+        Err(TzifError::InvalidLocalTimeType)
+    );
+    assert_eq!(
+        DynamicTz::from_tzif("", &tzif(&[], &[(0, 2, 0)], b"UTC\0")),
+        Err(TzifError::InvalidLocalTimeType)
+    );
+    assert_eq!(
+        DynamicTz::from_tzif("", &tzif(&[], &[(0, 0, 4)], b"UTC\0")),
+        Err(TzifError::InvalidDesignation)
+    );
+    assert_eq!(
+        DynamicTz::from_tzif("", &tzif(&[], &[(0, 0, 0)], b"UTC")),
+        Err(TzifError::InvalidDesignation)
+    );
+
+    let mut data = tzif(&[], &[(0, 0, 0)], b"UTC\0");
+    data.pop();
+    assert_eq!(
+        DynamicTz::from_tzif("", &data),
+        Err(TzifError::InvalidFooter)
+    );
+
+    let mut data = tzif(&[], &[(0, 0, 0)], b"UTC\0");
+    data.truncate(data.len() - 1);
+    data.extend_from_slice(b"UTC\n");
+    assert_eq!(
+        DynamicTz::from_tzif("", &data),
+        Err(TzifError::InvalidFooter)
+    );
+}
+
+#[cfg(feature = "tzif-writer")]
+#[test]
+fn written_files_match_zic() {
+    let zones: [(Tz, &[u8]); 6] = [
+        (
+            Tz::America__Detroit,
+            include_bytes!("data/tzif/America/Detroit"),
+        ),
+        (Tz::Asia__Kolkata, include_bytes!("data/tzif/Asia/Kolkata")),
+        (
+            Tz::Atlantic__Canary,
+            include_bytes!("data/tzif/Atlantic/Canary"),
+        ),
+        (Tz::Etc__GMTPlus5, include_bytes!("data/tzif/Etc/GMT+5")),
+        (
+            Tz::Europe__Andorra,
+            include_bytes!("data/tzif/Europe/Andorra"),
+        ),
+        (Tz::Europe__Sofia, include_bytes!("data/tzif/Europe/Sofia")),
+    ];
+    for (tz, expected) in zones {
+        assert_eq!(tz.to_tzif(), expected, "{}", tz);
+    }
+}
+
+#[cfg(feature = "tzif-writer")]
+#[test]
+fn written_files_round_trip() {
+    use chrono_tz::TZ_VARIANTS;
+
+    for tz in TZ_VARIANTS {
+        let dynamic = DynamicTz::from_tzif(tz.name(), &tz.to_tzif()).unwrap();
+        let mut dt = Utc.with_ymd_and_hms(1800, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2120, 1, 1, 0, 0, 0).unwrap();
+        while dt < end {
+            let expected = dt.with_timezone(&tz);
+            let actual = dt.with_timezone(&dynamic);
+            assert_eq!(
+                actual.offset().fix(),
+                expected.offset().fix(),
+                "{} at {}",
+                tz,
+                dt
+            );
+            // Numeric abbreviations are spelled out in the file.
+            assert_eq!(
+                actual.offset().to_string(),
+                expected.offset().to_string(),
+                "{} at {}",
+                tz,
+                dt
+            );
+            dt += Duration::days(7);
+        }
+    }
+}
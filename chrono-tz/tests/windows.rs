@@ -0,0 +1,96 @@
+//! Tests for the mapping between Windows time zone IDs and zones.
+
+use chrono_tz::{windows_zone, windows_zones, Tz, TZ_VARIANTS};
+
+#[test]
+fn from_windows_id() {
+    assert_eq!(
+        Tz::from_windows_id("Pacific Standard Time"),
+        Some(Tz::America__Los_Angeles)
+    );
+    assert_eq!(
+        Tz::from_windows_id("pacific standard time"),
+        Some(Tz::America__Los_Angeles)
+    );
+    assert_eq!(Tz::from_windows_id("UTC"), Some(Tz::Etc__UTC));
+    assert_eq!(Tz::from_windows_id("UTC+12"), Some(Tz::Etc__GMTMinus12));
+    assert_eq!(Tz::from_windows_id("Pacific Standard Time "), None);
+    assert_eq!(Tz::from_windows_id(""), None);
+
+    let pacific = windows_zone("Pacific Standard Time").unwrap();
+    assert_eq!(pacific.id(), "Pacific Standard Time");
+    assert_eq!(pacific.territory_zones("CA"), [Tz::America__Vancouver]);
+    assert_eq!(pacific.territory_zones("ZZ"), [Tz::PST8PDT]);
+    assert_eq!(
+        pacific.territories()[0],
+        ("001", &[Tz::America__Los_Angeles][..])
+    );
+    assert_eq!(pacific.territories().len(), 4);
+}
+
+#[test]
+fn to_windows_id() {
+    assert_eq!(Tz::Europe__London.windows_id(), Some("GMT Standard Time"));
+    assert_eq!(
+        Tz::Africa__Abidjan.windows_id(),
+        Some("Greenwich Standard Time")
+    );
+    // Zones listed under several IDs take the one they are the default of.
+    assert_eq!(
+        Tz::Etc__GMTPlus12.windows_id(),
+        Some("Dateline Standard Time")
+    );
+    assert_eq!(Tz::Etc__UTC.windows_id(), Some("UTC"));
+    assert_eq!(Tz::Etc__GMT.windows_id(), Some("UTC"));
+    // Links listed by CLDR keep their own ID rather than that of their zone.
+    assert_eq!(
+        Tz::Europe__Amsterdam.windows_id(),
+        Some("W. Europe Standard Time")
+    );
+    assert_eq!(
+        Tz::Europe__Brussels.windows_id(),
+        Some("Romance Standard Time")
+    );
+    // Others take that of their zone, or of another link to it.
+    assert_eq!(Tz::US__Eastern.windows_id(), Some("Eastern Standard Time"));
+    assert_eq!(Tz::Asia__Kolkata.windows_id(), Some("India Standard Time"));
+    assert_eq!(Tz::Europe__Kyiv.windows_id(), Some("FLE Standard Time"));
+}
+
+#[test]
+fn consistent_tables() {
+    let zones = windows_zones();
+    for pair in zones.windows(2) {
+        assert!(
+            pair[0].id().to_lowercase() < pair[1].id().to_lowercase(),
+            "{} before {}",
+            pair[0].id(),
+            pair[1].id()
+        );
+    }
+    for zone in zones {
+        assert_eq!(windows_zone(zone.id()), Some(zone));
+        assert_eq!(zone.territories()[0].0, "001");
+        for &(_, territory_zones) in zone.territories() {
+            assert!(!territory_zones.is_empty(), "{}", zone.id());
+            for tz in territory_zones {
+                assert!(tz.windows_id().is_some(), "{} in {}", tz, zone.id());
+            }
+        }
+    }
+    for tz in TZ_VARIANTS {
+        if let Some(id) = tz.windows_id() {
+            let zone = windows_zone(id).unwrap();
+            let canonical = tz.canonical();
+            assert!(
+                zone.territories()
+                    .iter()
+                    .flat_map(|(_, zones)| zones.iter())
+                    .any(|zone| *zone == tz || zone.canonical() == canonical),
+                "{} in {}",
+                tz,
+                id
+            );
+        }
+    }
+}
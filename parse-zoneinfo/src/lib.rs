@@ -26,6 +26,10 @@
 //!   `table` module;
 //! - **Calculating transitions** from this table is done by the `transitions`
 //!   module.
+//!
+//! The results can then be written out as compiled TZif files by the `tzif`
+//! module, with a TZ string from the `posix` module describing each zone
+//! after its last transition.
 
 #![warn(missing_copy_implementations)]
 //#![warn(missing_docs)]
@@ -35,6 +39,8 @@
 #![warn(unused)]
 
 pub mod line;
+pub mod posix;
 pub mod structure;
 pub mod table;
 pub mod transitions;
+pub mod tzif;
@@ -500,6 +500,29 @@ fn leap_years() {
     assert!(!is_leap(2100));
 }
 
+/// Returns the number of days between the Unix epoch and a date of the
+/// proleptic Gregorian calendar, which is negative for dates before it.
+/// Months and days start at 1.
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Counting years from March puts leap days at their end.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+#[test]
+fn civil_days() {
+    assert_eq!(days_from_civil(1970, 1, 1), 0);
+    assert_eq!(days_from_civil(1969, 12, 31), -1);
+    assert_eq!(days_from_civil(1900, 1, 1), -25_567);
+    assert_eq!(days_from_civil(2000, 1, 1), 10_957);
+    assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+}
+
 impl DaySpec {
     /// Converts this day specification to a concrete date, given the year and
     /// month it should occur in.
@@ -0,0 +1,786 @@
+//! Describing the rules of a time zone as a POSIX-style TZ string.
+//!
+//! Most time zones that still observe daylight-saving time do so following
+//! a rule that repeats every year, such as “from the last Sunday in March
+//! until the last Sunday in October”. These rules can be written down as a
+//! TZ string, like the ones accepted by the `TZ` environment variable, which
+//! compiled TZif files include in their footer to cover timestamps after
+//! their last transition.
+//!
+//! This module provides the `PosixTz` type, which holds the parts of such a
+//! string, and the `TablePosix` trait, which computes the string for a zone
+//! from a `Table` the same way `zic` does. Parsing TZ strings is left to the
+//! `PosixTz` type of `chrono-tz`, which also works without the standard
+//! library.
+//!
+//! Writing slim TZif files needs the transitions a string produces, so this
+//! module evaluates them too. `chrono-tz` has its own evaluator, since it
+//! can't depend on this crate without the standard library, and this crate
+//! doesn't depend on `chrono`; a test in `chrono-tz` checks that the two
+//! agree for every zone.
+//!
+//! ## Example
+//!
+//! ```
+//! use parse_zoneinfo::posix::{PosixDate, PosixDst, PosixTransition, PosixTz};
+//!
+//! let last_sunday = |month, time| PosixTransition {
+//!     date: PosixDate::MonthWeekday { month, week: 5, weekday: 0 },
+//!     time,
+//! };
+//! let tz = PosixTz {
+//!     std_abbreviation: "GMT".to_owned(),
+//!     std_offset: 0,
+//!     dst: Some(PosixDst {
+//!         abbreviation: "BST".to_owned(),
+//!         utc_offset: 3600,
+//!         start: last_sunday(3, 3600),
+//!         end: last_sunday(10, 7200),
+//!     }),
+//! };
+//! assert_eq!(tz.to_string(), "GMT0BST,M3.5.0/1,M10.5.0");
+//! ```
+//!
+//! The format is described in the POSIX standard and, with the extensions
+//! used by version 3 TZif files, in section 3.3 of [RFC 8536][rfc].
+//!
+//! [rfc]: https://datatracker.ietf.org/doc/html/rfc8536#section-3.3
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::line::{days_from_civil, DaySpec, Month, TimeType, Year};
+use crate::table::{Format, RuleInfo, Saving, Table, ZoneInfo};
+use crate::transitions::FixedTimespan;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The default time of day for a transition, when a TZ string leaves it out.
+const DEFAULT_TIME: i64 = 2 * 60 * 60;
+
+/// Offsets and transition times must be within a week of zero.
+const MAX_HOURS: i64 = 24 * 7;
+
+/// A computed POSIX-style TZ string.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct PosixTz {
+    /// The abbreviation in use during standard time.
+    pub std_abbreviation: String,
+
+    /// The number of seconds that need to be added to UTC to get standard
+    /// time. Note that TZ strings write this the other way around, so that
+    /// `EST5` has an offset of minus five hours.
+    pub std_offset: i64,
+
+    /// The daylight-saving time rule, or `None` if standard time is in effect
+    /// all year round.
+    pub dst: Option<PosixDst>,
+}
+
+/// The daylight-saving part of a TZ string.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct PosixDst {
+    /// The abbreviation in use during daylight-saving time.
+    pub abbreviation: String,
+
+    /// The number of seconds that need to be added to UTC to get
+    /// daylight-saving time. This is the *total* offset, not the amount
+    /// added to standard time, and it may be less than the standard offset.
+    pub utc_offset: i64,
+
+    /// When daylight-saving time starts each year.
+    pub start: PosixTransition,
+
+    /// When daylight-saving time ends each year.
+    pub end: PosixTransition,
+}
+
+/// The yearly instant at which a TZ string switches between standard and
+/// daylight-saving time.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct PosixTransition {
+    /// The day of the year of the transition.
+    pub date: PosixDate,
+
+    /// The number of seconds after midnight at which the transition happens,
+    /// in the local time that was in effect *before* it. This can be negative
+    /// or more than a day, to refer to the previous or following days.
+    pub time: i64,
+}
+
+/// A day of the year, in any of the three forms a TZ string can use.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum PosixDate {
+    /// `Jn`: a day of the year between 1 and 365, where February 29 is never
+    /// counted, so that `J60` is always March 1.
+    Julian(u16),
+
+    /// `n`: a zero-based day of the year between 0 and 365, counting
+    /// February 29 in leap years.
+    Ordinal(u16),
+
+    /// `Mm.w.d`: day `d` of the week (with Sunday as 0) in week `w` of month
+    /// `m`, where week 1 is the first week in which that day occurs and week
+    /// 5 means the last one.
+    MonthWeekday {
+        /// The month, from 1 to 12.
+        month: u8,
+        /// The week, from 1 to 5.
+        week: u8,
+        /// The day of the week, from 0 (Sunday) to 6 (Saturday).
+        weekday: u8,
+    },
+}
+
+impl PosixTz {
+    /// Returns the instants at which daylight-saving time starts and ends in
+    /// the given year, as Unix timestamps, or `None` if there is no
+    /// daylight-saving time.
+    ///
+    /// In the southern hemisphere, the end comes before the start.
+    pub fn dst_transitions(&self, year: i64) -> Option<(i64, i64)> {
+        let dst = self.dst.as_ref()?;
+        let start = dst.start.local_timestamp(year) - self.std_offset;
+        let end = dst.end.local_timestamp(year) - dst.utc_offset;
+        Some((start, end))
+    }
+
+    /// Whether writing this string requires version 3 of the TZif format,
+    /// which allows transition times outside of the range 0–24 hours.
+    pub(crate) fn needs_version_3(&self) -> bool {
+        match self.dst {
+            Some(ref dst) => [dst.start, dst.end]
+                .iter()
+                .any(|t| t.time < 0 || t.time > SECONDS_PER_DAY),
+            None => false,
+        }
+    }
+
+    /// Returns the first transition this TZ string produces after the given
+    /// time, or `None` if there is no daylight-saving time.
+    pub(crate) fn next_transition(&self, time: i64) -> Option<i64> {
+        let year = year_of_timestamp(time);
+        (year - 1..=year + 1)
+            .filter_map(|y| self.dst_transitions(y))
+            .flat_map(|(start, end)| [start, end])
+            .filter(|&t| t > time)
+            .min()
+    }
+
+    /// Whether a transition into `timespan` at the given time is one that
+    /// this TZ string would produce anyway.
+    pub(crate) fn predicts(&self, time: i64, timespan: &FixedTimespan) -> bool {
+        let dst = match self.dst {
+            Some(ref dst) => dst,
+            None => return false,
+        };
+
+        let year = year_of_timestamp(time);
+        let starts_dst = (year - 1..=year + 1).any(|y| match self.dst_transitions(y) {
+            Some((start, _)) => start == time,
+            None => false,
+        });
+        let ends_dst = (year - 1..=year + 1).any(|y| match self.dst_transitions(y) {
+            Some((_, end)) => end == time,
+            None => false,
+        });
+
+        if starts_dst {
+            timespan.dst_offset != 0
+                && timespan.total_offset() == dst.utc_offset
+                && timespan_abbreviation(timespan) == dst.abbreviation
+        } else if ends_dst {
+            timespan.dst_offset == 0
+                && timespan.total_offset() == self.std_offset
+                && timespan_abbreviation(timespan) == self.std_abbreviation
+        } else {
+            false
+        }
+    }
+}
+
+impl PosixTransition {
+    /// The local time of this transition in the given year, as a number of
+    /// seconds since the Unix epoch as if the local time were UTC.
+    fn local_timestamp(&self, year: i64) -> i64 {
+        let day = days_from_civil(year, 1, 1) + self.date.day_of_year(year);
+        day * SECONDS_PER_DAY + self.time
+    }
+}
+
+impl PosixDate {
+    /// Returns the zero-based day of the year this date falls on.
+    fn day_of_year(&self, year: i64) -> i64 {
+        match *self {
+            PosixDate::Julian(day) => {
+                let day = i64::from(day) - 1;
+                if is_leap(year) && day >= 59 {
+                    day + 1
+                } else {
+                    day
+                }
+            }
+            PosixDate::Ordinal(day) => i64::from(day),
+            PosixDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => {
+                let month = i64::from(month);
+                let first = days_from_civil(year, month, 1);
+                // 1970-01-01 was a Thursday.
+                let first_weekday = (first + 4).rem_euclid(7);
+                let mut day =
+                    (i64::from(weekday) - first_weekday).rem_euclid(7) + 7 * (i64::from(week) - 1);
+                let length = month_length(year, month);
+                while day >= length {
+                    day -= 7;
+                }
+                first + day - days_from_civil(year, 1, 1)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PosixTz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_abbreviation(f, &self.std_abbreviation)?;
+        write_duration(f, -self.std_offset)?;
+        if let Some(ref dst) = self.dst {
+            write_abbreviation(f, &dst.abbreviation)?;
+            if dst.utc_offset != self.std_offset + 60 * 60 {
+                write_duration(f, -dst.utc_offset)?;
+            }
+            write!(f, ",{},{}", dst.start, dst.end)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PosixTransition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.date {
+            PosixDate::Julian(day) => write!(f, "J{}", day)?,
+            PosixDate::Ordinal(day) => write!(f, "{}", day)?,
+            PosixDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            } => write!(f, "M{}.{}.{}", month, week, weekday)?,
+        }
+        if self.time != DEFAULT_TIME {
+            f.write_str("/")?;
+            write_duration(f, self.time)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes an abbreviation, quoting it unless it is entirely alphabetic.
+fn write_abbreviation(f: &mut fmt::Formatter, abbreviation: &str) -> fmt::Result {
+    if abbreviation.bytes().all(|b| b.is_ascii_alphabetic()) {
+        f.write_str(abbreviation)
+    } else {
+        write!(f, "<{}>", abbreviation)
+    }
+}
+
+/// Writes a number of seconds as hours, with minutes and seconds only if
+/// they are needed.
+fn write_duration(f: &mut fmt::Formatter, seconds: i64) -> fmt::Result {
+    if seconds < 0 {
+        f.write_str("-")?;
+    }
+    let seconds = seconds.abs();
+    write!(f, "{}", seconds / 3600)?;
+    if seconds % 3600 != 0 {
+        write!(f, ":{:02}", seconds / 60 % 60)?;
+        if seconds % 60 != 0 {
+            write!(f, ":{:02}", seconds % 60)?;
+        }
+    }
+    Ok(())
+}
+
+/// Trait to put the `posix_tz` method on Tables.
+pub trait TablePosix {
+    /// Computes the TZ string describing the time zone with the given name
+    /// after its last transition, using the rules of its last zone line.
+    ///
+    /// Returns `None` if the table doesn’t contain a time zone with that
+    /// name, or if its rules can’t be expressed as a TZ string.
+    fn posix_tz(&self, zone_name: &str) -> Option<PosixTz>;
+}
+
+impl TablePosix for Table {
+    fn posix_tz(&self, zone_name: &str) -> Option<PosixTz> {
+        let zone = self.get_zoneset(zone_name)?.last()?;
+        let rules: &[RuleInfo] = match zone.saving {
+            Saving::Multiple(ref name) => &self.rulesets[name],
+            _ => &[],
+        };
+
+        // Find the last standard time and daylight-saving time rules. If two
+        // rules of the same kind apply indefinitely, the zone has more than
+        // two transitions a year, which a TZ string can’t describe.
+        let mut last_std: Option<&RuleInfo> = None;
+        let mut last_dst: Option<&RuleInfo> = None;
+        for rule in rules {
+            let last = if rule.time_to_add != 0 {
+                &mut last_dst
+            } else {
+                &mut last_std
+            };
+            match compare_rules(*last, Some(rule)) {
+                Ordering::Less => *last = Some(rule),
+                Ordering::Equal => return None,
+                Ordering::Greater => {}
+            }
+        }
+
+        let dst_cmp = if rules.is_empty() {
+            match zone.saving {
+                Saving::OneOff(amount) if amount != 0 => Ordering::Greater,
+                _ => Ordering::Less,
+            }
+        } else {
+            compare_rules(last_dst, last_std)
+        };
+
+        let tz = match dst_cmp {
+            // Standard time all year round.
+            Ordering::Less => PosixTz {
+                std_abbreviation: abbreviation(zone, last_std, 0),
+                std_offset: zone.offset,
+                dst: None,
+            },
+
+            // Daylight-saving time all year round. This is written as a zone
+            // with daylight-saving time from January 1 until the end of the
+            // year, with a made-up standard time that’s never in effect.
+            Ordering::Greater => {
+                let save = match (last_dst, &zone.saving) {
+                    (Some(rule), _) => rule.time_to_add,
+                    (None, &Saving::OneOff(amount)) => amount,
+                    (None, _) => 0,
+                };
+                let letters = last_dst.and_then(|rule| rule.letters.as_ref());
+                let (std_abbreviation, std_offset) = if save >= 0 {
+                    ("XXX".to_owned(), zone.offset + 2 * save)
+                } else {
+                    let letters = last_std.and_then(|rule| rule.letters.as_ref());
+                    (
+                        format_abbreviation(zone, zone.offset, letters, 0),
+                        zone.offset,
+                    )
+                };
+                let save = -save.abs();
+                PosixTz {
+                    std_abbreviation,
+                    std_offset,
+                    dst: Some(PosixDst {
+                        abbreviation: format_abbreviation(zone, std_offset, letters, save),
+                        utc_offset: std_offset + save,
+                        start: PosixTransition {
+                            date: PosixDate::Ordinal(0),
+                            time: 0,
+                        },
+                        end: PosixTransition {
+                            date: PosixDate::Julian(365),
+                            time: SECONDS_PER_DAY + save,
+                        },
+                    }),
+                }
+            }
+
+            Ordering::Equal => {
+                let (std_rule, dst_rule) = (last_std?, last_dst?);
+                let save = dst_rule.time_to_add;
+                PosixTz {
+                    std_abbreviation: abbreviation(zone, Some(std_rule), 0),
+                    std_offset: zone.offset,
+                    dst: Some(PosixDst {
+                        abbreviation: abbreviation(zone, Some(dst_rule), save),
+                        utc_offset: zone.offset + save,
+                        start: rule_transition(dst_rule, save, zone.offset)?,
+                        end: rule_transition(std_rule, save, zone.offset)?,
+                    }),
+                }
+            }
+        };
+
+        let in_range = |offset: i64| offset.abs() / 3600 < MAX_HOURS;
+        if !in_range(tz.std_offset) || !tz.dst.as_ref().map_or(true, |dst| in_range(dst.utc_offset))
+        {
+            return None;
+        }
+        Some(tz)
+    }
+}
+
+/// Orders rules by how late they apply, the way `zic` does: first by their
+/// last year, then by their month and day. Rules that apply indefinitely are
+/// all equal, and `None` comes before anything else.
+fn compare_rules(a: Option<&RuleInfo>, b: Option<&RuleInfo>) -> Ordering {
+    let (a, b) = match (a, b) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Less,
+        (Some(_), None) => return Ordering::Greater,
+        (Some(a), Some(b)) => (a, b),
+    };
+
+    let last_year = |rule: &RuleInfo| match (rule.from_year, rule.to_year) {
+        (_, Some(Year::Maximum)) => i64::MAX,
+        (_, Some(Year::Number(year))) | (Year::Number(year), None) => year,
+        _ => i64::MIN,
+    };
+    let day_of_month = |rule: &RuleInfo| match rule.day {
+        DaySpec::Ordinal(day)
+        | DaySpec::LastOnOrBefore(_, day)
+        | DaySpec::FirstOnOrAfter(_, day) => day,
+        DaySpec::Last(_) => month_length(2000, rule.month as i64) as i8,
+    };
+
+    match last_year(a).cmp(&last_year(b)) {
+        Ordering::Equal if last_year(a) == i64::MAX => Ordering::Equal,
+        Ordering::Equal => (a.month as i8, day_of_month(a)).cmp(&(b.month as i8, day_of_month(b))),
+        ordering => ordering,
+    }
+}
+
+/// Returns the abbreviation used by a zone line under a rule.
+fn abbreviation(zone: &ZoneInfo, rule: Option<&RuleInfo>, save: i64) -> String {
+    let letters = rule.and_then(|rule| rule.letters.as_ref());
+    format_abbreviation(zone, zone.offset, letters, save)
+}
+
+fn format_abbreviation(
+    zone: &ZoneInfo,
+    std_offset: i64,
+    letters: Option<&String>,
+    save: i64,
+) -> String {
+    match zone.format {
+        Format::Constant(ref format) if format == "%z" => numeric_abbreviation(std_offset + save),
+        ref format => format.format(save, letters),
+    }
+}
+
+/// Returns the abbreviation of a timespan, expanding `%z` into its offset.
+pub(crate) fn timespan_abbreviation(timespan: &FixedTimespan) -> String {
+    if timespan.name == "%z" {
+        numeric_abbreviation(timespan.total_offset())
+    } else {
+        timespan.name.clone()
+    }
+}
+
+/// Formats an offset the way `%z` does in zone lines, such as `+0530`.
+fn numeric_abbreviation(offset: i64) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    let (hours, minutes, seconds) = (offset / 3600, offset / 60 % 60, offset % 60);
+    if seconds != 0 {
+        format!("{}{:02}{:02}{:02}", sign, hours, minutes, seconds)
+    } else if minutes != 0 {
+        format!("{}{:02}{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{:02}", sign, hours)
+    }
+}
+
+/// Converts the date and time at which a rule applies into a TZ string
+/// transition, following `stringrule` in `zic.c`.
+///
+/// Rules such as `Sun>=8` that don’t line up with the weeks of a month are
+/// shifted into the form `M3.2.0` by moving the time of day instead.
+fn rule_transition(rule: &RuleInfo, save: i64, std_offset: i64) -> Option<PosixTransition> {
+    let mut time = rule.time;
+    let month = rule.month as u8;
+
+    let date = match rule.day {
+        DaySpec::Ordinal(day) => {
+            if rule.month == Month::February && day == 29 {
+                return None;
+            }
+            let day_of_year = days_from_civil(2001, i64::from(month), i64::from(day))
+                - days_from_civil(2001, 1, 1);
+            if month <= 2 {
+                PosixDate::Ordinal(day_of_year as u16)
+            } else {
+                PosixDate::Julian(day_of_year as u16 + 1)
+            }
+        }
+        DaySpec::Last(weekday) => PosixDate::MonthWeekday {
+            month,
+            week: 5,
+            weekday: weekday as u8,
+        },
+        DaySpec::FirstOnOrAfter(weekday, day) => {
+            let shift = i64::from(day - 1) % 7;
+            time += shift * SECONDS_PER_DAY;
+            PosixDate::MonthWeekday {
+                month,
+                week: 1 + (day as u8 - 1) / 7,
+                weekday: (weekday as i64 - shift).rem_euclid(7) as u8,
+            }
+        }
+        DaySpec::LastOnOrBefore(weekday, day) => {
+            if i64::from(day) == month_length(2000, i64::from(month)) {
+                PosixDate::MonthWeekday {
+                    month,
+                    week: 5,
+                    weekday: weekday as u8,
+                }
+            } else {
+                let shift = i64::from(day) % 7;
+                time += shift * SECONDS_PER_DAY;
+                PosixDate::MonthWeekday {
+                    month,
+                    week: day as u8 / 7,
+                    weekday: (weekday as i64 - shift).rem_euclid(7) as u8,
+                }
+            }
+        }
+    };
+
+    // TZ strings give the time of a transition in the local time in effect
+    // before it, so rules given in standard time or UTC need adjusting.
+    if rule.time_type == TimeType::UTC {
+        time += std_offset;
+    }
+    if rule.time_type != TimeType::Wall && rule.time_to_add == 0 {
+        time += save;
+    }
+
+    if time.abs() / 3600 >= MAX_HOURS {
+        return None;
+    }
+    Some(PosixTransition { date, time })
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn month_length(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the year that a Unix timestamp falls in.
+fn year_of_timestamp(timestamp: i64) -> i64 {
+    let days = timestamp.div_euclid(SECONDS_PER_DAY);
+    // Dividing by the shortest and longest year lengths gives an estimate
+    // that’s never too late.
+    let mut year = 1970 + days.div_euclid(365).min(days.div_euclid(366));
+    while days_from_civil(year + 1, 1, 1) <= days {
+        year += 1;
+    }
+    year
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::line::{Line, LineParser};
+    use crate::table::TableBuilder;
+
+    fn table(lines: &[&str]) -> Table {
+        let parser = LineParser::default();
+        let mut builder = TableBuilder::new();
+        for line in lines {
+            match parser.parse_str(line).unwrap() {
+                Line::Zone(zone) => builder.add_zone_line(zone).unwrap(),
+                Line::Continuation(cont) => builder.add_continuation_line(cont).unwrap(),
+                Line::Rule(rule) => builder.add_rule_line(rule).unwrap(),
+                Line::Link(link) => builder.add_link_line(link).unwrap(),
+                Line::Space => {}
+            }
+        }
+        builder.build()
+    }
+
+    fn posix_tz(lines: &[&str], zone_name: &str) -> String {
+        table(lines).posix_tz(zone_name).unwrap().to_string()
+    }
+
+    #[test]
+    fn fixed_offset() {
+        let lines = [
+            "Zone Asia/Kolkata 5:53:28 - LMT 1854 Jun 28",
+            "                  5:30     - IST",
+            "Zone Asia/Dubai   3:41:12 - LMT 1920",
+            "                  4:00     - %z",
+            "Zone America/Noronha -2:09:40 - LMT 1914",
+            "                     -2:00    - %z",
+        ];
+        assert_eq!(posix_tz(&lines, "Asia/Kolkata"), "IST-5:30");
+        assert_eq!(posix_tz(&lines, "Asia/Dubai"), "<+04>-4");
+        assert_eq!(posix_tz(&lines, "America/Noronha"), "<-02>2");
+    }
+
+    #[test]
+    fn last_sunday_in_utc() {
+        let lines = [
+            "Rule EU 1977 1980 - Apr Sun>=1   1:00u 1:00 S",
+            "Rule EU 1981 max  - Mar lastSun  1:00u 1:00 S",
+            "Rule EU 1996 max  - Oct lastSun  1:00u 0    -",
+            "Zone Europe/London -0:01:15 - LMT 1847 Dec 1",
+            "                    0:00   EU GMT/BST",
+            "Zone Europe/Berlin  0:53:28 - LMT 1893 Apr",
+            "                    1:00   EU CE%sT",
+        ];
+        assert_eq!(
+            posix_tz(&lines, "Europe/London"),
+            "GMT0BST,M3.5.0/1,M10.5.0"
+        );
+        assert_eq!(
+            posix_tz(&lines, "Europe/Berlin"),
+            "CET-1CEST,M3.5.0,M10.5.0/3"
+        );
+    }
+
+    #[test]
+    fn negative_dst() {
+        let lines = [
+            "Rule Eire 1981 max - Mar lastSun 1:00u  0    -",
+            "Rule Eire 1996 max - Oct lastSun 1:00u -1:00 -",
+            "Zone Europe/Dublin -0:25:21 - LMT 1880 Aug 2",
+            "                    1:00 Eire IST/GMT",
+        ];
+        assert_eq!(
+            posix_tz(&lines, "Europe/Dublin"),
+            "IST-1GMT0,M10.5.0,M3.5.0/1"
+        );
+    }
+
+    #[test]
+    fn half_hour_dst() {
+        let lines = [
+            "Rule LH 2008 max - Apr Sun>=1 2:00 0    -",
+            "Rule LH 2008 max - Oct Sun>=1 2:00 0:30 -",
+            "Zone Australia/Lord_Howe 10:36:20 - LMT 1895 Feb",
+            "                         10:30 LH %z",
+        ];
+        assert_eq!(
+            posix_tz(&lines, "Australia/Lord_Howe"),
+            "<+1030>-10:30<+11>-11,M10.1.0,M4.1.0"
+        );
+    }
+
+    #[test]
+    fn shifted_weekday() {
+        let lines = [
+            "Rule Zion 2013 max - Mar Fri>=23 2:00 1:00 D",
+            "Rule Zion 2013 max - Oct lastSun 2:00 0    S",
+            "Zone Asia/Jerusalem 2:20:54 - LMT 1880",
+            "                    2:00 Zion I%sT",
+        ];
+        let tz = table(&lines).posix_tz("Asia/Jerusalem").unwrap();
+        assert_eq!(tz.to_string(), "IST-2IDT,M3.4.4/26,M10.5.0");
+        assert!(tz.needs_version_3());
+    }
+
+    #[test]
+    fn more_than_two_rules() {
+        let lines = [
+            "Rule Odd 2000 max - Mar Sun>=1 2:00 1:00 D",
+            "Rule Odd 2000 max - Jun Sun>=1 2:00 2:00 D",
+            "Rule Odd 2000 max - Oct Sun>=1 2:00 0    S",
+            "Zone Odd/Zone 1:00 Odd %sT",
+        ];
+        assert_eq!(table(&lines).posix_tz("Odd/Zone"), None);
+    }
+
+    fn month_weekday(month: u8, week: u8, weekday: u8, time: i64) -> PosixTransition {
+        PosixTransition {
+            date: PosixDate::MonthWeekday {
+                month,
+                week,
+                weekday,
+            },
+            time,
+        }
+    }
+
+    fn with_dst(
+        std: (&str, i64),
+        dst: (&str, i64),
+        start: PosixTransition,
+        end: PosixTransition,
+    ) -> PosixTz {
+        PosixTz {
+            std_abbreviation: std.0.to_owned(),
+            std_offset: std.1 * 3600,
+            dst: Some(PosixDst {
+                abbreviation: dst.0.to_owned(),
+                utc_offset: dst.1 * 3600,
+                start,
+                end,
+            }),
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        let tz = with_dst(
+            ("EST", -5),
+            ("EDT", -4),
+            month_weekday(3, 2, 0, 7200),
+            month_weekday(11, 1, 0, 7200),
+        );
+        assert_eq!(tz.to_string(), "EST5EDT,M3.2.0,M11.1.0");
+        // 2024-03-10 07:00 UTC and 2024-11-03 06:00 UTC
+        assert_eq!(
+            tz.dst_transitions(2024),
+            Some((1_710_054_000, 1_730_613_600))
+        );
+
+        let tz = with_dst(
+            ("-03", -3),
+            ("-02", -2),
+            month_weekday(3, 5, 0, -7200),
+            month_weekday(10, 5, 0, -3600),
+        );
+        assert_eq!(tz.to_string(), "<-03>3<-02>,M3.5.0/-2,M10.5.0/-1");
+        // 2024-03-31 01:00 UTC and 2024-10-27 01:00 UTC
+        assert_eq!(
+            tz.dst_transitions(2024),
+            Some((1_711_846_800, 1_729_990_800))
+        );
+
+        let tz = with_dst(
+            ("XXX", -3),
+            ("EDT", -4),
+            PosixTransition {
+                date: PosixDate::Ordinal(0),
+                time: 0,
+            },
+            PosixTransition {
+                date: PosixDate::Julian(365),
+                time: 23 * 3600,
+            },
+        );
+        assert_eq!(tz.to_string(), "XXX3EDT4,0/0,J365/23");
+        // 2023-01-01 03:00 UTC and 2024-01-01 03:00 UTC
+        assert_eq!(
+            tz.dst_transitions(2023),
+            Some((1_672_542_000, 1_704_078_000))
+        );
+    }
+
+    #[test]
+    fn years() {
+        assert_eq!(year_of_timestamp(0), 1970);
+        assert_eq!(year_of_timestamp(-1), 1969);
+        assert_eq!(year_of_timestamp(951_782_400), 2000);
+        assert_eq!(year_of_timestamp(978_307_199), 2000);
+        assert_eq!(year_of_timestamp(978_307_200), 2001);
+        assert_eq!(year_of_timestamp(-2_208_988_800), 1900);
+    }
+}
@@ -331,7 +331,7 @@ impl TableBuilder {
     pub fn add_continuation_line(
         &mut self,
         continuation_line: line::ZoneInfo,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'_>> {
         let zoneset = match self.current_zoneset_name {
             Some(ref name) => self.table.zonesets.get_mut(name).unwrap(),
             None => return Err(Error::SurpriseContinuationLine),
@@ -343,7 +343,7 @@ impl TableBuilder {
 
     /// Adds a new line describing one entry in a ruleset, creating that set
     /// if it didn’t exist already.
-    pub fn add_rule_line(&mut self, rule_line: line::Rule) -> Result<(), Error> {
+    pub fn add_rule_line(&mut self, rule_line: line::Rule) -> Result<(), Error<'_>> {
         let ruleset = self
             .table
             .rulesets
@@ -16,7 +16,8 @@
 //! (currently 1800..2100), rather than having an iterator that produces
 //! timespans indefinitely. Not only do we need a complete set of timespans
 //! for sorting, but it is not necessarily advisable to rely on offset
-//! changes so far into the future!
+//! changes so far into the future! The rule that a zone keeps following
+//! after that is available as a TZ string from the `posix` module.
 //!
 //! ### Example
 //!
@@ -158,10 +159,7 @@ impl TableTransitions for Table {
     fn timespans(&self, zone_name: &str) -> Option<FixedTimespanSet> {
         let mut builder = FixedTimespanSetBuilder::default();
 
-        let zoneset = match self.get_zoneset(zone_name) {
-            Some(zones) => zones,
-            None => return None,
-        };
+        let zoneset = self.get_zoneset(zone_name)?;
 
         for (i, zone_info) in zoneset.iter().enumerate() {
             let mut dst_offset = 0;
@@ -290,8 +288,6 @@ impl FixedTimespanSetBuilder {
         start_utc_offset: &mut i64,
         start_dst_offset: &mut i64,
     ) {
-        use std::mem::replace;
-
         for year in 1800..2100 {
             if use_until && year > timespan.end_time.unwrap().year() {
                 break;
@@ -335,15 +331,12 @@ impl FixedTimespanSetBuilder {
 
                 if *insert_start_transition {
                     if earliest_at < self.start_time.unwrap() {
-                        let _ = replace(start_utc_offset, timespan.offset);
-                        let _ = replace(start_dst_offset, *dst_offset);
-                        let _ = replace(
-                            start_zone_id,
-                            Some(
-                                timespan
-                                    .format
-                                    .format(*dst_offset, earliest_rule.letters.as_ref()),
-                            ),
+                        *start_utc_offset = timespan.offset;
+                        *start_dst_offset = *dst_offset;
+                        *start_zone_id = Some(
+                            timespan
+                                .format
+                                .format(*dst_offset, earliest_rule.letters.as_ref()),
                         );
                         continue;
                     }
@@ -351,13 +344,10 @@ impl FixedTimespanSetBuilder {
                     if start_zone_id.is_none()
                         && *start_utc_offset + *start_dst_offset == timespan.offset + *dst_offset
                     {
-                        let _ = replace(
-                            start_zone_id,
-                            Some(
-                                timespan
-                                    .format
-                                    .format(*dst_offset, earliest_rule.letters.as_ref()),
-                            ),
+                        *start_zone_id = Some(
+                            timespan
+                                .format
+                                .format(*dst_offset, earliest_rule.letters.as_ref()),
                         );
                     }
                 }
@@ -378,7 +368,7 @@ impl FixedTimespanSetBuilder {
     }
 
     fn build(mut self) -> FixedTimespanSet {
-        self.rest.sort_by(|a, b| a.0.cmp(&b.0));
+        self.rest.sort_by_key(|a| a.0);
 
         let first = match self.first {
             Some(ft) => ft,
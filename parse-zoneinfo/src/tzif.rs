@@ -0,0 +1,390 @@
+//! Writing timespan sets as compiled TZif files.
+//!
+//! TZif is the binary format of the files under `/usr/share/zoneinfo`, as
+//! produced by `zic` and described in [RFC 8536][rfc]. This module writes a
+//! `FixedTimespanSet` in that format, with a TZ string footer describing the
+//! time after its last transition.
+//!
+//! The output follows `zic -b slim`: the version 1 data block is left empty,
+//! and transitions that the footer already describes are left out, apart from
+//! the first one. Local time types are numbered in order of first use, which
+//! is usually, but not always, the order `zic` picks.
+//!
+//! ## Example
+//!
+//! ```
+//! use parse_zoneinfo::line::{Line, LineParser};
+//! use parse_zoneinfo::posix::TablePosix;
+//! use parse_zoneinfo::table::TableBuilder;
+//! use parse_zoneinfo::transitions::TableTransitions;
+//!
+//! let parser = LineParser::default();
+//! let mut builder = TableBuilder::new();
+//! for line in [
+//!     "Rule  NZ  2007  max  -  Sep  lastSun  2:00s  1:00  D",
+//!     "Rule  NZ  2008  max  -  Apr  Sun>=1   2:00s  0     S",
+//!     "Zone  Pacific/Auckland  12:00  NZ  NZ%sT",
+//! ] {
+//!     match parser.parse_str(line)? {
+//!         Line::Zone(zone) => builder.add_zone_line(zone).unwrap(),
+//!         Line::Rule(rule) => builder.add_rule_line(rule).unwrap(),
+//!         _ => unreachable!(),
+//!     }
+//! }
+//! let table = builder.build();
+//!
+//! let timespans = table.timespans("Pacific/Auckland").unwrap();
+//! let footer = table.posix_tz("Pacific/Auckland").unwrap();
+//! let mut data = Vec::new();
+//! parse_zoneinfo::tzif::write(&mut data, &timespans, Some(&footer)).unwrap();
+//!
+//! assert!(data.starts_with(b"TZif2"));
+//! assert!(data.ends_with(b"\nNZST-12NZDT,M9.5.0,M4.1.0/3\n"));
+//! # Ok::<(), parse_zoneinfo::line::Error>(())
+//! ```
+//!
+//! [rfc]: https://datatracker.ietf.org/doc/html/rfc8536
+
+use std::io::{self, Write};
+
+use crate::posix::{timespan_abbreviation, PosixTz};
+use crate::transitions::{FixedTimespan, FixedTimespanSet};
+
+/// A local time type, as stored in the file.
+#[derive(PartialEq, Debug, Clone)]
+struct LocalTimeType {
+    utc_offset: i32,
+    is_dst: bool,
+    abbreviation: String,
+}
+
+impl LocalTimeType {
+    fn new(timespan: &FixedTimespan) -> io::Result<LocalTimeType> {
+        let utc_offset = timespan.total_offset();
+        if utc_offset.abs() >= 24 * 60 * 60 {
+            return Err(invalid_input("UTC offset out of range"));
+        }
+        let abbreviation = timespan_abbreviation(timespan);
+        if !abbreviation.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(invalid_input("invalid time zone abbreviation"));
+        }
+        Ok(LocalTimeType {
+            utc_offset: utc_offset as i32,
+            is_dst: timespan.dst_offset != 0,
+            abbreviation,
+        })
+    }
+}
+
+/// Writes a set of timespans as a TZif file, followed by the given TZ string
+/// footer.
+///
+/// The footer should describe the time zone after the last transition in the
+/// set; trailing transitions that it predicts are left out of the file. An
+/// empty footer is written if there is none, telling readers that local time
+/// is unspecified after the last transition.
+///
+/// Version 3 of the format is used if the footer needs it, and version 2
+/// otherwise. Returns an error of the `InvalidInput` kind if the timespans
+/// can’t be represented in a TZif file, such as when there are more than
+/// 256 distinct ones.
+pub fn write<W: Write>(
+    mut out: W,
+    timespans: &FixedTimespanSet,
+    footer: Option<&PosixTz>,
+) -> io::Result<()> {
+    let mut rest = &timespans.rest[..];
+    if let Some(footer) = footer {
+        // Walk back over the transitions that the footer predicts, as long as
+        // each one leads straight to the next, without a year being skipped.
+        let mut first_predicted = rest.len();
+        while first_predicted > 0 {
+            let (time, timespan) = &rest[first_predicted - 1];
+            let leads_to_next = match rest.get(first_predicted) {
+                Some((next, _)) => footer.next_transition(*time) == Some(*next),
+                None => true,
+            };
+            if !leads_to_next || !footer.predicts(*time, timespan) {
+                break;
+            }
+            first_predicted -= 1;
+        }
+        if first_predicted + 1 < rest.len() {
+            rest = &rest[..first_predicted + 1];
+        }
+    }
+
+    // The first timespan becomes type 0, which applies before the first
+    // transition. The others are numbered in order of first use, and
+    // transitions that don’t change the type are merged away.
+    let mut types = vec![LocalTimeType::new(&timespans.first)?];
+    let mut transitions = Vec::with_capacity(rest.len());
+    let mut current = 0;
+    for (time, timespan) in rest {
+        let local_time_type = LocalTimeType::new(timespan)?;
+        let index = match types.iter().position(|t| *t == local_time_type) {
+            Some(index) => index,
+            None => {
+                types.push(local_time_type);
+                types.len() - 1
+            }
+        };
+        if index != current {
+            transitions.push((*time, index));
+            current = index;
+        }
+    }
+    if types.len() > 256 {
+        return Err(invalid_input("too many local time types"));
+    }
+
+    // Abbreviations are stored NUL-terminated, and can share the end of a
+    // longer abbreviation that’s already there.
+    let mut designations = Vec::<u8>::new();
+    let mut designation_indices = Vec::with_capacity(types.len());
+    for local_time_type in &types {
+        let abbreviation = local_time_type.abbreviation.as_bytes();
+        let existing = (0..designations.len()).find(|&i| {
+            designations[i..].starts_with(abbreviation)
+                && designations.get(i + abbreviation.len()) == Some(&0)
+        });
+        let index = match existing {
+            Some(index) => index,
+            None => {
+                designations.extend_from_slice(abbreviation);
+                designations.push(0);
+                designations.len() - abbreviation.len() - 1
+            }
+        };
+        if index > 255 {
+            return Err(invalid_input("too many time zone abbreviations"));
+        }
+        designation_indices.push(index as u8);
+    }
+
+    let version = match footer {
+        Some(footer) if footer.needs_version_3() => b'3',
+        _ => b'2',
+    };
+
+    // A minimal version 1 data block, with a single unnamed type, for readers
+    // that only understand 32-bit data.
+    write_header(&mut out, version, 0, 1, 1)?;
+    out.write_all(&[0, 0, 0, 0, 0, 0, 0])?;
+
+    write_header(
+        &mut out,
+        version,
+        transitions.len(),
+        types.len(),
+        designations.len(),
+    )?;
+    for (time, _) in &transitions {
+        out.write_all(&time.to_be_bytes())?;
+    }
+    for (_, index) in &transitions {
+        out.write_all(&[*index as u8])?;
+    }
+    for (local_time_type, index) in types.iter().zip(designation_indices) {
+        out.write_all(&local_time_type.utc_offset.to_be_bytes())?;
+        out.write_all(&[local_time_type.is_dst as u8, index])?;
+    }
+    out.write_all(&designations)?;
+
+    match footer {
+        Some(footer) => write!(out, "\n{}\n", footer),
+        None => out.write_all(b"\n\n"),
+    }
+}
+
+fn write_header<W: Write>(
+    out: &mut W,
+    version: u8,
+    transitions: usize,
+    types: usize,
+    designations: usize,
+) -> io::Result<()> {
+    let transitions =
+        u32::try_from(transitions).map_err(|_| invalid_input("too many transitions"))?;
+    out.write_all(b"TZif")?;
+    out.write_all(&[version])?;
+    out.write_all(&[0; 15])?;
+    // The counts of UT/local indicators, standard/wall indicators and leap
+    // seconds are always zero.
+    for count in [0, 0, 0, transitions, types as u32, designations as u32] {
+        out.write_all(&count.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::posix::{PosixDate, PosixDst, PosixTransition};
+
+    fn timespan(utc_offset: i64, dst_offset: i64, name: &str) -> FixedTimespan {
+        FixedTimespan {
+            utc_offset,
+            dst_offset,
+            name: name.to_owned(),
+        }
+    }
+
+    fn header(timecnt: u8, typecnt: u8, charcnt: u8) -> Vec<u8> {
+        let mut header = b"TZif2".to_vec();
+        header.extend_from_slice(&[0; 15]);
+        header.extend_from_slice(&[0; 12]);
+        for count in [timecnt, typecnt, charcnt] {
+            header.extend_from_slice(&[0, 0, 0, count]);
+        }
+        header
+    }
+
+    /// `GMT0BST,M3.5.0/1,M10.5.0`
+    fn london_footer() -> PosixTz {
+        let last_sunday = |month, time| PosixTransition {
+            date: PosixDate::MonthWeekday {
+                month,
+                week: 5,
+                weekday: 0,
+            },
+            time,
+        };
+        PosixTz {
+            std_abbreviation: "GMT".to_owned(),
+            std_offset: 0,
+            dst: Some(PosixDst {
+                abbreviation: "BST".to_owned(),
+                utc_offset: 3600,
+                start: last_sunday(3, 3600),
+                end: last_sunday(10, 7200),
+            }),
+        }
+    }
+
+    #[test]
+    fn fixed_offset() {
+        let timespans = FixedTimespanSet {
+            first: timespan(0, 0, "UTC"),
+            rest: vec![],
+        };
+        let footer = PosixTz {
+            std_abbreviation: "UTC".to_owned(),
+            std_offset: 0,
+            dst: None,
+        };
+        let mut data = Vec::new();
+        write(&mut data, &timespans, Some(&footer)).unwrap();
+
+        let mut expected = header(0, 1, 1);
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(&header(0, 1, 4));
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(b"UTC\0\nUTC0\n");
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn shared_designations() {
+        let timespans = FixedTimespanSet {
+            first: timespan(3600, 0, "CET"),
+            rest: vec![
+                (100, timespan(3600, 3600, "CEST")),
+                (200, timespan(3600, 0, "CET")),
+                (300, timespan(3600, 3600, "EST")),
+                (400, timespan(3600, 3600, "CEST")),
+            ],
+        };
+        let mut data = Vec::new();
+        write(&mut data, &timespans, None).unwrap();
+        assert!(data.ends_with(b"CET\0CEST\0\n\n"));
+
+        // The types are CET, CEST and EST, with EST sharing the end of CEST.
+        let types = &data[data.len() - 11 - 18..data.len() - 11];
+        assert_eq!(
+            types,
+            [0, 0, 14, 16, 0, 0, 0, 0, 28, 32, 1, 4, 0, 0, 28, 32, 1, 5]
+        );
+    }
+
+    #[test]
+    fn merged_transitions() {
+        let timespans = FixedTimespanSet {
+            first: timespan(0, 0, "GMT"),
+            rest: vec![
+                (100, timespan(0, 0, "GMT")),
+                (200, timespan(0, 3600, "BST")),
+            ],
+        };
+        let mut data = Vec::new();
+        write(&mut data, &timespans, None).unwrap();
+        // One transition and two types.
+        assert_eq!(
+            &data[44 + 7 + 32..44 + 7 + 44],
+            [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 8]
+        );
+    }
+
+    #[test]
+    fn predicted_transitions() {
+        let footer = london_footer();
+        let timespans = FixedTimespanSet {
+            first: timespan(0, 0, "GMT"),
+            rest: (2000..2100)
+                .flat_map(|year| {
+                    let (start, end) = footer.dst_transitions(year).unwrap();
+                    vec![
+                        (start, timespan(0, 3600, "BST")),
+                        (end, timespan(0, 0, "GMT")),
+                    ]
+                })
+                .collect(),
+        };
+        let mut data = Vec::new();
+        write(&mut data, &timespans, Some(&footer)).unwrap();
+        // Only the first transition is kept.
+        assert_eq!(&data[44 + 7 + 32..44 + 7 + 36], [0, 0, 0, 1]);
+        // 2000-03-26 01:00 UTC
+        assert_eq!(
+            &data[44 + 7 + 44..44 + 7 + 52],
+            954_032_400_i64.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn skipped_year() {
+        let footer = london_footer();
+        let transitions = |year| {
+            let (start, end) = footer.dst_transitions(year).unwrap();
+            vec![
+                (start, timespan(0, 3600, "BST")),
+                (end, timespan(0, 0, "GMT")),
+            ]
+        };
+        let timespans = FixedTimespanSet {
+            first: timespan(0, 0, "GMT"),
+            rest: [2000, 2001, 2003, 2004]
+                .iter()
+                .flat_map(|&year| transitions(year))
+                .collect(),
+        };
+        let mut data = Vec::new();
+        write(&mut data, &timespans, Some(&footer)).unwrap();
+        // The transitions up to 2003 are kept, as the footer would add some in 2002.
+        assert_eq!(&data[44 + 7 + 32..44 + 7 + 36], [0, 0, 0, 5]);
+    }
+
+    #[test]
+    fn offset_out_of_range() {
+        let timespans = FixedTimespanSet {
+            first: timespan(90_000, 0, "XXX"),
+            rest: vec![],
+        };
+        let error = write(Vec::new(), &timespans, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -77,6 +77,7 @@ fn one_transition() {
 }
 
 #[test]
+#[allow(clippy::zero_prefixed_literal)]
 fn two_transitions() {
     let zone_1 = ZoneInfo {
         offset: 1234,